@@ -32,15 +32,51 @@
 
 //! Actions invoked by command-line arguments.
 
-use crate::errors::Result;
+use crate::analyzer::analyze;
+use crate::differ::{diff_documents, report_json, report_side_by_side, report_text};
+use crate::document::Document;
+use crate::errors::*;
+use crate::exporter::export;
+use crate::formatter::format;
+use crate::highlighter::THEMES;
+use crate::linter::{fix, lint};
+use crate::merger::merge_documents;
+use crate::model::Orientation;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+use crate::testing::{parse_test_cases, run_test_cases};
 use crate::{Editor, ATTO_DESCRIPTION, ATTO_NAME, ATTO_VERSION};
-use clap::{arg, ArgMatches, Command};
+use clap::{arg, Arg, ArgAction, ArgMatches, Command};
 use ncurses::*;
+use std::{fs, process};
 
 /// Available command-line actions.
 enum CliAction {
-  /// Edit decision table loaded from file with specified name.
-  EditDecisionTable(String),
+  /// Edit decision tables loaded from files with specified names (`-` for standard input),
+  /// optionally copying to the terminal clipboard, optionally in read-only mode, highlighted using the named theme.
+  EditDecisionTables(Vec<String>, bool, bool, String),
+  /// Test decision table loaded from file with test cases loaded from another file.
+  TestDecisionTable(String, String),
+  /// Analyze decision table loaded from file with specified name.
+  AnalyzeDecisionTable(String),
+  /// Check decision table loaded from file for structural problems, optionally fix mechanical problems.
+  LintFile(String, bool),
+  /// Reformat decision tables loaded from files, optionally only check if they are formatted.
+  FormatFiles(Vec<String>, bool),
+  /// Switch orientation of all decision tables loaded from file, rules placed in rows are placed in columns and vice versa,
+  /// crosstab tables are converted into tables with rules placed in rows.
+  PivotDecisionTables(String),
+  /// Convert all decision tables loaded from file into crosstab tables.
+  CrosstabDecisionTables(String),
+  /// Sort rules of all decision tables loaded from file by the specified clauses.
+  SortDecisionTables(String, String),
+  /// Export decision tables loaded from file to DMN, optionally into the output file.
+  ExportDecisionTables(String, Option<String>),
+  /// Compare decision tables loaded from two files, the report is printed in the specified format.
+  DiffDecisionTables(String, String, String),
+  /// Merge decision tables loaded from files with our and their changes made to the base file,
+  /// optionally into the output file.
+  MergeDecisionTables(String, String, String, Option<String>),
 }
 
 /// Parses command-line arguments.
@@ -48,22 +84,218 @@ fn get_matches() -> ArgMatches {
   Command::new(ATTO_NAME)
     .version(ATTO_VERSION)
     .about(ATTO_DESCRIPTION)
-    .arg(arg!(<FILE>).help("File containing decision table to edit").required(true).index(1))
+    .args_conflicts_with_subcommands(true)
+    .subcommand_negates_reqs(true)
+    .arg(
+      arg!(<FILE> ...)
+        .help("Files containing decision tables to edit, - reads from standard input")
+        .required(true)
+        .index(1),
+    )
+    .arg(
+      arg!(--osc52)
+        .help("Copy also to the terminal clipboard using OSC 52 escape sequence")
+        .action(ArgAction::SetTrue),
+    )
+    .arg(arg!(-R - -"read-only").help("Open decision table in read-only mode").action(ArgAction::SetTrue))
+    .arg(theme_arg())
+    .subcommand(
+      Command::new("view")
+        .about("Opens decision table in read-only mode")
+        .arg(
+          arg!(--osc52)
+            .help("Copy also to the terminal clipboard using OSC 52 escape sequence")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(theme_arg())
+        .arg(
+          arg!(<FILE> ...)
+            .help("Files containing decision tables to view, - reads from standard input")
+            .required(true)
+            .index(1),
+        ),
+    )
+    .subcommand(
+      Command::new("test")
+        .about("Tests decision table with test cases loaded from CSV or YAML file")
+        .arg(
+          arg!(<FILE>)
+            .help("File containing decision table to test, the first table in file is tested")
+            .required(true)
+            .index(1),
+        )
+        .arg(arg!(<CASES>).help("File containing test cases (.csv, .yaml or .yml)").required(true).index(2)),
+    )
+    .subcommand(
+      Command::new("analyze")
+        .about("Reports overlapping rules, subsumed rules and gaps in input coverage")
+        .arg(arg!(<FILE>).help("File containing decision tables to analyze").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("lint")
+        .about("Reports structural problems in decision table")
+        .arg(arg!(--fix).help("Fix mechanical problems and save the file").action(ArgAction::SetTrue))
+        .arg(arg!(<FILE>).help("File containing decision tables to check").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("fmt")
+        .about("Reformats decision tables to canonical form")
+        .arg(
+          arg!(--check)
+            .help("Only check if files are formatted, do not save them")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(arg!(<FILE> ...).help("Files containing decision tables to format").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("pivot")
+        .about("Switches decision tables between rules placed in rows and rules placed in columns")
+        .arg(arg!(<FILE>).help("File containing decision tables to pivot").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("crosstab")
+        .about("Converts decision tables with two inputs and a single output into crosstab tables")
+        .arg(arg!(<FILE>).help("File containing decision tables to convert").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("sort")
+        .about("Sorts rules of decision tables by input and output clauses, and renumbers rules")
+        .arg(
+          arg!(--by <KEYS>)
+            .help("Names of clauses separated with commas, each optionally followed by asc or desc")
+            .required(true),
+        )
+        .arg(arg!(<FILE>).help("File containing decision tables to sort").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("export")
+        .about("Exports decision tables to DMN")
+        .arg(arg!(-o --output <OUTPUT>).help("File to save the DMN model, printed to standard output when not specified"))
+        .arg(arg!(<FILE>).help("File containing decision tables to export").required(true).index(1)),
+    )
+    .subcommand(
+      Command::new("diff")
+        .about("Reports differences between decision tables, rules are matched by content, not by numbers")
+        .arg(
+          arg!(--format <FORMAT>)
+            .help("Format of the report")
+            .value_parser(["text", "json", "side-by-side"])
+            .default_value("text"),
+        )
+        .arg(arg!(<OLD>).help("File containing the old version of decision tables").required(true).index(1))
+        .arg(arg!(<NEW>).help("File containing the new version of decision tables").required(true).index(2)),
+    )
+    .subcommand(
+      Command::new("merge")
+        .about("Merges changes made independently to decision tables, usable as a git merge driver: atto merge %O %A %B -o %A")
+        .arg(arg!(-o --output <OUTPUT>).help("File to save the merged decision tables, printed to standard output when not specified"))
+        .arg(
+          arg!(<BASE>)
+            .help("File containing the common base version of decision tables")
+            .required(true)
+            .index(1),
+        )
+        .arg(arg!(<OURS>).help("File containing our version of decision tables").required(true).index(2))
+        .arg(arg!(<THEIRS>).help("File containing their version of decision tables").required(true).index(3)),
+    )
     .get_matches()
 }
 
+/// Returns the argument selecting the color theme of the editor.
+fn theme_arg() -> Arg {
+  arg!(--theme <THEME>)
+    .help("Color theme used for highlighting decision tables, monochrome is used also when colors are not supported")
+    .value_parser(THEMES.map(|theme| theme.name))
+    .default_value(THEMES[0].name)
+}
+
 /// Checks arguments passed from the command line and returns a corresponding action.
 fn get_cli_action() -> CliAction {
   let matches = get_matches();
-  let file_name = matches.get_one::<String>("FILE").unwrap();
-  CliAction::EditDecisionTable(file_name.to_string())
+  match matches.subcommand() {
+    Some(("view", matches)) => {
+      let file_names = matches.get_many::<String>("FILE").unwrap().cloned().collect();
+      CliAction::EditDecisionTables(file_names, matches.get_flag("osc52"), true, theme_name(matches))
+    }
+    Some(("test", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      let cases_file_name = matches.get_one::<String>("CASES").unwrap();
+      CliAction::TestDecisionTable(file_name.to_string(), cases_file_name.to_string())
+    }
+    Some(("analyze", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      CliAction::AnalyzeDecisionTable(file_name.to_string())
+    }
+    Some(("lint", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      CliAction::LintFile(file_name.to_string(), matches.get_flag("fix"))
+    }
+    Some(("fmt", matches)) => {
+      let file_names = matches.get_many::<String>("FILE").unwrap().cloned().collect();
+      CliAction::FormatFiles(file_names, matches.get_flag("check"))
+    }
+    Some(("pivot", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      CliAction::PivotDecisionTables(file_name.to_string())
+    }
+    Some(("crosstab", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      CliAction::CrosstabDecisionTables(file_name.to_string())
+    }
+    Some(("sort", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      let keys = matches.get_one::<String>("by").unwrap();
+      CliAction::SortDecisionTables(file_name.to_string(), keys.to_string())
+    }
+    Some(("export", matches)) => {
+      let file_name = matches.get_one::<String>("FILE").unwrap();
+      CliAction::ExportDecisionTables(file_name.to_string(), matches.get_one::<String>("output").cloned())
+    }
+    Some(("diff", matches)) => {
+      let old_file_name = matches.get_one::<String>("OLD").unwrap();
+      let new_file_name = matches.get_one::<String>("NEW").unwrap();
+      let format = matches.get_one::<String>("format").unwrap();
+      CliAction::DiffDecisionTables(old_file_name.to_string(), new_file_name.to_string(), format.to_string())
+    }
+    Some(("merge", matches)) => {
+      let base_file_name = matches.get_one::<String>("BASE").unwrap();
+      let ours_file_name = matches.get_one::<String>("OURS").unwrap();
+      let theirs_file_name = matches.get_one::<String>("THEIRS").unwrap();
+      CliAction::MergeDecisionTables(
+        base_file_name.to_string(),
+        ours_file_name.to_string(),
+        theirs_file_name.to_string(),
+        matches.get_one::<String>("output").cloned(),
+      )
+    }
+    _ => {
+      let file_names = matches.get_many::<String>("FILE").unwrap().cloned().collect();
+      CliAction::EditDecisionTables(file_names, matches.get_flag("osc52"), matches.get_flag("read-only"), theme_name(&matches))
+    }
+  }
+}
+
+/// Returns the name of the selected color theme.
+fn theme_name(matches: &ArgMatches) -> String {
+  matches.get_one::<String>("theme").unwrap().to_string()
+}
+
+/// Loads a file with the specified name.
+fn load_file(file_name: &str) -> Result<String> {
+  fs::read_to_string(file_name).map_err(|e| err_load_file(file_name, &e.to_string()))
+}
+
+/// Saves a file with the specified name.
+fn save_file(file_name: &str, content: &str) -> Result<()> {
+  fs::write(file_name, content).map_err(|e| err_save_file(file_name, &e.to_string()))
 }
 
 /// Executes command-line action.
 pub fn do_action() -> Result<()> {
   match get_cli_action() {
-    CliAction::EditDecisionTable(file_name) => {
-      let mut editor = Editor::new(&file_name)?;
+    CliAction::EditDecisionTables(file_names, osc52, read_only, theme_name) => {
+      let mut editor = Editor::new(&file_names, osc52, read_only, &theme_name)?;
+      editor.check_table();
       editor.repaint_plane();
       editor.update_cursor();
       editor.update_cursor_coordinates();
@@ -71,5 +303,201 @@ pub fn do_action() -> Result<()> {
       editor.process_keystrokes();
       editor.finalize()
     }
+    CliAction::TestDecisionTable(file_name, cases_file_name) => {
+      let document = Document::new(&load_file(&file_name)?);
+      let decision_table = recognize(document.plane())?;
+      let test_cases = parse_test_cases(&cases_file_name, &load_file(&cases_file_name)?)?;
+      let (report, passed) = run_test_cases(&decision_table, &test_cases)?;
+      for line in report {
+        println!("{}", line);
+      }
+      if !passed {
+        process::exit(1);
+      }
+      Ok(())
+    }
+    CliAction::AnalyzeDecisionTable(file_name) => {
+      let document = Document::new(&load_file(&file_name)?);
+      let mut found = false;
+      for (index, plane) in document.planes().enumerate() {
+        let decision_table = recognize(plane)?;
+        let findings = analyze(&decision_table)?;
+        for finding in &findings {
+          if document.table_count() > 1 {
+            println!("table {}: {}", index + 1, finding.message);
+          } else {
+            println!("{}", finding.message);
+          }
+        }
+        found |= !findings.is_empty();
+      }
+      if found {
+        process::exit(1);
+      }
+      Ok(())
+    }
+    CliAction::LintFile(file_name, fix_issues) => {
+      let mut document = Document::new(&load_file(&file_name)?);
+      if fix_issues {
+        let fixed: usize = document.planes_mut().map(fix).sum();
+        if fixed > 0 {
+          let content = document.to_string();
+          save_file(&file_name, &content)?;
+          println!("{}: fixed {} problem(s)", file_name, fixed);
+          document = Document::new(&content);
+        }
+      }
+      let mut found = false;
+      for (index, plane) in document.planes().enumerate() {
+        for issue in lint(plane) {
+          let (line, column) = document.origin(index, issue.row, issue.col);
+          println!(
+            "{}:{}:{}: {}{}",
+            file_name,
+            line,
+            column,
+            issue.message,
+            if issue.fixable { " (fixable)" } else { "" }
+          );
+          found = true;
+        }
+      }
+      if found {
+        process::exit(1);
+      }
+      Ok(())
+    }
+    CliAction::FormatFiles(file_names, check) => {
+      let mut success = true;
+      for file_name in &file_names {
+        let content = load_file(file_name)?;
+        let mut document = Document::new(&content);
+        let mut recognized = true;
+        for index in 0..document.table_count() {
+          let result = recognize(document.planes().nth(index).unwrap());
+          match result {
+            Ok(decision_table) => document.replace_table(index, Plane::new(&format(&decision_table))),
+            Err(reason) => {
+              eprintln!("{}: {}", file_name, reason);
+              recognized = false;
+            }
+          }
+        }
+        if !recognized {
+          success = false;
+          continue;
+        }
+        let formatted = document.to_string();
+        if formatted != content {
+          if check {
+            println!("{}: not formatted", file_name);
+            success = false;
+          } else {
+            save_file(file_name, &formatted)?;
+            println!("{}: formatted", file_name);
+          }
+        }
+      }
+      if !success {
+        process::exit(1);
+      }
+      Ok(())
+    }
+    CliAction::PivotDecisionTables(file_name) => {
+      let mut document = Document::new(&load_file(&file_name)?);
+      for index in 0..document.table_count() {
+        let mut decision_table = recognize(document.planes().nth(index).unwrap())?;
+        decision_table.orientation = match decision_table.orientation {
+          Orientation::RuleAsRow => Orientation::RuleAsColumn,
+          Orientation::RuleAsColumn | Orientation::CrossTable => Orientation::RuleAsRow,
+        };
+        if decision_table.orientation == Orientation::RuleAsColumn && decision_table.output_clauses.len() > 1 {
+          if let Some(label) = decision_table.output_label.as_ref().filter(|label| !label.is_empty()) {
+            eprintln!(
+              "{}: output label '{}' is not displayed when rules are placed in columns",
+              file_name,
+              label.text()
+            );
+          }
+        }
+        document.replace_table(index, Plane::new(&format(&decision_table)));
+      }
+      save_file(&file_name, &document.to_string())?;
+      Ok(())
+    }
+    CliAction::CrosstabDecisionTables(file_name) => {
+      let mut document = Document::new(&load_file(&file_name)?);
+      for index in 0..document.table_count() {
+        let decision_table = recognize(document.planes().nth(index).unwrap())?;
+        if decision_table.orientation == Orientation::CrossTable {
+          continue;
+        }
+        if !decision_table.annotation_clauses.is_empty() {
+          eprintln!("{}: annotations are not displayed in crosstab tables", file_name);
+        }
+        document.replace_table(index, Plane::new(&format(&decision_table.to_crosstab()?)));
+      }
+      save_file(&file_name, &document.to_string())?;
+      Ok(())
+    }
+    CliAction::SortDecisionTables(file_name, keys) => {
+      let mut document = Document::new(&load_file(&file_name)?);
+      for plane in document.planes_mut() {
+        if let Some(warning) = plane.sort_rules(&keys)? {
+          eprintln!("{}: {}", file_name, warning);
+        }
+      }
+      save_file(&file_name, &document.to_string())?;
+      Ok(())
+    }
+    CliAction::ExportDecisionTables(file_name, output_file_name) => {
+      let document = Document::new(&load_file(&file_name)?);
+      let decision_tables = document.planes().map(recognize).collect::<Result<Vec<_>>>()?;
+      let name = std::path::Path::new(&file_name)
+        .file_stem()
+        .map_or(file_name.clone(), |stem| stem.to_string_lossy().to_string());
+      let xml = export(&name, &decision_tables);
+      match output_file_name {
+        Some(output_file_name) => save_file(&output_file_name, &xml),
+        None => {
+          print!("{}", xml);
+          Ok(())
+        }
+      }
+    }
+    CliAction::DiffDecisionTables(old_file_name, new_file_name, format) => {
+      let old = Document::new(&load_file(&old_file_name)?);
+      let new = Document::new(&load_file(&new_file_name)?);
+      let tables = diff_documents(&old, &new)?;
+      match format.as_str() {
+        "json" => print!("{}", report_json(&tables)),
+        "side-by-side" => {
+          for line in report_side_by_side(&old, &new, &tables) {
+            println!("{}", line);
+          }
+        }
+        _ => {
+          for line in report_text(&tables, old.table_count().max(new.table_count()) > 1) {
+            println!("{}", line);
+          }
+        }
+      }
+      if !tables.is_empty() {
+        process::exit(1);
+      }
+      Ok(())
+    }
+    CliAction::MergeDecisionTables(base_file_name, ours_file_name, theirs_file_name, output_file_name) => {
+      let (merged, conflicts) = merge_documents(&load_file(&base_file_name)?, &load_file(&ours_file_name)?, &load_file(&theirs_file_name)?);
+      match output_file_name {
+        Some(output_file_name) => save_file(&output_file_name, &merged)?,
+        None => print!("{}", merged),
+      }
+      if conflicts > 0 {
+        eprintln!("{}: {} conflict(s) marked", ours_file_name, conflicts);
+        process::exit(1);
+      }
+      Ok(())
+    }
   }
 }
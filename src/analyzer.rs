@@ -0,0 +1,347 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Completeness and consistency analysis of decision tables.
+//!
+//! The domain of each input is divided into elementary segments, bounded by values
+//! used in input entries and in allowed values; segments outside allowed values are removed.
+//! Each input entry covers a set of segments, so each rule covers a box
+//! in the space of all inputs. Overlapping rules, subsumed rules and gaps
+//! in input coverage are detected by comparing these boxes, overlapping and subsumed
+//! rules are reported only when they are not allowed by the hit policy.
+
+use crate::errors::*;
+use crate::feel::*;
+use crate::model::*;
+
+/// Maximum number of boxes processed while searching for gaps.
+const MAX_GAP_BOXES: usize = 10_000;
+
+/// Single problem found during the analysis.
+pub struct Finding {
+  /// Indexes of rules related to this finding.
+  pub rules: Vec<usize>,
+  /// Description of the finding.
+  pub message: String,
+}
+
+/// Elementary segment of the input domain.
+#[derive(Clone)]
+enum Segment {
+  /// Single number.
+  Point(f64),
+  /// Open interval between two numbers, `None` means infinity.
+  Between(Option<f64>, Option<f64>),
+  /// Single non-numeric value.
+  Value(Value),
+  /// All values not explicitly mentioned in input entries.
+  Other(Vec<Value>),
+}
+
+impl Segment {
+  /// Returns the value representing all values in the segment.
+  fn representative(&self) -> Value {
+    match self {
+      Segment::Point(point) => Value::Number(*point),
+      Segment::Between(Some(start), Some(end)) => Value::Number((start + end) / 2.0),
+      Segment::Between(None, Some(end)) => Value::Number(end - 1.0),
+      Segment::Between(Some(start), None) => Value::Number(start + 1.0),
+      Segment::Between(None, None) => Value::Number(0.0),
+      Segment::Value(value) => value.clone(),
+      Segment::Other(_) => Value::String("\u{0}".to_string()),
+    }
+  }
+}
+
+/// Set of segments covered by an input entry, one flag per segment.
+type Set = Vec<bool>;
+
+/// Product of sets covered by a rule, one set per input.
+type Cube = Vec<Set>;
+
+/// Analyzes the decision table, returns overlapping rules, subsumed rules and gaps in input coverage.
+pub fn analyze(decision_table: &DecisionTable) -> Result<Vec<Finding>> {
+  let mut domains = vec![];
+  let mut cubes: Vec<Cube> = vec![vec![]; decision_table.rules.len()];
+  for (input_index, input_clause) in decision_table.input_clauses.iter().enumerate() {
+    let mut tests = vec![];
+    for rule in &decision_table.rules {
+      tests.push(UnaryTests::parse(&rule.input_entries[input_index].text())?);
+    }
+    let allowed_values = match &input_clause.allowed_values {
+      Some(cell) if !cell.is_empty() => Some(UnaryTests::parse(&cell.text())?),
+      _ => None,
+    };
+    // bounds of allowed values are bounds of segments, so each segment is entirely allowed or not
+    let domain: Vec<Segment> = segments(tests.iter().chain(&allowed_values))
+      .into_iter()
+      .filter(|segment| allowed_values.as_ref().is_none_or(|allowed| allowed.matches(&segment.representative())))
+      .collect();
+    for (cube, test) in cubes.iter_mut().zip(&tests) {
+      cube.push(domain.iter().map(|segment| test.matches(&segment.representative())).collect());
+    }
+    domains.push(domain);
+  }
+  let mut findings = vec![];
+  // rules are reported by numbers placed in the table, not by their positions
+  let number = |index: usize| decision_table.rules[index].number.text();
+  // overlapping rules, overlaps are intended with all hit policies except U, and A when outputs differ
+  for i in 0..cubes.len() {
+    for j in i + 1..cubes.len() {
+      if intersects(&cubes[i], &cubes[j]) {
+        let violated = match decision_table.hit_policy {
+          HitPolicy::Unique => Some("U"),
+          HitPolicy::Any if !equal_outputs(&decision_table.rules[i], &decision_table.rules[j]) => Some("A"),
+          _ => None,
+        };
+        if let Some(hit_policy) = violated {
+          findings.push(Finding {
+            rules: vec![i, j],
+            message: format!("rules {} and {} overlap (violates hit policy {})", number(i), number(j), hit_policy),
+          });
+        }
+      }
+    }
+  }
+  // subsumed rules, with hit policy F only rules subsumed by preceding rules never match,
+  // with hit policies selecting by priority or collecting outputs of multiple rules subsumed rules are intended
+  let reported = |i: usize, j: usize| match decision_table.hit_policy {
+    HitPolicy::Unique | HitPolicy::Any => true,
+    HitPolicy::First => j < i,
+    _ => false,
+  };
+  for i in 0..cubes.len() {
+    for j in 0..cubes.len() {
+      if i != j && reported(i, j) && is_subset(&cubes[i], &cubes[j]) && (j < i || !is_subset(&cubes[j], &cubes[i])) {
+        findings.push(Finding {
+          rules: vec![i, j],
+          message: format!("rule {} is subsumed by rule {}", number(i), number(j)),
+        });
+        break;
+      }
+    }
+  }
+  // gaps in input coverage
+  let names: Vec<String> = decision_table
+    .input_clauses
+    .iter()
+    .map(|input_clause| input_clause.input_expression.text())
+    .collect();
+  let (gaps, complete) = gaps(&domains, &cubes);
+  for gap in gaps {
+    let conditions: Vec<String> = names
+      .iter()
+      .zip(domains.iter().zip(&gap))
+      .filter(|(_, (domain, set))| !set.iter().all(|flag| *flag) || domain.is_empty())
+      .map(|(name, (domain, set))| format!("{} = {}", name, describe(domain, set)))
+      .collect();
+    findings.push(Finding {
+      rules: vec![],
+      message: format!(
+        "no rule matches: {}",
+        if conditions.is_empty() { "-".to_string() } else { conditions.join(", ") }
+      ),
+    });
+  }
+  if !complete {
+    findings.push(Finding {
+      rules: vec![],
+      message: "too many gaps, search was stopped".to_string(),
+    });
+  }
+  Ok(findings)
+}
+
+/// Divides the domain of an input into elementary segments based on values used in unary tests.
+fn segments<'a>(tests: impl Iterator<Item = &'a UnaryTests>) -> Vec<Segment> {
+  let mut numbers: Vec<f64> = vec![];
+  let mut values: Vec<Value> = vec![];
+  for value in tests.flat_map(|test| test.values()) {
+    match value {
+      Value::Number(number) => {
+        if !numbers.contains(number) {
+          numbers.push(*number);
+        }
+      }
+      other => {
+        if !values.contains(other) {
+          values.push(other.clone());
+        }
+      }
+    }
+  }
+  numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+  let mut segments = vec![];
+  if !numbers.is_empty() {
+    let mut start = None;
+    for number in &numbers {
+      segments.push(Segment::Between(start, Some(*number)));
+      segments.push(Segment::Point(*number));
+      start = Some(*number);
+    }
+    segments.push(Segment::Between(start, None));
+  }
+  let booleans = values.contains(&Value::Boolean(true)) && values.contains(&Value::Boolean(false));
+  let only_booleans = values.iter().all(|value| matches!(value, Value::Boolean(_)));
+  for value in &values {
+    segments.push(Segment::Value(value.clone()));
+  }
+  let other = if values.is_empty() {
+    numbers.is_empty()
+  } else {
+    !(booleans && only_booleans)
+  };
+  if other {
+    segments.push(Segment::Other(values));
+  }
+  segments
+}
+
+/// Returns `true` when both rules have equal output entries.
+fn equal_outputs(a: &DecisionRule, b: &DecisionRule) -> bool {
+  a.output_entries
+    .iter()
+    .map(|cell| cell.text())
+    .eq(b.output_entries.iter().map(|cell| cell.text()))
+}
+
+/// Returns `true` when two cubes have common part.
+fn intersects(a: &Cube, b: &Cube) -> bool {
+  a.iter().zip(b).all(|(x, y)| x.iter().zip(y).any(|(p, q)| *p && *q))
+}
+
+/// Returns `true` when cube `a` is fully contained in cube `b`.
+fn is_subset(a: &Cube, b: &Cube) -> bool {
+  a.iter().zip(b).all(|(x, y)| x.iter().zip(y).all(|(p, q)| !*p || *q))
+}
+
+/// Returns `true` when the cube contains no segments.
+fn is_empty(cube: &Cube) -> bool {
+  cube.iter().any(|set| set.iter().all(|flag| !*flag))
+}
+
+/// Finds cubes not covered by any rule, returns `false` when the search was stopped.
+fn gaps(domains: &[Vec<Segment>], cubes: &[Cube]) -> (Vec<Cube>, bool) {
+  let full: Cube = domains.iter().map(|domain| vec![true; domain.len()]).collect();
+  if is_empty(&full) {
+    return (vec![], true);
+  }
+  let mut uncovered = vec![full];
+  for rule_cube in cubes {
+    let mut remaining = vec![];
+    for cube in uncovered {
+      if !intersects(&cube, rule_cube) {
+        remaining.push(cube);
+        continue;
+      }
+      // split the cube into disjoint parts placed outside the rule
+      let mut rest = cube;
+      for dimension in 0..rest.len() {
+        let mut part = rest.clone();
+        part[dimension] = rest[dimension].iter().zip(&rule_cube[dimension]).map(|(p, q)| *p && !*q).collect();
+        if !is_empty(&part) {
+          remaining.push(part);
+        }
+        rest[dimension] = rest[dimension].iter().zip(&rule_cube[dimension]).map(|(p, q)| *p && *q).collect();
+      }
+    }
+    if remaining.len() > MAX_GAP_BOXES {
+      remaining.truncate(MAX_GAP_BOXES);
+      return (remaining, false);
+    }
+    uncovered = remaining;
+  }
+  (uncovered, true)
+}
+
+/// Describes a set of segments as unary tests.
+fn describe(domain: &[Segment], set: &Set) -> String {
+  if set.iter().all(|flag| *flag) {
+    return "-".to_string();
+  }
+  let mut items = vec![];
+  let mut run: Option<(&Segment, &Segment)> = None;
+  let flush = |run: &mut Option<(&Segment, &Segment)>, items: &mut Vec<String>| {
+    if let Some((first, last)) = run.take() {
+      items.push(describe_range(first, last));
+    }
+  };
+  for (segment, flag) in domain.iter().zip(set) {
+    match segment {
+      Segment::Point(_) | Segment::Between(_, _) if *flag => {
+        run = Some((run.map_or(segment, |(first, _)| first), segment));
+      }
+      Segment::Value(value) if *flag => {
+        flush(&mut run, &mut items);
+        items.push(value.to_string());
+      }
+      Segment::Other(values) if *flag => {
+        flush(&mut run, &mut items);
+        items.push(format!(
+          "not({})",
+          values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")
+        ));
+      }
+      _ => flush(&mut run, &mut items),
+    }
+  }
+  flush(&mut run, &mut items);
+  items.join(", ")
+}
+
+/// Describes a range of numeric segments as a single unary test.
+fn describe_range(first: &Segment, last: &Segment) -> String {
+  let (start, start_closed) = match first {
+    Segment::Point(point) => (Some(*point), true),
+    Segment::Between(start, _) => (*start, false),
+    _ => (None, false),
+  };
+  let (end, end_closed) = match last {
+    Segment::Point(point) => (Some(*point), true),
+    Segment::Between(_, end) => (*end, false),
+    _ => (None, false),
+  };
+  let number = |value: f64| Value::Number(value).to_string();
+  match (start, end) {
+    (None, None) => "-".to_string(),
+    (None, Some(end)) => format!("{}{}", if end_closed { "<=" } else { "<" }, number(end)),
+    (Some(start), None) => format!("{}{}", if start_closed { ">=" } else { ">" }, number(start)),
+    (Some(start), Some(end)) if start == end => number(start),
+    (Some(start), Some(end)) => format!(
+      "{}{}..{}{}",
+      if start_closed { "[" } else { "(" },
+      number(start),
+      number(end),
+      if end_closed { "]" } else { ")" }
+    ),
+  }
+}
@@ -0,0 +1,84 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Box-drawing characters used to draw decision tables.
+
+/// Checks if the specified character is a box-drawing character.
+macro_rules! is_box_drawing_character {
+  ($ch:expr) => {
+    match $ch {
+      '┌' | '┐' | '└' | '┘' | '─' | '│' | '├' | '┤' | '┴' | '┬' | '┼' | '╪' | '╫' | '╬' | '╞' | '╡' | '╥' | '╨' | '═' | '║' | '╟' | '╢' | '╤' | '╧' => true,
+      _ => false,
+    }
+  };
+}
+
+pub(crate) use is_box_drawing_character;
+
+/// Box-drawing characters with weights of their arms: up, right, down, left
+/// (0 - no line, 1 - single line, 2 - double line).
+const ARMS: [(char, [u8; 4]); 24] = [
+  ('─', [0, 1, 0, 1]),
+  ('│', [1, 0, 1, 0]),
+  ('┌', [0, 1, 1, 0]),
+  ('┐', [0, 0, 1, 1]),
+  ('└', [1, 1, 0, 0]),
+  ('┘', [1, 0, 0, 1]),
+  ('├', [1, 1, 1, 0]),
+  ('┤', [1, 0, 1, 1]),
+  ('┬', [0, 1, 1, 1]),
+  ('┴', [1, 1, 0, 1]),
+  ('┼', [1, 1, 1, 1]),
+  ('═', [0, 2, 0, 2]),
+  ('║', [2, 0, 2, 0]),
+  ('╞', [1, 2, 1, 0]),
+  ('╡', [1, 0, 1, 2]),
+  ('╪', [1, 2, 1, 2]),
+  ('╫', [2, 1, 2, 1]),
+  ('╬', [2, 2, 2, 2]),
+  ('╥', [0, 1, 2, 1]),
+  ('╨', [2, 1, 0, 1]),
+  ('╟', [2, 1, 2, 0]),
+  ('╢', [2, 0, 2, 1]),
+  ('╤', [0, 2, 1, 2]),
+  ('╧', [1, 2, 0, 2]),
+];
+
+/// Returns the weights of arms of the character.
+pub fn arms_of(ch: char) -> [u8; 4] {
+  ARMS.iter().find(|(c, _)| *c == ch).map_or([0; 4], |(_, arms)| *arms)
+}
+
+/// Returns the box-drawing character having specified arms.
+pub fn junction(arms: [u8; 4]) -> Option<char> {
+  ARMS.iter().find(|(_, a)| *a == arms).map(|(ch, _)| *ch)
+}
@@ -0,0 +1,566 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Structural comparison of decision tables.
+//!
+//! Clauses are matched by kind and name, rules are matched by the content of entries
+//! in common clauses (not by rule numbers), so renumbering, reordering or reformatting
+//! the table does not produce any differences.
+
+use crate::document::Document;
+use crate::errors::*;
+use crate::model::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+use crate::unicode::CH_WIDE;
+use std::fmt;
+use std::fmt::Display;
+
+/// Escape sequence coloring removed parts of tables in the terminal (red).
+const COLOR_REMOVED: &str = "\x1b[31m";
+
+/// Escape sequence coloring added parts of tables in the terminal (green).
+const COLOR_ADDED: &str = "\x1b[32m";
+
+/// Escape sequence coloring changed parts of tables in the terminal (yellow).
+const COLOR_CHANGED: &str = "\x1b[33m";
+
+/// Escape sequence restoring the default color in the terminal.
+const COLOR_RESET: &str = "\x1b[0m";
+
+/// Separator placed between tables displayed side by side.
+const SEPARATOR: &str = "   ";
+
+/// Changed entry of the rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellChange {
+  /// Name of the clause.
+  pub clause: String,
+  /// Text of the entry in the old table.
+  pub old: String,
+  /// Text of the entry in the new table.
+  pub new: String,
+}
+
+/// Semantic change between two decision tables, rules are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+  /// The table is present only in the new document.
+  TableAdded,
+  /// The table is present only in the old document.
+  TableRemoved,
+  /// The name of the information item was changed from the first text to the second one.
+  InformationItemNameChanged(String, String),
+  /// The hit policy was changed from the first notation to the second one.
+  HitPolicyChanged(String, String),
+  /// The clause of the specified kind and name was added.
+  ClauseAdded(ClauseKind, String),
+  /// The clause of the specified kind and name was removed.
+  ClauseRemoved(ClauseKind, String),
+  /// Allowed values of the clause were changed from the first text to the second one.
+  AllowedValuesChanged(ClauseKind, String, String, String),
+  /// The rule with the specified number in the new table and with specified entries was added.
+  RuleAdded(usize, Vec<String>),
+  /// The rule with the specified number in the old table and with specified entries was removed.
+  RuleRemoved(usize, Vec<String>),
+  /// Entries of the rule were changed, numbers of the rule in the old and the new table are given.
+  RuleChanged(usize, usize, Vec<CellChange>),
+}
+
+impl Display for Change {
+  /// Converts a [Change] into a single-line message.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::TableAdded => write!(f, "table added"),
+      Self::TableRemoved => write!(f, "table removed"),
+      Self::InformationItemNameChanged(old, new) => write!(f, "information item name changed from '{}' to '{}'", old, new),
+      Self::HitPolicyChanged(old, new) => write!(f, "hit policy changed from '{}' to '{}'", old, new),
+      Self::ClauseAdded(kind, name) => write!(f, "{} clause '{}' added", kind, name),
+      Self::ClauseRemoved(kind, name) => write!(f, "{} clause '{}' removed", kind, name),
+      Self::AllowedValuesChanged(kind, name, old, new) => {
+        write!(f, "allowed values of {} clause '{}' changed from '{}' to '{}'", kind, name, old, new)
+      }
+      Self::RuleAdded(number, entries) => write!(f, "rule {} added: {}", number, entries.join(" | ")),
+      Self::RuleRemoved(number, entries) => write!(f, "rule {} removed: {}", number, entries.join(" | ")),
+      Self::RuleChanged(old_number, new_number, cells) => {
+        write!(f, "rule {} changed", old_number)?;
+        if old_number != new_number {
+          write!(f, " (rule {} in the new table)", new_number)?;
+        }
+        let cells: Vec<String> = cells
+          .iter()
+          .map(|cell| format!("{} from '{}' to '{}'", cell.clause, cell.old, cell.new))
+          .collect();
+        write!(f, ": {}", cells.join(", "))
+      }
+    }
+  }
+}
+
+/// Single difference between two decision tables, with regions of cells affected by the change.
+#[derive(Debug, Clone)]
+pub struct Difference {
+  /// Semantic change.
+  pub change: Change,
+  /// Regions of the old table affected by the change.
+  pub old_regions: Vec<Region>,
+  /// Regions of the new table affected by the change.
+  pub new_regions: Vec<Region>,
+}
+
+impl Difference {
+  /// Creates a difference affecting the specified regions.
+  fn new(change: Change, old_regions: Vec<Region>, new_regions: Vec<Region>) -> Self {
+    Self {
+      change,
+      old_regions,
+      new_regions,
+    }
+  }
+}
+
+/// Differences between decision tables placed at the same position in compared documents.
+pub struct TableDiff {
+  /// Index of the decision table in both documents.
+  pub index: usize,
+  /// Differences found in the decision table.
+  pub differences: Vec<Difference>,
+}
+
+/// Compares all decision tables of two documents, tables are paired by their position in documents.
+/// Returns only tables having differences.
+pub fn diff_documents(old: &Document, new: &Document) -> Result<Vec<TableDiff>> {
+  let old_tables = old.planes().map(recognize).collect::<Result<Vec<_>>>()?;
+  let new_tables = new.planes().map(recognize).collect::<Result<Vec<_>>>()?;
+  let mut tables = vec![];
+  for index in 0..old_tables.len().max(new_tables.len()) {
+    let differences = match (old_tables.get(index), new_tables.get(index)) {
+      (Some(old), Some(new)) => diff(old, new),
+      (Some(_), None) => vec![Difference::new(Change::TableRemoved, whole(old, index), vec![])],
+      _ => vec![Difference::new(Change::TableAdded, vec![], whole(new, index))],
+    };
+    if !differences.is_empty() {
+      tables.push(TableDiff { index, differences });
+    }
+  }
+  Ok(tables)
+}
+
+/// Compares two decision tables and returns semantic differences between them.
+pub fn diff(old: &DecisionTable, new: &DecisionTable) -> Vec<Difference> {
+  let mut differences = vec![];
+  let old_name = old.information_item_name.as_ref().map_or(String::new(), |cell| normalized(&cell.text()));
+  let new_name = new.information_item_name.as_ref().map_or(String::new(), |cell| normalized(&cell.text()));
+  if old_name != new_name {
+    differences.push(Difference::new(
+      Change::InformationItemNameChanged(old_name, new_name),
+      old.information_item_name.iter().map(|cell| cell.region).collect(),
+      new.information_item_name.iter().map(|cell| cell.region).collect(),
+    ));
+  }
+  if old.hit_policy != new.hit_policy {
+    differences.push(Difference::new(
+      Change::HitPolicyChanged(old.hit_policy.to_string(), new.hit_policy.to_string()),
+      vec![old.hit_policy_cell.region],
+      vec![new.hit_policy_cell.region],
+    ));
+  }
+  let old_clauses = old.clauses();
+  let new_clauses = new.clauses();
+  let clause_pairs = pair_clauses(&old_clauses, &new_clauses);
+  let mut common: Vec<(&Clause, &Clause)> = vec![];
+  for (old_clause, new_index) in old_clauses.iter().zip(&clause_pairs) {
+    match new_index {
+      Some(index) => {
+        let new_clause = &new_clauses[*index];
+        common.push((old_clause, new_clause));
+        if allowed_values(old_clause) != allowed_values(new_clause) {
+          differences.push(Difference::new(
+            Change::AllowedValuesChanged(
+              old_clause.kind,
+              normalized(&old_clause.name),
+              allowed_values(old_clause),
+              allowed_values(new_clause),
+            ),
+            old_clause.name_cell.map(|cell| cell.region).into_iter().collect(),
+            new_clause.name_cell.map(|cell| cell.region).into_iter().collect(),
+          ));
+        }
+      }
+      None => differences.push(Difference::new(
+        Change::ClauseRemoved(old_clause.kind, normalized(&old_clause.name)),
+        old_clause.name_cell.map(|cell| cell.region).into_iter().collect(),
+        vec![],
+      )),
+    }
+  }
+  for (_, new_clause) in new_clauses.iter().enumerate().filter(|(index, _)| !clause_pairs.contains(&Some(*index))) {
+    differences.push(Difference::new(
+      Change::ClauseAdded(new_clause.kind, normalized(&new_clause.name)),
+      vec![],
+      new_clause.name_cell.map(|cell| cell.region).into_iter().collect(),
+    ));
+  }
+  let pairs = match_rules(old, new, &common);
+  for (old_index, new_index) in pairs.iter().enumerate() {
+    let old_rule = &old.rules[old_index];
+    match new_index {
+      Some(new_index) => {
+        let new_rule = &new.rules[*new_index];
+        let mut cells = vec![];
+        let mut old_regions = vec![];
+        let mut new_regions = vec![];
+        for (old_clause, new_clause) in &common {
+          let old_cell = old_rule.entry(old_clause);
+          let new_cell = new_rule.entry(new_clause);
+          let (old_text, new_text) = (normalized(&old_cell.text()), normalized(&new_cell.text()));
+          if old_text != new_text {
+            cells.push(CellChange {
+              clause: normalized(&old_clause.name),
+              old: old_text,
+              new: new_text,
+            });
+            old_regions.push(old_cell.region);
+            new_regions.push(new_cell.region);
+          }
+        }
+        if !cells.is_empty() {
+          differences.push(Difference::new(
+            Change::RuleChanged(old_index + 1, new_index + 1, cells),
+            old_regions,
+            new_regions,
+          ));
+        }
+      }
+      None => differences.push(Difference::new(
+        Change::RuleRemoved(old_index + 1, entries(old_rule, &old_clauses)),
+        vec![old_rule.region],
+        vec![],
+      )),
+    }
+  }
+  for (new_index, new_rule) in new.rules.iter().enumerate() {
+    if !pairs.contains(&Some(new_index)) {
+      differences.push(Difference::new(
+        Change::RuleAdded(new_index + 1, entries(new_rule, &new_clauses)),
+        vec![],
+        vec![new_rule.region],
+      ));
+    }
+  }
+  differences
+}
+
+/// Returns lines of the text report, prefixed with the table number when there are more tables.
+pub fn report_text(tables: &[TableDiff], multiple_tables: bool) -> Vec<String> {
+  let mut lines = vec![];
+  for table in tables {
+    for difference in &table.differences {
+      if multiple_tables {
+        lines.push(format!("table {}: {}", table.index + 1, difference.change));
+      } else {
+        lines.push(difference.change.to_string());
+      }
+    }
+  }
+  lines
+}
+
+/// Returns the report in JSON format, a list of objects describing changes.
+pub fn report_json(tables: &[TableDiff]) -> String {
+  let mut objects = vec![];
+  for table in tables {
+    for difference in &table.differences {
+      let mut fields = vec![("table".to_string(), (table.index + 1).to_string())];
+      let mut field = |name: &str, value: String| fields.push((name.to_string(), value));
+      match &difference.change {
+        Change::TableAdded => field("change", json_string("tableAdded")),
+        Change::TableRemoved => field("change", json_string("tableRemoved")),
+        Change::InformationItemNameChanged(old, new) => {
+          field("change", json_string("informationItemNameChanged"));
+          field("old", json_string(old));
+          field("new", json_string(new));
+        }
+        Change::HitPolicyChanged(old, new) => {
+          field("change", json_string("hitPolicyChanged"));
+          field("old", json_string(old));
+          field("new", json_string(new));
+        }
+        Change::ClauseAdded(kind, name) => {
+          field("change", json_string("clauseAdded"));
+          field("kind", json_string(&kind.to_string()));
+          field("clause", json_string(name));
+        }
+        Change::ClauseRemoved(kind, name) => {
+          field("change", json_string("clauseRemoved"));
+          field("kind", json_string(&kind.to_string()));
+          field("clause", json_string(name));
+        }
+        Change::AllowedValuesChanged(kind, name, old, new) => {
+          field("change", json_string("allowedValuesChanged"));
+          field("kind", json_string(&kind.to_string()));
+          field("clause", json_string(name));
+          field("old", json_string(old));
+          field("new", json_string(new));
+        }
+        Change::RuleAdded(number, entries) => {
+          field("change", json_string("ruleAdded"));
+          field("newRule", number.to_string());
+          field("entries", json_list(entries.iter().map(|entry| json_string(entry))));
+        }
+        Change::RuleRemoved(number, entries) => {
+          field("change", json_string("ruleRemoved"));
+          field("oldRule", number.to_string());
+          field("entries", json_list(entries.iter().map(|entry| json_string(entry))));
+        }
+        Change::RuleChanged(old_number, new_number, cells) => {
+          field("change", json_string("ruleChanged"));
+          field("oldRule", old_number.to_string());
+          field("newRule", new_number.to_string());
+          field(
+            "cells",
+            json_list(cells.iter().map(|cell| {
+              json_object(&[
+                ("clause".to_string(), json_string(&cell.clause)),
+                ("old".to_string(), json_string(&cell.old)),
+                ("new".to_string(), json_string(&cell.new)),
+              ])
+            })),
+          );
+        }
+      }
+      objects.push(json_object(&fields));
+    }
+  }
+  if objects.is_empty() {
+    "[]\n".to_string()
+  } else {
+    format!("[\n  {}\n]\n", objects.join(",\n  "))
+  }
+}
+
+/// Returns lines displaying compared tables side by side, removed parts are colored red,
+/// added parts green and changed parts yellow, followed by the text report.
+pub fn report_side_by_side(old: &Document, new: &Document, tables: &[TableDiff]) -> Vec<String> {
+  let mut lines = vec![];
+  let multiple_tables = old.table_count().max(new.table_count()) > 1;
+  for table in tables {
+    if multiple_tables {
+      lines.push(format!("table {}:", table.index + 1));
+    }
+    let old_plane = old.planes().nth(table.index);
+    let new_plane = new.planes().nth(table.index);
+    let old_rows = old_plane.map_or(vec![], |plane| plane.chars.clone());
+    let new_rows = new_plane.map_or(vec![], |plane| plane.chars.clone());
+    let mut old_colors = colors_of(&old_rows);
+    let mut new_colors = colors_of(&new_rows);
+    for difference in &table.differences {
+      let color = match difference.change {
+        Change::TableRemoved | Change::ClauseRemoved(..) | Change::RuleRemoved(..) => COLOR_REMOVED,
+        Change::TableAdded | Change::ClauseAdded(..) | Change::RuleAdded(..) => COLOR_ADDED,
+        _ => COLOR_CHANGED,
+      };
+      paint(&mut old_colors, &difference.old_regions, color);
+      paint(&mut new_colors, &difference.new_regions, color);
+    }
+    let old_width = old_rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    for index in 0..old_rows.len().max(new_rows.len()) {
+      let left = render_row(old_plane, index, old_colors.get(index), old_width);
+      let right = render_row(new_plane, index, new_colors.get(index), 0);
+      lines.push(format!("{}{}{}", left, SEPARATOR, right).trim_end().to_string());
+    }
+    lines.push(String::new());
+  }
+  lines.extend(report_text(tables, multiple_tables));
+  lines
+}
+
+/// Returns the region covering the whole plane of the table with the specified index.
+fn whole(document: &Document, index: usize) -> Vec<Region> {
+  document
+    .planes()
+    .nth(index)
+    .filter(|plane| !plane.chars.is_empty())
+    .map(|plane| Region {
+      top: 0,
+      left: 0,
+      bottom: plane.chars.len() - 1,
+      right: plane.chars.iter().map(|row| row.len()).max().unwrap_or(1).saturating_sub(1),
+    })
+    .into_iter()
+    .collect()
+}
+
+/// Pairs clauses by kind and name, in the order of their appearance.
+/// Returns the index of the paired clause from the second list for each clause from the first list.
+pub(crate) fn pair_clauses(first: &[Clause], second: &[Clause]) -> Vec<Option<usize>> {
+  let mut pairs = vec![];
+  let mut paired = vec![false; second.len()];
+  for clause in first {
+    let found = second
+      .iter()
+      .enumerate()
+      .position(|(index, other)| !paired[index] && other.kind == clause.kind && normalized(&other.name) == normalized(&clause.name));
+    if let Some(index) = found {
+      paired[index] = true;
+    }
+    pairs.push(found);
+  }
+  pairs
+}
+
+/// Returns normalized texts of all entries of the rule.
+fn entries(rule: &DecisionRule, clauses: &[Clause]) -> Vec<String> {
+  clauses.iter().map(|clause| normalized(&rule.entry(clause).text())).collect()
+}
+
+/// Returns normalized allowed values of the clause, empty when not specified.
+fn allowed_values(clause: &Clause) -> String {
+  clause.allowed_values.map_or(String::new(), |cell| normalized(&cell.text()))
+}
+
+/// Pairs rules of the old table with rules of the new table, returns the index of the paired new rule
+/// for each old rule. Rules are paired when all entries in common clauses are equal, then when
+/// all input entries are equal (outputs were changed), then when most of entries are equal.
+pub(crate) fn match_rules(old: &DecisionTable, new: &DecisionTable, common: &[(&Clause, &Clause)]) -> Vec<Option<usize>> {
+  let keys = |dt: &DecisionTable, old_side: bool| -> Vec<Vec<String>> {
+    dt.rules
+      .iter()
+      .map(|rule| {
+        common
+          .iter()
+          .map(|(old_clause, new_clause)| normalized(&rule.entry(if old_side { old_clause } else { new_clause }).text()))
+          .collect()
+      })
+      .collect()
+  };
+  let old_keys = keys(old, true);
+  let new_keys = keys(new, false);
+  let inputs: Vec<bool> = common.iter().map(|(clause, _)| clause.kind == ClauseKind::Input).collect();
+  let equal_count = |a: &[String], b: &[String]| a.iter().zip(b).filter(|(a, b)| a == b).count();
+  let same_inputs = |a: &[String], b: &[String]| inputs.iter().any(|input| *input) && a.iter().zip(b).zip(&inputs).all(|((a, b), input)| !input || a == b);
+  let mut pairs: Vec<Option<usize>> = vec![None; old_keys.len()];
+  let mut paired = vec![false; new_keys.len()];
+  for pass in 0..3 {
+    for (old_index, old_key) in old_keys.iter().enumerate() {
+      if pairs[old_index].is_some() {
+        continue;
+      }
+      let candidates = new_keys.iter().enumerate().filter(|(new_index, _)| !paired[*new_index]);
+      let found = match pass {
+        0 => candidates.filter(|(_, new_key)| *new_key == old_key).map(|(new_index, _)| new_index).next(),
+        1 => candidates
+          .filter(|(_, new_key)| same_inputs(old_key, new_key))
+          .map(|(new_index, _)| new_index)
+          .next(),
+        _ => candidates
+          .map(|(new_index, new_key)| (new_index, equal_count(old_key, new_key)))
+          .filter(|(_, count)| *count > 0 && *count * 2 >= old_key.len())
+          .max_by_key(|(new_index, count)| (*count, usize::MAX - new_index))
+          .map(|(new_index, _)| new_index),
+      };
+      if let Some(new_index) = found {
+        pairs[old_index] = Some(new_index);
+        paired[new_index] = true;
+      }
+    }
+  }
+  pairs
+}
+
+/// Returns uncolored grid of colors for all characters of the plane.
+fn colors_of(rows: &[Vec<char>]) -> Vec<Vec<Option<&'static str>>> {
+  rows.iter().map(|row| vec![None; row.len()]).collect()
+}
+
+/// Colors all characters in specified regions.
+fn paint(colors: &mut [Vec<Option<&'static str>>], regions: &[Region], color: &'static str) {
+  for region in regions {
+    for row in colors.iter_mut().take(region.bottom + 1).skip(region.top) {
+      for column in row.iter_mut().take(region.right + 1).skip(region.left) {
+        *column = Some(color);
+      }
+    }
+  }
+}
+
+/// Renders the row of the plane with colors, padded to the specified width.
+fn render_row(plane: Option<&Plane>, row_index: usize, colors: Option<&Vec<Option<&'static str>>>, width: usize) -> String {
+  let row = plane.and_then(|plane| plane.chars.get(row_index));
+  let mut text = String::new();
+  let mut current = None;
+  let columns = row.map_or(0, |row| row.len());
+  for index in 0..columns.max(width) {
+    let ch = row.and_then(|row| row.get(index)).copied().unwrap_or(' ');
+    if ch == CH_WIDE {
+      continue;
+    }
+    let color = colors.and_then(|colors| colors.get(index)).copied().flatten();
+    if color != current {
+      text.push_str(color.unwrap_or(COLOR_RESET));
+      current = color;
+    }
+    text.push_str(&plane.map_or_else(|| ch.to_string(), |plane| plane.clusters.text(&[ch])));
+  }
+  if current.is_some() {
+    text.push_str(COLOR_RESET);
+  }
+  text
+}
+
+/// Returns the text as a JSON string.
+fn json_string(text: &str) -> String {
+  let mut json = String::from("\"");
+  for ch in text.chars() {
+    match ch {
+      '"' => json.push_str("\\\""),
+      '\\' => json.push_str("\\\\"),
+      '\n' => json.push_str("\\n"),
+      '\t' => json.push_str("\\t"),
+      ch if ch.is_control() => json.push_str(&format!("\\u{:04x}", ch as u32)),
+      ch => json.push(ch),
+    }
+  }
+  json.push('"');
+  json
+}
+
+/// Returns values as a JSON array.
+fn json_list(values: impl Iterator<Item = String>) -> String {
+  format!("[{}]", values.collect::<Vec<String>>().join(", "))
+}
+
+/// Returns fields as a JSON object.
+fn json_object(fields: &[(String, String)]) -> String {
+  let fields: Vec<String> = fields.iter().map(|(name, value)| format!("{}: {}", json_string(name), value)).collect();
+  format!("{{{}}}", fields.join(", "))
+}
@@ -0,0 +1,329 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Implementation of a document containing decision tables and text placed between them.
+
+use crate::box_drawing::is_box_drawing_character;
+use crate::plane::Plane;
+use std::fmt;
+use std::fmt::Display;
+
+/// Byte order mark.
+const BOM: char = '\u{feff}';
+
+/// Part of the document.
+enum Segment {
+  /// Lines of text placed outside decision tables, carriage returns ending lines are kept.
+  Text(Vec<String>),
+  /// Decision table with leading and trailing whitespace of each row, trailing whitespace includes the carriage return.
+  Table(Plane, Vec<(String, String)>),
+}
+
+/// Document loaded from file, may contain multiple decision tables.
+pub struct Document {
+  /// Segments of the document in the order of appearance.
+  segments: Vec<Segment>,
+  /// Index of the current decision table.
+  current: usize,
+  /// Line ending of rows added while editing (`\n` or `\r\n`), the same as the ending of the first loaded line.
+  line_ending: &'static str,
+  /// Flag indicating if the loaded content ends with line ending.
+  final_line_ending: bool,
+  /// Flag indicating if the loaded content starts with byte order mark.
+  bom: bool,
+}
+
+impl Display for Document {
+  /// Converts a [Document] into its string representation, the same as the loaded content when nothing was edited.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.bom {
+      write!(f, "{}", BOM)?;
+    }
+    write!(f, "{}", self.lines().join("\n"))?;
+    if self.final_line_ending {
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+impl Document {
+  /// Creates a document from text, each block of lines starting with `┌`
+  /// and followed by lines starting with box-drawing characters is a decision table.
+  /// Lines are split at `\n`, so each line keeps its own ending (`\n` or `\r\n`).
+  pub fn new(content: &str) -> Self {
+    let bom = content.starts_with(BOM);
+    let content = content.strip_prefix(BOM).unwrap_or(content);
+    let final_line_ending = content.ends_with('\n');
+    let mut lines: Vec<&str> = if content.is_empty() { vec![] } else { content.split('\n').collect() };
+    if final_line_ending {
+      lines.pop();
+    }
+    let line_ending = if lines.first().is_some_and(|line| line.ends_with('\r')) {
+      "\r\n"
+    } else {
+      "\n"
+    };
+    let mut segments = vec![];
+    let mut text: Vec<String> = vec![];
+    let mut table: Vec<&str> = vec![];
+    let mut closed = false;
+    let starts_with = |line: &str, f: fn(char) -> bool| line.trim_start().chars().next().is_some_and(f);
+    for line in lines {
+      if !table.is_empty() && !closed && starts_with(line, |ch| is_box_drawing_character!(ch)) {
+        closed = starts_with(line, |ch| ch == '└');
+        table.push(line);
+        continue;
+      }
+      if !table.is_empty() {
+        segments.push(table_segment(&table));
+        table.clear();
+      }
+      if starts_with(line, |ch| ch == '┌') {
+        if !text.is_empty() {
+          segments.push(Segment::Text(text.clone()));
+          text.clear();
+        }
+        closed = false;
+        table.push(line);
+      } else {
+        text.push(line.to_string());
+      }
+    }
+    if !table.is_empty() {
+      segments.push(table_segment(&table));
+    }
+    if !text.is_empty() {
+      segments.push(Segment::Text(text));
+    }
+    if !segments.iter().any(|segment| matches!(segment, Segment::Table(..))) {
+      // no decision table found, the text is kept unchanged and followed by an empty table
+      segments.push(Segment::Table(Plane::new(""), vec![]));
+    }
+    Self {
+      segments,
+      current: 0,
+      line_ending,
+      final_line_ending,
+      bom,
+    }
+  }
+
+  /// Returns the number of decision tables in the document.
+  pub fn table_count(&self) -> usize {
+    self.planes().count()
+  }
+
+  /// Returns the index of the current decision table.
+  pub fn current_table(&self) -> usize {
+    self.current
+  }
+
+  /// Makes the decision table with the specified index the current one.
+  pub fn select_table(&mut self, index: usize) -> bool {
+    if index < self.table_count() {
+      self.current = index;
+      return true;
+    }
+    false
+  }
+
+  /// Returns the plane of the current decision table.
+  pub fn plane(&self) -> &Plane {
+    self.planes().nth(self.current).unwrap()
+  }
+
+  /// Returns the mutable plane of the current decision table.
+  pub fn plane_mut(&mut self) -> &mut Plane {
+    let current = self.current;
+    self.planes_mut().nth(current).unwrap()
+  }
+
+  /// Returns planes of all decision tables.
+  pub fn planes(&self) -> impl Iterator<Item = &Plane> {
+    self.segments.iter().filter_map(|segment| match segment {
+      Segment::Table(plane, _) => Some(plane),
+      Segment::Text(_) => None,
+    })
+  }
+
+  /// Returns mutable planes of all decision tables.
+  pub fn planes_mut(&mut self) -> impl Iterator<Item = &mut Plane> {
+    self.segments.iter_mut().filter_map(|segment| match segment {
+      Segment::Table(plane, _) => Some(plane),
+      Segment::Text(_) => None,
+    })
+  }
+
+  /// Returns lines of text placed outside decision tables, a table is represented by an empty list of lines.
+  pub fn texts(&self) -> Vec<&[String]> {
+    self
+      .segments
+      .iter()
+      .map(|segment| match segment {
+        Segment::Text(lines) => lines.as_slice(),
+        Segment::Table(..) => &[],
+      })
+      .collect()
+  }
+
+  /// Returns the index of the first row of the decision table with the specified index.
+  pub fn table_top(&self, index: usize) -> usize {
+    let mut top = 0;
+    let mut table_index = 0;
+    for segment in &self.segments {
+      match segment {
+        Segment::Text(lines) => top += lines.len(),
+        Segment::Table(plane, _) => {
+          if table_index == index {
+            break;
+          }
+          top += plane.chars.len();
+          table_index += 1;
+        }
+      }
+    }
+    top
+  }
+
+  /// Returns the line number and column number (both starting from 1) in the loaded content,
+  /// corresponding to the specified position in the decision table with the specified index.
+  pub fn origin(&self, index: usize, row: usize, col: usize) -> (usize, usize) {
+    let (line, column) = self.planes().nth(index).map_or((row + 1, col + 1), |plane| plane.origin(row, col));
+    (self.table_top(index) + line, column)
+  }
+
+  /// Returns rows of the decision table with the specified index, including leading and trailing whitespace.
+  pub fn table_rows(&self, index: usize) -> Vec<String> {
+    match self.table(index) {
+      Some((plane, margins)) => (0..plane.chars.len()).map(|row| self.table_line(plane, margins, row)).collect(),
+      None => vec![],
+    }
+  }
+
+  /// Replaces the decision table with the specified index with the table having the same index in another document,
+  /// rows are copied unchanged, including leading and trailing whitespace.
+  pub fn copy_table(&mut self, index: usize, source: &Document) {
+    if let Some((source_plane, source_margins)) = source.table(index) {
+      if let Some(Segment::Table(plane, margins)) = self.segments.iter_mut().filter(|segment| matches!(segment, Segment::Table(..))).nth(index) {
+        *plane = source_plane.clone();
+        *margins = source_margins.to_vec();
+      }
+    }
+  }
+
+  /// Returns the plane and margins of the decision table with the specified index.
+  fn table(&self, index: usize) -> Option<(&Plane, &[(String, String)])> {
+    self
+      .segments
+      .iter()
+      .filter_map(|segment| match segment {
+        Segment::Table(plane, margins) => Some((plane, margins.as_slice())),
+        Segment::Text(_) => None,
+      })
+      .nth(index)
+  }
+
+  /// Replaces the decision table with the specified index, all rows of the new table
+  /// are indented like the first row of the replaced table, trailing whitespace is removed.
+  pub fn replace_table(&mut self, index: usize, new_plane: Plane) {
+    if let Some(Segment::Table(plane, margins)) = self.segments.iter_mut().filter(|segment| matches!(segment, Segment::Table(..))).nth(index) {
+      let indent = margins.first().map_or(String::new(), |(leading, _)| leading.clone());
+      *margins = vec![(indent, self.line_ending.trim_end_matches('\n').to_string()); new_plane.chars.len()];
+      *plane = new_plane;
+    }
+  }
+
+  /// Returns the indentation (number of characters) of the specified row in the current decision table.
+  pub fn margin(&self, row: usize) -> usize {
+    self.table_margin(self.current, row)
+  }
+
+  /// Returns the indentation (number of characters) of the specified row in the decision table with the specified index.
+  pub fn table_margin(&self, index: usize, row: usize) -> usize {
+    let table = self.segments.iter().filter(|segment| matches!(segment, Segment::Table(..))).nth(index);
+    if let Some(Segment::Table(_, margins)) = table {
+      return margin_of(margins, row, self.line_ending).0.chars().count();
+    }
+    0
+  }
+
+  /// Returns all rows of the document, without carriage returns ending lines.
+  pub fn rows(&self) -> Vec<String> {
+    self
+      .lines()
+      .into_iter()
+      .map(|line| line.strip_suffix('\r').map(str::to_string).unwrap_or(line))
+      .collect()
+  }
+
+  /// Returns all lines of the document, ending with carriage returns like the loaded lines.
+  fn lines(&self) -> Vec<String> {
+    let mut lines = vec![];
+    for segment in &self.segments {
+      match segment {
+        Segment::Text(text) => lines.extend(text.iter().cloned()),
+        Segment::Table(plane, margins) => lines.extend((0..plane.chars.len()).map(|row| self.table_line(plane, margins, row))),
+      }
+    }
+    lines
+  }
+
+  /// Returns the row of the decision table with leading and trailing whitespace.
+  fn table_line(&self, plane: &Plane, margins: &[(String, String)], row: usize) -> String {
+    let (leading, trailing) = margin_of(margins, row, self.line_ending);
+    format!("{}{}{}", leading, plane.clusters.text(&plane.chars[row]), trailing)
+  }
+}
+
+/// Creates a segment containing decision table from lines of text.
+fn table_segment(lines: &[&str]) -> Segment {
+  let margins = lines
+    .iter()
+    .map(|line| {
+      let trimmed = line.trim();
+      let leading = &line[..line.len() - line.trim_start().len()];
+      let trailing = &line[leading.len() + trimmed.len()..];
+      (leading.to_string(), trailing.to_string())
+    })
+    .collect();
+  Segment::Table(Plane::new(&lines.join("\n")), margins)
+}
+
+/// Returns leading and trailing whitespace of the specified row, rows added while editing are indented
+/// like the last row and have no trailing whitespace, except the carriage return of the specified line ending.
+fn margin_of<'a>(margins: &'a [(String, String)], row: usize, line_ending: &'static str) -> (&'a str, &'a str) {
+  match margins.get(row) {
+    Some((leading, trailing)) => (leading, trailing),
+    None => (margins.last().map_or("", |(leading, _)| leading), line_ending.trim_end_matches('\n')),
+  }
+}
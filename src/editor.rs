@@ -30,73 +30,445 @@
  * limitations under the License.
  */
 
+use crate::analyzer::analyze;
+use crate::document::Document;
 use crate::errors::*;
+use crate::evaluator::evaluate;
+use crate::feel::parse_value;
+use crate::highlighter::{highlight, theme, Style, Theme, MONOCHROME, STYLES};
 use crate::keys::*;
-use crate::plane::*;
+use crate::linter::lint;
+use crate::model::Region;
+use crate::plane::{Register, SearchScope, SelectionMode};
+use crate::recognizer::recognize;
+use crate::unicode::{clip_columns, text_width};
 use crate::utils::debug;
+use crate::views::{Area, Cursor, Split, View, Views};
 use ncurses::*;
 use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+/// Escape sequence enabling the bracketed paste mode of the terminal.
+const BRACKETED_PASTE_ON: &str = "\x1b[?2004h";
+
+/// Escape sequence disabling the bracketed paste mode of the terminal.
+const BRACKETED_PASTE_OFF: &str = "\x1b[?2004l";
+
+/// Bytes following the escape key at the beginning of the pasted text.
+const PASTE_BEGIN: &[u8] = b"[200~";
+
+/// Bytes ending the pasted text.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Time in milliseconds to wait for the rest of the escape sequence.
+const ESCAPE_SEQUENCE_DELAY: i32 = 25;
+
+/// Minimal width of cursor coordinates displayed in the status bar.
+const STATUS_WIDTH: usize = 32;
+
+/// File name denoting the standard input.
+pub const STDIN_FILE_NAME: &str = "-";
+
+/// Suffix of the file name where the modified document is saved when the editor is terminated by a signal.
+const SWAP_FILE_SUFFIX: &str = ".swp";
+
+/// Key following the escape key when Alt + Q is pressed.
+const KEY_FORCE_QUIT: i32 = b'q' as i32;
+
+/// Flag set when the editor is requested to terminate by SIGTERM or SIGHUP signal.
+static TERMINATION_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable disabling colors when set to any value.
+const NO_COLOR: &str = "NO_COLOR";
+
+/// Terminal device used for reading key-strokes when the content is loaded from the standard input.
+const TERMINAL_DEVICE: &str = "/dev/tty";
+
 /// Editor actions.
 enum EditorAction {
+  AnalyzeTable,
+  BeginSelection,
+  CancelSelection,
+  Copy,
   CursorMoveCellStart,
   CursorMoveCellEnd,
   CursorMoveCellLeft,
   CursorMoveCellRight,
+  CursorMoveBody,
   CursorMoveDown,
+  CursorMoveHeader,
   CursorMoveLeft,
+  CursorMoveNextRule,
+  CursorMovePreviousRule,
   CursorMoveRight,
   CursorMoveTableStart,
   CursorMoveTableEnd,
   CursorMoveUp,
+  Cut,
   DebugKeystroke(i32, String),
   DeleteChar,
   DeleteCharBefore,
+  DeleteRule,
+  EditInformationItemName,
+  EvaluateTable,
+  ForceQuit,
+  GoTo,
   InsertChar(char),
+  InsertRule,
+  ListBuffers,
+  MergeCells,
+  MoveRuleDown,
+  MoveRuleUp,
+  NextBuffer,
+  NextTable,
   Nop,
+  Paste,
+  PasteText(String),
+  PreviousBuffer,
+  PreviousTable,
+  Replace,
   ResizeWindow,
+  SaveFile,
+  Search,
+  SearchNext,
+  SearchPrevious,
+  SortRules,
+  SplitCell,
+  SplitHorizontally,
   SplitLine,
+  SplitVertically,
+  SwitchView,
+  Terminate,
   Quit,
 }
 
+impl EditorAction {
+  /// Returns `true` when the action modifies the document, such actions are disabled in read-only mode.
+  fn modifies_document(&self) -> bool {
+    matches!(
+      self,
+      EditorAction::Cut
+        | EditorAction::DeleteChar
+        | EditorAction::DeleteCharBefore
+        | EditorAction::DeleteRule
+        | EditorAction::EditInformationItemName
+        | EditorAction::InsertChar(_)
+        | EditorAction::InsertRule
+        | EditorAction::MergeCells
+        | EditorAction::MoveRuleDown
+        | EditorAction::MoveRuleUp
+        | EditorAction::Paste
+        | EditorAction::PasteText(_)
+        | EditorAction::Replace
+        | EditorAction::SaveFile
+        | EditorAction::SortRules
+        | EditorAction::SplitCell
+        | EditorAction::SplitLine
+    )
+  }
+
+  /// Returns `true` when the action moves the cursor or changes the plane, so it can not be executed when the plane has no rows.
+  fn uses_plane(&self) -> bool {
+    !matches!(
+      self,
+      EditorAction::AnalyzeTable
+        | EditorAction::CancelSelection
+        | EditorAction::DebugKeystroke(..)
+        | EditorAction::EvaluateTable
+        | EditorAction::ForceQuit
+        | EditorAction::ListBuffers
+        | EditorAction::NextBuffer
+        | EditorAction::NextTable
+        | EditorAction::Nop
+        | EditorAction::PreviousBuffer
+        | EditorAction::PreviousTable
+        | EditorAction::ResizeWindow
+        | EditorAction::SaveFile
+        | EditorAction::SplitHorizontally
+        | EditorAction::SplitVertically
+        | EditorAction::SwitchView
+        | EditorAction::Terminate
+        | EditorAction::Quit
+    )
+  }
+}
+
+/// Panel displayed below the plane, like results of the decision table evaluation.
+struct Panel {
+  /// Regions of rules to be highlighted.
+  highlighted: Vec<Region>,
+  /// Lines of text displayed in the panel.
+  lines: Vec<String>,
+}
+
+/// Text searched in cells of the decision table.
+struct Search {
+  /// Searched text.
+  pattern: String,
+  /// Scope of searching.
+  scope: SearchScope,
+  /// Regions of text matching the pattern, highlighted in the plane.
+  matches: Vec<Region>,
+}
+
+/// Item displayed in the navigable list below the plane.
+struct ListItem {
+  /// Text of the item.
+  message: String,
+  /// Index of the buffer the item refers to.
+  buffer: usize,
+  /// Index of the decision table the item refers to.
+  table: usize,
+  /// Cursor position related to the item.
+  position: Option<(usize, usize)>,
+  /// Regions to be highlighted when the item is selected.
+  highlighted: Vec<Region>,
+}
+
+/// File opened in the editor.
+struct Buffer {
+  /// Name of the edited file, `-` for the standard input.
+  file_name: String,
+  /// Document containing decision tables, the current table is edited in its plane.
+  document: Document,
+  /// Content of the document when it was loaded or saved.
+  saved_content: String,
+  /// Flag indicating if the document was modified since it was loaded or saved.
+  modified: bool,
+  /// Flag indicating if the document is only viewed, actions modifying the document are disabled.
+  read_only: bool,
+}
+
+impl Buffer {
+  /// Creates a buffer with the content loaded from file, the content loaded
+  /// from the standard input (`-`) is always read-only.
+  fn load(file_name: &str, read_only: bool) -> Result<Self> {
+    let content = load_content(file_name)?;
+    Ok(Self {
+      file_name: file_name.to_string(),
+      document: Document::new(&content),
+      saved_content: content,
+      modified: false,
+      read_only: read_only || file_name == STDIN_FILE_NAME,
+    })
+  }
+
+  /// Saves the document to the edited file.
+  fn save(&mut self) -> Result<()> {
+    let content = self.document.to_string();
+    fs::write(&self.file_name, &content).map_err(|e| err_save_file(&self.file_name, &e.to_string()))?;
+    self.saved_content = content;
+    self.modified = false;
+    Ok(())
+  }
+
+  /// Saves the document to the swap file placed next to the edited file, the edited file is left untouched.
+  fn save_swap(&self) -> Result<()> {
+    let swap_file_name = format!("{}{}", self.file_name, SWAP_FILE_SUFFIX);
+    fs::write(&swap_file_name, self.document.to_string()).map_err(|e| err_save_file(&swap_file_name, &e.to_string()))
+  }
+}
+
 /// Implementation of the decision table editor.
 pub struct Editor {
   /// Handle of the main window of the terminal.
   window: WINDOW,
-  /// Plane where the edited text is stored and manipulated.
-  plane: Plane,
+  /// Files opened in the editor.
+  buffers: Vec<Buffer>,
+  /// Views displaying buffers, there are two views when the terminal is split.
+  views: Views,
+  /// Input values entered during the last evaluation.
+  input_values: Vec<String>,
+  /// Panel displayed below the plane, present when the decision table was evaluated or analyzed.
+  panel: Option<Panel>,
+  /// Content copied or cut from the plane.
+  register: Option<Register>,
+  /// Flag indicating if the copied content is sent to the terminal clipboard using OSC 52 escape sequence.
+  osc52: bool,
+  /// The last searched text.
+  search: Option<Search>,
+  /// The last text replacing the searched text.
+  replacement: String,
+  /// The last clauses used for sorting rules.
+  sort_keys: String,
+  /// Color theme used for highlighting decision tables.
+  theme: &'static Theme,
 }
 
 impl Editor {
-  /// Creates a new editor initialized with the content loaded from file.
-  pub fn new(file_name: &str) -> Result<Self, AttoError> {
-    let content = fs::read_to_string(file_name).map_err(|e| err_load_file(file_name, &e.to_string()))?;
-    let plane = Plane::new(&content);
+  /// Creates a new editor initialized with the content loaded from files, the first file is edited,
+  /// decision tables are highlighted using the theme with the specified name.
+  pub fn new(file_names: &[String], osc52: bool, read_only: bool, theme_name: &str) -> Result<Self, AttoError> {
+    let buffers = file_names
+      .iter()
+      .map(|file_name| Buffer::load(file_name, read_only))
+      .collect::<Result<Vec<Buffer>>>()?;
     let window = Self::initialize();
-    Ok(Self { window, plane })
+    let theme = initialize_colors(theme(theme_name));
+    Ok(Self {
+      window,
+      buffers,
+      views: Views::new(0),
+      input_values: vec![],
+      panel: None,
+      register: None,
+      osc52,
+      search: None,
+      replacement: String::new(),
+      sort_keys: String::new(),
+      theme,
+    })
+  }
+
+  /// Returns the index of the edited buffer.
+  fn current_buffer(&self) -> usize {
+    self.view().buffer
+  }
+
+  /// Returns the edited buffer.
+  fn buffer(&self) -> &Buffer {
+    &self.buffers[self.current_buffer()]
+  }
+
+  /// Returns the edited buffer for modification.
+  fn buffer_mut(&mut self) -> &mut Buffer {
+    let index = self.current_buffer();
+    &mut self.buffers[index]
+  }
+
+  /// Returns the view having the focus.
+  fn view(&self) -> &View {
+    self.views.view()
+  }
+
+  /// Returns the area of the terminal occupied by the view with the specified index,
+  /// the bottom line of the terminal is reserved for messages and the status bar.
+  fn view_area(&self, index: usize) -> Area {
+    let mut max_x = 0;
+    let mut max_y = 0;
+    getmaxyx(self.window, &mut max_y, &mut max_x);
+    let height = (max_y as usize).saturating_sub(1).max(1);
+    let width = (max_x as usize).max(1);
+    self.views.area(index, height, width)
+  }
+
+  /// Returns the edited document.
+  fn document(&self) -> &Document {
+    &self.buffer().document
+  }
+
+  /// Returns the edited document for modification.
+  fn document_mut(&mut self) -> &mut Document {
+    &mut self.buffer_mut().document
   }
 
   /// Initializes terminal via ncurses.
   pub fn initialize() -> WINDOW {
-    let locale_conf = LcCategory::all;
-    setlocale(locale_conf, "en_US.UTF-8");
+    // characters outside ASCII are read and displayed only in UTF-8 locales
+    let locale = setlocale(LcCategory::all, "");
+    if !locale.to_uppercase().replace('-', "").contains("UTF8") {
+      for fallback in ["C.UTF-8", "en_US.UTF-8"] {
+        if !setlocale(LcCategory::all, fallback).is_empty() {
+          break;
+        }
+      }
+    }
+    install_signal_handlers();
     let window = initscr();
     raw();
     keypad(window, true);
     noecho();
+    send_to_terminal(BRACKETED_PASTE_ON);
     window
   }
 
   /// Terminates terminal via ncurses.
   pub fn finalize(&self) -> Result<()> {
+    send_to_terminal(BRACKETED_PASTE_OFF);
     endwin();
     Ok(())
   }
 
-  /// Updates cursor position.
-  pub fn update_cursor(&self) {
-    mv(self.plane.cursor_row() as i32, self.plane.cursor_col() as i32);
+  /// Updates cursor position, scrolls the document when the cursor is not visible.
+  pub fn update_cursor(&mut self) {
+    let area = self.view_area(self.views.current());
+    let row = self.cursor_document_row();
+    let plane = self.document().plane();
+    let col = self.document().margin(plane.cursor_row()) + plane.cursor_col();
+    if self.views.view_mut().scroll_to(row, col, &area) {
+      self.repaint_plane();
+    }
+    mv((area.top + row - self.view().top_row) as i32, (area.left + col - self.view().left_col) as i32);
+  }
+
+  /// Returns the index of the document row containing the cursor.
+  fn cursor_document_row(&self) -> usize {
+    self.document().table_top(self.document().current_table()) + self.document().plane().cursor_row()
+  }
+
+  /// Changes the attributes of characters displayed in the view having the focus,
+  /// the row and the column are related to the current decision table.
+  fn highlight(&self, row: usize, col: usize, width: usize, attributes: attr_t) {
+    let left = self.document().margin(row) + col;
+    let row = self.document().table_top(self.document().current_table()) + row;
+    self.paint(self.views.current(), row, left, width, attributes, 0);
+  }
+
+  /// Changes the attributes and the color pair of characters displayed in the view with the specified index,
+  /// the row and the column are related to the document, characters outside the view are skipped.
+  fn paint(&self, index: usize, row: usize, col: usize, width: usize, attributes: attr_t, pair: i16) {
+    let area = self.view_area(index);
+    let view = self.views.get(index);
+    let right = (col + width).min(view.left_col + area.width);
+    let left = col.max(view.left_col);
+    if row >= view.top_row && row < view.top_row + area.height && left < right {
+      let y = area.top + row - view.top_row;
+      let x = area.left + left - view.left_col;
+      mvchgat(y as i32, x as i32, (right - left) as i32, attributes, pair);
+    }
+  }
+
+  /// Highlights decision tables displayed in the view with the specified index, using colors of the theme.
+  fn paint_tables(&self, index: usize) {
+    let document = &self.buffers[self.views.get(index).buffer].document;
+    let view = self.views.get(index);
+    let bottom = view.top_row + self.view_area(index).height;
+    for (table, plane) in document.planes().enumerate() {
+      let top = document.table_top(table);
+      if top >= bottom || top + plane.chars.len() <= view.top_row {
+        continue;
+      }
+      for (r, styles) in highlight(plane).iter().enumerate() {
+        let margin = document.table_margin(table, r);
+        let mut start = 0;
+        for c in 1..=styles.len() {
+          if c == styles.len() || styles[c] != styles[start] {
+            if styles[start] != Style::Text {
+              let (attributes, pair) = self.appearance(styles[start]);
+              self.paint(index, top + r, margin + start, c - start, attributes, pair);
+            }
+            start = c;
+          }
+        }
+      }
+    }
+  }
+
+  /// Returns ncurses attributes and the color pair of characters displayed in the specified style.
+  fn appearance(&self, style: Style) -> (attr_t, i16) {
+    let appearance = self.theme.appearance(style);
+    let mut attributes = A_NORMAL();
+    if appearance.bold {
+      attributes |= A_BOLD();
+    }
+    if appearance.underline {
+      attributes |= A_UNDERLINE();
+    }
+    let pair = if self.theme.is_monochrome() { 0 } else { style as i16 + 1 };
+    (attributes, pair)
   }
 
   /// Updates cursor coordinates in status bar.
@@ -107,20 +479,662 @@ impl Editor {
     let mut max_y = 0;
     getmaxyx(self.window, &mut max_y, &mut max_x);
     getyx(self.window, &mut cur_y, &mut cur_x);
+    let coordinates = self.cursor_coordinates();
+    // names of clauses make coordinates longer, shorter coordinates overwrite the longer ones
+    let width = text_width(&coordinates).max(STATUS_WIDTH);
     mvaddstr(
       max_y - 1,
-      max_x - 20,
-      &format!("{:>20}", format!("{}:{} ", self.plane.cursor_col(), self.plane.cursor_row())),
+      max_x - width as i32,
+      &format!("{}{}", " ".repeat(width - text_width(&coordinates)), coordinates),
     );
     mv(cur_y, cur_x);
   }
 
-  /// Repaints the content of a plane.
+  /// Returns cursor coordinates displayed in status bar, prefixed with the selection mode when selecting,
+  /// with the file name when there are more files and with the table number when there are more tables. Coordinates are the rule and the clause
+  /// under the cursor, or the column and the row outside rules and clauses.
+  fn cursor_coordinates(&self) -> String {
+    let plane = self.document().plane();
+    let mode = match plane.selection_mode() {
+      Some(SelectionMode::Characters) => "CHARS ",
+      Some(SelectionMode::Cells) => "CELLS ",
+      Some(SelectionMode::Rules) => "RULES ",
+      None => "",
+    };
+    let location = plane
+      .cursor_location()
+      .unwrap_or_else(|| format!("{}:{}", plane.cursor_col(), plane.cursor_row()));
+    let file = if self.buffers.len() > 1 {
+      format!("{}{} ", self.buffer().file_name, if self.buffer().modified { "*" } else { "" })
+    } else {
+      String::new()
+    };
+    if self.document().table_count() > 1 {
+      format!(
+        "{}{}{}/{} {} ",
+        mode,
+        file,
+        self.document().current_table() + 1,
+        self.document().table_count(),
+        location
+      )
+    } else {
+      format!("{}{}{} ", mode, file, location)
+    }
+  }
+
+  /// Repaints the content of all views, highlights are displayed only in the view having the focus.
   pub fn repaint_plane(&self) {
-    for (r, row) in self.plane.chars.iter().enumerate() {
-      mv(r as i32, 0);
-      addstr(&row.iter().collect::<String>());
-      addstr("  ");
+    for index in 0..self.views.count() {
+      self.repaint_view(index);
+    }
+    let mut max_x = 0;
+    let mut max_y = 0;
+    getmaxyx(self.window, &mut max_y, &mut max_x);
+    match self.views.split() {
+      Some(Split::Horizontal) => {
+        let area = self.view_area(0);
+        mvaddstr((area.top + area.height) as i32, 0, &"─".repeat(max_x as usize));
+      }
+      Some(Split::Vertical) => {
+        let area = self.view_area(0);
+        for row in 0..area.height {
+          mvaddstr(row as i32, (area.left + area.width) as i32, "│");
+        }
+      }
+      None => {}
+    }
+    mv(max_y - 1, 0);
+    clrtoeol();
+    if let Some(panel) = &self.panel {
+      for region in &panel.highlighted {
+        for r in region.top..=region.bottom {
+          self.highlight(r, region.left, region.width(), A_REVERSE());
+        }
+      }
+    }
+    if let Some(search) = &self.search {
+      for region in &search.matches {
+        self.highlight(region.top, region.left, region.width(), A_UNDERLINE());
+      }
+    }
+    for region in self.document().plane().selected_regions() {
+      for r in region.top..=region.bottom {
+        self.highlight(r, region.left, region.width(), A_REVERSE());
+      }
+    }
+  }
+
+  /// Repaints the content of the view with the specified index, starting from the first displayed row and column,
+  /// the panel is displayed below the plane in the view having the focus.
+  fn repaint_view(&self, index: usize) {
+    let area = self.view_area(index);
+    let view = self.views.get(index);
+    let blank = clip_columns("", 0, area.width);
+    let rows = self.buffers[view.buffer].document.rows();
+    let mut lines: Vec<String> = rows.iter().skip(view.top_row).map(|row| clip_columns(row, view.left_col, area.width)).collect();
+    if let Some(panel) = self.panel.as_ref().filter(|_| index == self.views.current()) {
+      lines.push(blank.clone());
+      lines.extend(panel.lines.iter().map(|line| clip_columns(line, 0, area.width)));
+    }
+    lines.resize(area.height.max(lines.len()), blank);
+    for (offset, line) in lines.iter().take(area.height).enumerate() {
+      mvaddstr((area.top + offset) as i32, area.left as i32, line);
+    }
+    self.paint_tables(index);
+  }
+
+  /// Saves the document to the edited file, the result is displayed in the bottom line of the terminal.
+  fn save_file(&mut self) {
+    let message = match self.buffer_mut().save() {
+      Ok(()) => format!("saved '{}'", self.buffer().file_name),
+      Err(reason) => reason.to_string(),
+    };
+    self.show_message(&message);
+  }
+
+  /// Asks what to do with modified files before quitting, returns `true` when the editor may quit.
+  fn confirm_quit(&mut self) -> bool {
+    if !self.buffers.iter().any(|buffer| buffer.modified) {
+      return true;
+    }
+    self.show_message("modified files: (s)ave all, (d)iscard changes, (c)ancel");
+    refresh();
+    match read_key() {
+      (_, Some('s' | 'S')) => {
+        for buffer in self.buffers.iter_mut().filter(|buffer| buffer.modified) {
+          if let Err(reason) = buffer.save() {
+            self.show_message(&reason.to_string());
+            return false;
+          }
+        }
+        true
+      }
+      (_, Some('d' | 'D')) => true,
+      _ => {
+        self.show_message("");
+        false
+      }
+    }
+  }
+
+  /// Displays a message in the bottom line of the terminal.
+  fn show_message(&self, message: &str) {
+    let mut max_x = 0;
+    let mut max_y = 0;
+    getmaxyx(self.window, &mut max_y, &mut max_x);
+    mv(max_y - 1, 0);
+    clrtoeol();
+    mvaddstr(max_y - 1, 0, message);
+  }
+
+  /// Starts selecting characters, pressed again switches to selecting cells, rules and again characters.
+  fn begin_selection(&mut self) {
+    let plane = self.document_mut().plane_mut();
+    let mode = match plane.selection_mode() {
+      None | Some(SelectionMode::Rules) => SelectionMode::Characters,
+      Some(SelectionMode::Characters) => SelectionMode::Cells,
+      Some(SelectionMode::Cells) => SelectionMode::Rules,
+    };
+    plane.begin_selection(mode);
+  }
+
+  /// Copies (or cuts) the selected content into the register.
+  fn copy(&mut self, cut: bool) {
+    let plane = self.document_mut().plane_mut();
+    let register = if cut { plane.cut() } else { plane.copy() };
+    if let Some(register) = register {
+      if self.osc52 {
+        send_to_terminal_clipboard(&register.to_string());
+      }
+      self.register = Some(register);
+    }
+  }
+
+  /// Pastes the content of the register at the cursor position.
+  fn paste(&mut self) -> bool {
+    let index = self.current_buffer();
+    match &self.register {
+      Some(register) => self.buffers[index].document.plane_mut().paste(register),
+      None => false,
+    }
+  }
+
+  /// Displays a prompt in the bottom line of the terminal and reads a single line of text.
+  /// Returns `None` when the prompt is cancelled with ESCAPE.
+  fn prompt(&self, label: &str, initial: &str) -> Option<String> {
+    let mut max_x = 0;
+    let mut max_y = 0;
+    getmaxyx(self.window, &mut max_y, &mut max_x);
+    let mut text = initial.to_string();
+    let result = loop {
+      mv(max_y - 1, 0);
+      clrtoeol();
+      mvaddstr(max_y - 1, 0, &format!("{}{}", label, text));
+      refresh();
+      let (key_name, ch) = read_key();
+      if TERMINATION_REQUESTED.load(Ordering::SeqCst) {
+        break None;
+      }
+      match key_name.as_deref() {
+        Some(KN_ENTER) => break Some(text),
+        Some(KN_ESCAPE) => break None,
+        Some(KN_BACKSPACE) => {
+          text.pop();
+        }
+        _ => {
+          if let Some(ch) = ch.filter(|ch| !ch.is_control()) {
+            text.push(ch);
+          }
+        }
+      }
+    };
+    mv(max_y - 1, 0);
+    clrtoeol();
+    result
+  }
+
+  /// Searches the text incrementally, typed characters extend the pattern and the cursor is placed
+  /// at the nearest match. TAB switches between searching in the whole table and in the clause
+  /// under the cursor, UP and DOWN select the previous and the next match. ENTER accepts
+  /// the found position, ESCAPE restores the cursor position. Returns `true` when accepted.
+  fn search(&mut self, label: &str) -> bool {
+    let plane = self.document().plane();
+    let origin = (plane.cursor_row(), plane.cursor_col());
+    let (mut pattern, mut scope) = self
+      .search
+      .as_ref()
+      .map_or((String::new(), SearchScope::Table), |search| (search.pattern.clone(), search.scope));
+    let mut offset = 0;
+    let accepted = loop {
+      // the clause is always the one where searching started
+      let plane = self.document_mut().plane_mut();
+      plane.cursor_move_to(origin.0, origin.1);
+      let matches = plane.find(&pattern, scope);
+      let nearest = matches.iter().position(|found| (found.top, found.left) >= origin).unwrap_or(0);
+      let current = (!matches.is_empty()).then(|| (nearest as isize + offset).rem_euclid(matches.len() as isize) as usize);
+      if let Some(index) = current {
+        plane.cursor_move_to(matches[index].top, matches[index].left);
+      }
+      let status = current.map_or("no matches".to_string(), |index| format!("{}/{}", index + 1, matches.len()));
+      self.search = Some(Search {
+        pattern: pattern.clone(),
+        scope,
+        matches,
+      });
+      self.repaint_plane();
+      self.update_cursor();
+      let scope_name = if scope == SearchScope::Clause { "clause" } else { "table" };
+      self.show_message(&format!("{} in {} ({}): {}", label, scope_name, status, pattern));
+      refresh();
+      let (key_name, ch) = read_key();
+      if TERMINATION_REQUESTED.load(Ordering::SeqCst) {
+        break false;
+      }
+      match key_name.as_deref() {
+        Some(KN_ENTER) => break current.is_some(),
+        Some(KN_ESCAPE) => {
+          self.document_mut().plane_mut().cursor_move_to(origin.0, origin.1);
+          break false;
+        }
+        Some(KN_TAB) => {
+          scope = if scope == SearchScope::Table {
+            SearchScope::Clause
+          } else {
+            SearchScope::Table
+          };
+          offset = 0;
+        }
+        Some(KN_DOWN) => offset += 1,
+        Some(KN_UP) => offset -= 1,
+        Some(KN_BACKSPACE) => {
+          pattern.pop();
+          offset = 0;
+        }
+        _ => {
+          if let Some(ch) = ch.filter(|ch| !ch.is_control()) {
+            pattern.push(ch);
+            offset = 0;
+          }
+        }
+      }
+    };
+    if !accepted {
+      if let Some(search) = &mut self.search {
+        search.matches.clear();
+      }
+    }
+    self.show_message("");
+    accepted
+  }
+
+  /// Moves the cursor to the next (or previous) match of the last searched text.
+  fn search_next(&mut self, forward: bool) -> bool {
+    let index = self.current_buffer();
+    let Some(search) = &mut self.search else {
+      return false;
+    };
+    let plane = self.buffers[index].document.plane_mut();
+    search.matches = plane.find(&search.pattern, search.scope);
+    let cursor = (plane.cursor_row(), plane.cursor_col());
+    let found = if forward {
+      search.matches.iter().find(|found| (found.top, found.left) > cursor).or(search.matches.first())
+    } else {
+      search
+        .matches
+        .iter()
+        .rev()
+        .find(|found| (found.top, found.left) < cursor)
+        .or(search.matches.last())
+    };
+    found.is_some_and(|found| plane.cursor_move_to(found.top, found.left))
+  }
+
+  /// Replaces the searched text, each replacement is confirmed: (y)es, (n)o, (a)ll remaining or (q)uit.
+  /// Returns the number of replacements, `None` when replacing was cancelled.
+  fn replace(&mut self) -> Option<usize> {
+    if !self.search("Replace") {
+      return None;
+    }
+    let Some(replacement) = self.prompt("Replace with: ", &self.replacement.clone()) else {
+      self.search = None;
+      return None;
+    };
+    self.replacement = replacement.clone();
+    let search = self.search.as_ref()?;
+    let (pattern, scope) = (search.pattern.clone(), search.scope);
+    let plane = self.document().plane();
+    let mut position = (plane.cursor_row(), plane.cursor_col());
+    let mut all = false;
+    let mut count = 0;
+    loop {
+      // matches are searched again, because replacing changes the widths of columns
+      let matches = self.document().plane().find(&pattern, scope);
+      let Some(found) = matches.iter().find(|found| (found.top, found.left) >= position).copied() else {
+        break;
+      };
+      if !all {
+        self.document_mut().plane_mut().cursor_move_to(found.top, found.left);
+        if let Some(search) = &mut self.search {
+          search.matches = matches;
+        }
+        self.repaint_plane();
+        self.show_message("Replace? (y)es, (n)o, (a)ll, (q)uit");
+        self.update_cursor();
+        refresh();
+        match read_key().1 {
+          Some('y') => {}
+          Some('a') => all = true,
+          Some('n') => {
+            position = (found.top, found.right + 1);
+            continue;
+          }
+          _ => break,
+        }
+      }
+      let plane = self.document_mut().plane_mut();
+      if !plane.replace(found, &replacement) {
+        break;
+      }
+      count += 1;
+      position = (plane.cursor_row(), plane.cursor_col());
+    }
+    self.search = Some(Search {
+      pattern,
+      scope,
+      matches: vec![],
+    });
+    Some(count)
+  }
+
+  /// Moves the cursor to the cell entered in the prompt, like `rule 12`, `input Customer type` or `12:Order size`.
+  fn go_to(&mut self) {
+    let location = self.document().plane().cursor_location().unwrap_or_default();
+    if let Some(target) = self.prompt("Go to: ", &location) {
+      if let Err(reason) = self.document_mut().plane_mut().go_to(&target) {
+        self.show_message(&reason.to_string());
+      }
+    }
+  }
+
+  /// Sorts rules by clauses entered in the prompt, like `Age, Score desc`.
+  /// Returns the message to be displayed after the table is repainted.
+  fn sort_rules(&mut self) -> Option<String> {
+    let keys = self.prompt("Sort by: ", &self.sort_keys.clone())?;
+    self.sort_keys = keys.clone();
+    match self.document_mut().plane_mut().sort_rules(&keys) {
+      Ok(warning) => warning,
+      Err(reason) => Some(reason.to_string()),
+    }
+  }
+
+  /// Edits the name of the information item in the prompt, the empty name removes the name cell.
+  fn edit_information_item_name(&mut self) {
+    let name = self.document().plane().information_item_name();
+    if let Some(name) = self.prompt("Information item name: ", &name) {
+      self.document_mut().plane_mut().set_information_item_name(&name);
+    }
+  }
+
+  /// Evaluates the decision table with input values entered by the user.
+  fn evaluate_table(&mut self) {
+    let decision_table = match recognize(self.document().plane()) {
+      Ok(decision_table) => decision_table,
+      Err(reason) => {
+        self.panel = Some(Panel {
+          highlighted: vec![],
+          lines: vec![reason.to_string()],
+        });
+        return;
+      }
+    };
+    self.input_values.resize(decision_table.input_clauses.len(), String::new());
+    let mut input_values = vec![];
+    for (index, input_clause) in decision_table.input_clauses.iter().enumerate() {
+      let label = format!("{}: ", input_clause.input_expression.text());
+      let Some(text) = self.prompt(&label, &self.input_values[index]) else {
+        return;
+      };
+      self.input_values[index] = text;
+    }
+    let mut lines = vec![];
+    if let Some(name) = &decision_table.information_item_name {
+      lines.push(name.text());
+    }
+    for (input_clause, text) in decision_table.input_clauses.iter().zip(&self.input_values) {
+      match parse_value(text) {
+        Ok(value) => {
+          lines.push(format!("  {} = {}", input_clause.input_expression.text(), value));
+          input_values.push(value);
+        }
+        Err(reason) => lines.push(reason.to_string()),
+      }
+    }
+    let mut highlighted = vec![];
+    if input_values.len() == decision_table.input_clauses.len() {
+      match evaluate(&decision_table, &input_values) {
+        Ok(evaluation) => {
+          let rule_numbers: Vec<String> = evaluation
+            .matched_rules
+            .iter()
+            .map(|index| decision_table.rules[*index].number.text())
+            .collect();
+          lines.push(format!("Matched rules: {}", rule_numbers.join(", ")));
+          for (index, reason) in &evaluation.invalid_rules {
+            lines.push(format!("Rule {} not evaluated: {}", decision_table.rules[*index].number.text(), reason));
+          }
+          for index in &evaluation.matched_rules {
+            let rule = &decision_table.rules[*index];
+            let annotations: Vec<String> = decision_table
+              .annotation_clauses
+              .iter()
+              .zip(&rule.annotation_entries)
+              .map(|(annotation_clause, annotation_entry)| format!("{} = {}", annotation_clause.name.text(), annotation_entry.text()))
+              .collect();
+            if !annotations.is_empty() {
+              lines.push(format!("  {}: {}", rule.number.text(), annotations.join(", ")));
+            }
+          }
+          match evaluation.result {
+            Ok(value) => lines.push(format!("Result: {}", value)),
+            Err(reason) => lines.push(format!("Result: {}", reason)),
+          }
+          for index in evaluation.matched_rules {
+            let rule = &decision_table.rules[index];
+            highlighted.push(rule.region);
+          }
+        }
+        Err(reason) => lines.push(reason.to_string()),
+      }
+    }
+    self.panel = Some(Panel { highlighted, lines });
+  }
+
+  /// Analyzes the decision table and displays a navigable list of findings.
+  fn analyze_table(&mut self) {
+    let result = recognize(self.document().plane()).and_then(|decision_table| Ok((analyze(&decision_table)?, decision_table)));
+    let (findings, decision_table) = match result {
+      Ok(result) => result,
+      Err(reason) => {
+        self.panel = Some(Panel {
+          highlighted: vec![],
+          lines: vec![reason.to_string()],
+        });
+        return;
+      }
+    };
+    if findings.is_empty() {
+      self.panel = Some(Panel {
+        highlighted: vec![],
+        lines: vec!["No overlapping rules, subsumed rules or gaps found.".to_string()],
+      });
+      return;
+    }
+    let items: Vec<ListItem> = findings
+      .iter()
+      .map(|finding| ListItem {
+        message: finding.message.clone(),
+        buffer: self.current_buffer(),
+        table: self.document().current_table(),
+        position: finding.rules.first().map(|index| {
+          let region = decision_table.rules[*index].number.region;
+          (region.top, region.left)
+        }),
+        highlighted: finding.rules.iter().map(|index| decision_table.rules[*index].region).collect(),
+      })
+      .collect();
+    self.browse("Findings", &items);
+  }
+
+  /// Displays the buffer with the specified index in the view having the focus, the buffer becomes the edited one.
+  fn select_buffer(&mut self, index: usize) -> bool {
+    if index >= self.buffers.len() {
+      return false;
+    }
+    if self.views.select_buffer(index) {
+      // highlighted matches refer to the previously edited document, the searched text is remembered
+      if let Some(search) = &mut self.search {
+        search.matches.clear();
+      }
+    }
+    true
+  }
+
+  /// Splits the terminal into two views in the specified direction, the new view displays the same buffer.
+  /// Splitting again in the same direction closes the view not having the focus.
+  fn split_view(&mut self, split: Split) {
+    let cursor = Cursor::of(self.document());
+    self.views.split_in(split, cursor);
+  }
+
+  /// Moves the focus to the other view of the split terminal, the cursor is placed
+  /// where it was when the view lost the focus.
+  fn switch_view(&mut self) -> bool {
+    let Some(cursor) = self.views.switch(Cursor::of(self.document())) else {
+      return false;
+    };
+    cursor.restore(self.document_mut());
+    // highlighted matches may refer to the document displayed in the other view, the searched text is remembered
+    if let Some(search) = &mut self.search {
+      search.matches.clear();
+    }
+    true
+  }
+
+  /// Displays a navigable list of opened files, the selected file is edited.
+  fn list_buffers(&mut self) {
+    let items: Vec<ListItem> = self
+      .buffers
+      .iter()
+      .enumerate()
+      .map(|(index, buffer)| ListItem {
+        message: format!(
+          "{}{}{}",
+          buffer.file_name,
+          if buffer.modified { " [modified]" } else { "" },
+          if buffer.read_only { " [read-only]" } else { "" }
+        ),
+        buffer: index,
+        table: buffer.document.current_table(),
+        position: None,
+        highlighted: vec![],
+      })
+      .collect();
+    self.browse_from("Files", &items, self.current_buffer());
+  }
+
+  /// Checks all decision tables in all buffers for structural problems and displays a navigable list of problems.
+  pub fn check_table(&mut self) {
+    let mut items = vec![];
+    for (buffer_index, buffer) in self.buffers.iter().enumerate() {
+      // problems are prefixed with the file name only when more files are opened
+      let prefix = if self.buffers.len() > 1 {
+        format!("{}:", buffer.file_name)
+      } else {
+        String::new()
+      };
+      for (table, plane) in buffer.document.planes().enumerate() {
+        for issue in lint(plane) {
+          let (line, column) = buffer.document.origin(table, issue.row, issue.col);
+          items.push(ListItem {
+            message: format!("{}{}:{}: {}", prefix, line, column, issue.message),
+            buffer: buffer_index,
+            table,
+            position: Some((issue.row, issue.col)),
+            highlighted: vec![],
+          });
+        }
+      }
+    }
+    if !items.is_empty() {
+      self.browse("Structural problems", &items);
+    }
+  }
+
+  /// Displays a navigable list of items below the plane, selecting an item moves the cursor.
+  fn browse(&mut self, title: &str, items: &[ListItem]) {
+    self.browse_from(title, items, 0);
+  }
+
+  /// Displays a navigable list of items below the plane, starting from the item with the specified index.
+  fn browse_from(&mut self, title: &str, items: &[ListItem], first_selected: usize) {
+    let visible = self
+      .view_area(self.views.current())
+      .height
+      .saturating_sub(self.document().rows().len().saturating_sub(self.view().top_row) + 2)
+      .max(1);
+    let mut selected = first_selected.min(items.len().saturating_sub(1));
+    loop {
+      let item = &items[selected];
+      let first = selected.saturating_sub(visible - 1);
+      let mut lines = vec![format!(
+        "{} {}/{} (UP/DOWN to select, ENTER or ESC to close):",
+        title,
+        selected + 1,
+        items.len()
+      )];
+      for (index, item) in items.iter().enumerate().skip(first).take(visible) {
+        lines.push(format!("{} {}", if index == selected { ">" } else { " " }, item.message));
+      }
+      self.select_buffer(item.buffer);
+      self.document_mut().select_table(item.table);
+      if let Some((row, col)) = item.position {
+        self.document_mut().plane_mut().cursor_move_to(row, col);
+      }
+      self.panel = Some(Panel {
+        highlighted: item.highlighted.clone(),
+        lines,
+      });
+      self.repaint_plane();
+      self.update_cursor();
+      self.update_cursor_coordinates();
+      refresh();
+      match keyname(getch()).as_deref() {
+        Some(KN_UP) => selected = selected.saturating_sub(1),
+        Some(KN_DOWN) => selected = (selected + 1).min(items.len() - 1),
+        Some(KN_ENTER | KN_ESCAPE) => break,
+        _ if TERMINATION_REQUESTED.load(Ordering::SeqCst) => break,
+        _ => {}
+      }
+    }
+    self.panel = None;
+  }
+
+  /// Reads the next key-stroke or character and maps it to editor action.
+  fn read_action(&self) -> EditorAction {
+    // reading is interrupted by the signal requesting termination
+    if TERMINATION_REQUESTED.load(Ordering::SeqCst) {
+      return EditorAction::Terminate;
+    }
+    match get_wch() {
+      Some(WchResult::KeyCode(key)) => self.map_key_to_action(key),
+      // codes of characters outside ASCII overlap with codes of function keys
+      Some(WchResult::Char(code)) if code > 127 => match char::from_u32(code) {
+        Some(ch) if !ch.is_control() => EditorAction::InsertChar(ch),
+        _ => EditorAction::Nop,
+      },
+      Some(WchResult::Char(code)) => self.map_key_to_action(code as i32),
+      None => EditorAction::Nop,
     }
   }
 
@@ -128,7 +1142,36 @@ impl Editor {
   fn map_key_to_action(&self, key: i32) -> EditorAction {
     if let Some(key_name) = keyname(key) {
       match key_name.as_str() {
+        KN_CTRL_B => EditorAction::BeginSelection,
+        KN_CTRL_C => EditorAction::Copy,
+        KN_CTRL_D => EditorAction::DeleteRule,
+        KN_CTRL_E => EditorAction::EvaluateTable,
+        KN_CTRL_F => EditorAction::Search,
+        KN_CTRL_G => EditorAction::GoTo,
+        KN_CTRL_K => EditorAction::AnalyzeTable,
+        KN_CTRL_L => EditorAction::ListBuffers,
+        KN_CTRL_N => EditorAction::InsertRule,
+        KN_CTRL_O => EditorAction::SortRules,
         KN_CTRL_Q => EditorAction::Quit,
+        KN_CTRL_R => EditorAction::Replace,
+        KN_CTRL_S => EditorAction::SaveFile,
+        KN_CTRL_V => EditorAction::Paste,
+        KN_CTRL_X => EditorAction::Cut,
+        KN_CTRL_UP => EditorAction::CursorMovePreviousRule,
+        KN_CTRL_DOWN => EditorAction::CursorMoveNextRule,
+        KN_CTRL_HOME => EditorAction::CursorMoveHeader,
+        KN_CTRL_END => EditorAction::CursorMoveBody,
+        KN_ALT_UP | KN_ALT_LEFT => EditorAction::MoveRuleUp,
+        KN_ALT_DOWN | KN_ALT_RIGHT => EditorAction::MoveRuleDown,
+        KN_ESCAPE => self.map_escape_sequence(),
+        KN_F2 => EditorAction::EditInformationItemName,
+        KN_F3 => EditorAction::SearchNext,
+        KN_SHIFT_F3 => EditorAction::SearchPrevious,
+        KN_F5 => EditorAction::MergeCells,
+        KN_SHIFT_F5 => EditorAction::SplitCell,
+        KN_F6 => EditorAction::SwitchView,
+        KN_F7 => EditorAction::SplitHorizontally,
+        KN_SHIFT_F7 => EditorAction::SplitVertically,
         KN_UP => EditorAction::CursorMoveUp,
         KN_DOWN => EditorAction::CursorMoveDown,
         KN_LEFT => EditorAction::CursorMoveLeft,
@@ -141,6 +1184,10 @@ impl Editor {
         KN_SHIFT_END => EditorAction::CursorMoveTableEnd,
         KN_TAB => EditorAction::CursorMoveCellRight,
         KN_SHIFT_TAB => EditorAction::CursorMoveCellLeft,
+        KN_PAGE_DOWN => EditorAction::NextTable,
+        KN_PAGE_UP => EditorAction::PreviousTable,
+        KN_CTRL_PAGE_DOWN => EditorAction::NextBuffer,
+        KN_CTRL_PAGE_UP => EditorAction::PreviousBuffer,
         KN_RESIZE => EditorAction::ResizeWindow,
         _ => match key {
           10 => EditorAction::SplitLine,
@@ -154,75 +1201,238 @@ impl Editor {
     }
   }
 
+  /// Maps the escape key to editor action. The escape key starting the bracketed paste sequence
+  /// is followed by the pasted text, which is read entirely to be inserted in a single action.
+  fn map_escape_sequence(&self) -> EditorAction {
+    // pasted text is sent at once, so the rest of the sequence is already available
+    timeout(ESCAPE_SEQUENCE_DELAY);
+    let mut keys = vec![];
+    while keys.len() < PASTE_BEGIN.len() && keys.iter().zip(PASTE_BEGIN).all(|(key, byte)| *key == *byte as i32) {
+      match getch() {
+        ERR => break,
+        key => keys.push(key),
+      }
+    }
+    timeout(-1);
+    if keys.iter().copied().eq(PASTE_BEGIN.iter().map(|byte| *byte as i32)) {
+      return EditorAction::PasteText(read_pasted_text());
+    }
+    if keys == [KEY_FORCE_QUIT] {
+      return EditorAction::ForceQuit;
+    }
+    // keys not belonging to the bracketed paste sequence are processed as usual
+    for key in keys.iter().rev() {
+      ungetch(*key);
+    }
+    EditorAction::CancelSelection
+  }
+
   /// Processes input key-strokes.
   pub fn process_keystrokes(&mut self) {
     loop {
-      match self.map_key_to_action(getch()) {
+      let mut action = self.read_action();
+      if matches!(
+        action,
+        EditorAction::DeleteChar | EditorAction::DeleteCharBefore | EditorAction::InsertChar(_) | EditorAction::SplitLine
+      ) && self.document().plane().is_cursor_in_information_item_name()
+      {
+        // the name of the information item is edited separately from the table
+        action = EditorAction::EditInformationItemName;
+      }
+      if self.document().plane().chars.is_empty() && action.uses_plane() {
+        // there is no table in the document, only the text around it is displayed
+        action = EditorAction::Nop;
+      }
+      if self.buffer().read_only && action.modifies_document() {
+        self.show_message("read-only mode, the document can not be modified");
+        self.update_cursor();
+        self.update_cursor_coordinates();
+        refresh();
+        continue;
+      }
+      if matches!(
+        action,
+        EditorAction::DeleteChar
+          | EditorAction::DeleteCharBefore
+          | EditorAction::DeleteRule
+          | EditorAction::EditInformationItemName
+          | EditorAction::InsertChar(_)
+          | EditorAction::InsertRule
+          | EditorAction::MergeCells
+          | EditorAction::MoveRuleDown
+          | EditorAction::MoveRuleUp
+          | EditorAction::SplitCell
+          | EditorAction::SplitLine
+          | EditorAction::NextTable
+          | EditorAction::PreviousTable
+          | EditorAction::Replace
+          | EditorAction::SortRules
+      ) {
+        // evaluation results are no longer valid after the decision table was modified or another table was selected
+        self.panel = None;
+        self.document_mut().plane_mut().clear_selection();
+      }
+      if matches!(
+        action,
+        EditorAction::CancelSelection
+          | EditorAction::Cut
+          | EditorAction::DeleteChar
+          | EditorAction::DeleteCharBefore
+          | EditorAction::DeleteRule
+          | EditorAction::EditInformationItemName
+          | EditorAction::InsertChar(_)
+          | EditorAction::InsertRule
+          | EditorAction::MergeCells
+          | EditorAction::MoveRuleDown
+          | EditorAction::MoveRuleUp
+          | EditorAction::NextTable
+          | EditorAction::Paste
+          | EditorAction::PasteText(_)
+          | EditorAction::PreviousTable
+          | EditorAction::SortRules
+          | EditorAction::SplitCell
+          | EditorAction::SplitLine
+      ) {
+        // highlighted matches are no longer valid, the searched text is remembered
+        if let Some(search) = &mut self.search {
+          search.matches.clear();
+        }
+      }
+      if matches!(action, EditorAction::Cut | EditorAction::Paste | EditorAction::PasteText(_)) {
+        self.panel = None;
+      }
+      let selecting = self.document().plane().selection_mode().is_some();
+      // saving updates the flag of modification itself
+      let modifies = action.modifies_document() && !matches!(action, EditorAction::SaveFile);
+      match action {
+        EditorAction::AnalyzeTable => {
+          self.analyze_table();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::BeginSelection => {
+          self.begin_selection();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::CancelSelection => {
+          self.document_mut().plane_mut().clear_selection();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::Copy => {
+          self.copy(false);
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::Cut => {
+          self.copy(true);
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
         EditorAction::CursorMoveCellStart => {
-          if self.plane.cursor_move_cell_start() {
+          if self.document_mut().plane_mut().cursor_move_cell_start() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveCellEnd => {
-          if self.plane.cursor_move_cell_end() {
+          if self.document_mut().plane_mut().cursor_move_cell_end() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveCellLeft => {
-          if self.plane.cursor_move_cell_left() {
+          if self.document_mut().plane_mut().cursor_move_cell_left() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveCellRight => {
-          if self.plane.cursor_move_cell_right() {
+          if self.document_mut().plane_mut().cursor_move_cell_right() {
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::CursorMoveBody => {
+          if self.document_mut().plane_mut().cursor_move_body() {
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::CursorMoveHeader => {
+          if self.document_mut().plane_mut().cursor_move_header() {
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::CursorMoveNextRule => {
+          if self.document_mut().plane_mut().cursor_move_rule(true) {
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::CursorMovePreviousRule => {
+          if self.document_mut().plane_mut().cursor_move_rule(false) {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveDown => {
-          if self.plane.cursor_move_down() {
+          if self.document_mut().plane_mut().cursor_move_down() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveLeft => {
-          if self.plane.cursor_move_left() {
+          if self.document_mut().plane_mut().cursor_move_left() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveRight => {
-          if self.plane.cursor_move_right() {
+          if self.document_mut().plane_mut().cursor_move_right() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveTableStart => {
-          if self.plane.cursor_move_table_start() {
+          if self.document_mut().plane_mut().cursor_move_table_start() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveTableEnd => {
-          if self.plane.cursor_move_table_end() {
+          if self.document_mut().plane_mut().cursor_move_table_end() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
           }
         }
         EditorAction::CursorMoveUp => {
-          if self.plane.cursor_move_up() {
+          if self.document_mut().plane_mut().cursor_move_up() {
             self.update_cursor();
             self.update_cursor_coordinates();
             refresh();
@@ -232,22 +1442,144 @@ impl Editor {
           debug(&format!("KEY: {} | {}", key, key_name));
         }
         EditorAction::DeleteChar => {
-          self.plane.delete_char();
+          self.document_mut().plane_mut().delete_char();
           self.repaint_plane();
           self.update_cursor();
           self.update_cursor_coordinates();
         }
         EditorAction::DeleteCharBefore => {
-          self.plane.delete_char_before();
+          self.document_mut().plane_mut().delete_char_before();
           self.repaint_plane();
           self.update_cursor();
           self.update_cursor_coordinates();
           refresh();
         }
+        EditorAction::DeleteRule => {
+          if self.document_mut().plane_mut().delete_rule() {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::EditInformationItemName => {
+          self.edit_information_item_name();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::EvaluateTable => {
+          self.evaluate_table();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::GoTo => {
+          self.go_to();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::InsertRule => {
+          if self.document_mut().plane_mut().insert_rule() {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::ListBuffers => {
+          self.list_buffers();
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::MergeCells => {
+          let result = self.document_mut().plane_mut().merge_cells();
+          self.repaint_plane();
+          if let Err(reason) = result {
+            self.show_message(&reason.to_string());
+          }
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::MoveRuleDown => {
+          if self.document_mut().plane_mut().move_rule(true) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::MoveRuleUp => {
+          if self.document_mut().plane_mut().move_rule(false) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::NextBuffer => {
+          if self.select_buffer((self.current_buffer() + 1) % self.buffers.len()) {
+            self.panel = None;
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::NextTable => {
+          let table = self.document().current_table();
+          if self.document_mut().select_table(table + 1) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
         EditorAction::Nop => {}
+        EditorAction::Paste => {
+          if self.paste() {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::PasteText(text) => {
+          if self.document_mut().plane_mut().insert_text(&text) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::PreviousBuffer => {
+          let count = self.buffers.len();
+          if self.select_buffer((self.current_buffer() + count - 1) % count) {
+            self.panel = None;
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::PreviousTable => {
+          let table = self.document().current_table();
+          if table > 0 && self.document_mut().select_table(table - 1) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
         EditorAction::InsertChar(ch) => {
           let now = Instant::now();
-          self.plane.insert_char(ch);
+          self.document_mut().plane_mut().insert_char(ch);
           let elapsed = now.elapsed();
           debug(&format!("{:.2?}", elapsed));
           self.repaint_plane();
@@ -255,24 +1587,240 @@ impl Editor {
           self.update_cursor_coordinates();
           refresh();
         }
+        EditorAction::Replace => {
+          let replaced = self.replace();
+          self.repaint_plane();
+          if let Some(count) = replaced {
+            self.show_message(&format!("replaced {} occurrence(s)", count));
+          }
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SplitCell => {
+          let result = self.document_mut().plane_mut().split_cell();
+          self.repaint_plane();
+          if let Err(reason) = result {
+            self.show_message(&reason.to_string());
+          }
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SortRules => {
+          let message = self.sort_rules();
+          self.repaint_plane();
+          if let Some(message) = message {
+            self.show_message(&message);
+          }
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::Search => {
+          self.search("Search");
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SearchNext => {
+          if self.search_next(true) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
+        EditorAction::SearchPrevious => {
+          if self.search_next(false) {
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
+        }
         EditorAction::ResizeWindow => {
-          // getmaxyx(self.window, &mut max_y, &mut max_x);
-          // getyx(window, &mut cur_y, &mut cur_x);
-          // attron(A_REVERSE());
-          // mvaddstr(43, 1, &format!("{}:{}", max_x, max_y));
-          // attroff(A_REVERSE());
-          // mv(cur_y, cur_x);
-          // refresh();
+          // views are laid out according to the new size of the terminal
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SaveFile => {
+          self.save_file();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SplitHorizontally => {
+          self.split_view(Split::Horizontal);
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SplitVertically => {
+          self.split_view(Split::Vertical);
+          self.repaint_plane();
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+        EditorAction::SwitchView => {
+          if self.switch_view() {
+            self.panel = None;
+            self.repaint_plane();
+            self.update_cursor();
+            self.update_cursor_coordinates();
+            refresh();
+          }
         }
         EditorAction::SplitLine => {
-          self.plane.split_line();
+          self.document_mut().plane_mut().split_line();
           self.repaint_plane();
           self.update_cursor();
           self.update_cursor_coordinates();
           refresh();
         }
-        EditorAction::Quit => break,
+        EditorAction::ForceQuit => break,
+        EditorAction::Terminate => {
+          // there is no one to ask, modified documents are saved aside of edited files
+          for buffer in self.buffers.iter().filter(|buffer| buffer.modified) {
+            let _ = buffer.save_swap();
+          }
+          break;
+        }
+        EditorAction::Quit => {
+          if self.confirm_quit() {
+            break;
+          }
+          self.update_cursor();
+          self.update_cursor_coordinates();
+          refresh();
+        }
+      }
+      if modifies {
+        let buffer = self.buffer_mut();
+        buffer.modified = buffer.document.to_string() != buffer.saved_content;
+      }
+      if selecting && self.document().plane().selection_mode().is_some() {
+        // the selection follows the cursor
+        self.repaint_plane();
+        self.update_cursor();
+        self.update_cursor_coordinates();
+        refresh();
+      }
+    }
+  }
+}
+
+/// Loads the content from file or from the standard input. When the standard input is consumed,
+/// it is replaced with the terminal device, so key-strokes can still be read.
+fn load_content(file_name: &str) -> Result<String> {
+  if file_name != STDIN_FILE_NAME {
+    return fs::read_to_string(file_name).map_err(|e| err_load_file(file_name, &e.to_string()));
+  }
+  let mut content = String::new();
+  io::stdin()
+    .read_to_string(&mut content)
+    .map_err(|e| err_load_file("standard input", &e.to_string()))?;
+  let terminal = fs::File::open(TERMINAL_DEVICE).map_err(|e| err_load_file(TERMINAL_DEVICE, &e.to_string()))?;
+  // SAFETY: both file descriptors are valid, the duplicated descriptor outlives the opened file
+  if unsafe { libc::dup2(terminal.as_raw_fd(), libc::STDIN_FILENO) } < 0 {
+    return Err(err_load_file(TERMINAL_DEVICE, &io::Error::last_os_error().to_string()));
+  }
+  Ok(content)
+}
+
+/// Reads a single key-stroke, returns the name of the key and the entered character.
+fn read_key() -> (Option<String>, Option<char>) {
+  match get_wch() {
+    Some(WchResult::KeyCode(key)) => (keyname(key), None),
+    // codes of characters outside ASCII overlap with codes of function keys
+    Some(WchResult::Char(code)) if code > 127 => (None, char::from_u32(code)),
+    Some(WchResult::Char(code)) => (keyname(code as i32), char::from_u32(code)),
+    None => (None, None),
+  }
+}
+
+/// Installs handlers of SIGTERM and SIGHUP signals requesting the editor to terminate.
+/// System calls are not restarted after the signal is handled, so reading the next key-stroke is interrupted.
+fn install_signal_handlers() {
+  extern "C" fn request_termination(_signal: libc::c_int) {
+    TERMINATION_REQUESTED.store(true, Ordering::SeqCst);
+  }
+  for signal in [libc::SIGTERM, libc::SIGHUP] {
+    // SAFETY: the handler only stores an atomic flag, which is async-signal-safe,
+    // and the action structure is fully initialized before it is passed to sigaction.
+    unsafe {
+      let mut action: libc::sigaction = std::mem::zeroed();
+      action.sa_sigaction = request_termination as extern "C" fn(libc::c_int) as libc::sighandler_t;
+      libc::sigemptyset(&mut action.sa_mask);
+      libc::sigaction(signal, &action, std::ptr::null_mut());
+    }
+  }
+}
+
+/// Reads the text pasted into the terminal, until the sequence ending the bracketed paste.
+fn read_pasted_text() -> String {
+  let mut bytes = vec![];
+  while !bytes.ends_with(PASTE_END) {
+    match getch() {
+      ERR => break,
+      // new lines are received as key codes when the keypad mode is on
+      KEY_ENTER => bytes.push(b'\n'),
+      key @ 0..=255 => bytes.push(key as u8),
+      _ => {}
+    }
+  }
+  if bytes.ends_with(PASTE_END) {
+    bytes.truncate(bytes.len() - PASTE_END.len());
+  }
+  String::from_utf8_lossy(&bytes).to_string()
+}
+
+/// Initializes colors of the theme, the monochrome theme is used
+/// when the terminal does not support colors or colors are disabled with `NO_COLOR` environment variable.
+fn initialize_colors(theme: &'static Theme) -> &'static Theme {
+  if theme.is_monochrome() || !has_colors() || std::env::var_os(NO_COLOR).is_some() {
+    return &MONOCHROME;
+  }
+  start_color();
+  use_default_colors();
+  for style in STYLES {
+    init_pair(style as i16 + 1, theme.appearance(style).color, -1);
+  }
+  theme
+}
+
+/// Sends the escape sequence directly to the terminal.
+fn send_to_terminal(sequence: &str) {
+  let mut stdout = io::stdout();
+  let _ = write!(stdout, "{}", sequence).and_then(|_| stdout.flush());
+}
+
+/// Sends the text to the clipboard of the terminal using OSC 52 escape sequence.
+fn send_to_terminal_clipboard(text: &str) {
+  send_to_terminal(&format!("\x1b]52;c;{}\x07", base64(text.as_bytes())));
+}
+
+/// Encodes bytes using base64 encoding with padding.
+fn base64(bytes: &[u8]) -> String {
+  const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut encoded = String::new();
+  for chunk in bytes.chunks(3) {
+    let triple = chunk
+      .iter()
+      .enumerate()
+      .fold(0u32, |triple, (index, byte)| triple | (*byte as u32) << (16 - 8 * index));
+    for index in 0..4 {
+      if index <= chunk.len() {
+        encoded.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3f) as usize] as char);
+      } else {
+        encoded.push('=');
       }
     }
   }
+  encoded
 }
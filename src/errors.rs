@@ -32,6 +32,8 @@
 
 //! Definition of the common error type.
 
+use std::fmt;
+
 /// Common result type.
 pub type Result<T, E = AttoError> = std::result::Result<T, E>;
 
@@ -39,7 +41,74 @@ pub type Result<T, E = AttoError> = std::result::Result<T, E>;
 #[derive(Debug)]
 pub struct AttoError(String);
 
+impl fmt::Display for AttoError {
+  /// Converts an [AttoError] into its string representation.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
 /// Creates and error indicating that loading input file failed.
 pub fn err_load_file(file_name: &str, reason: &str) -> AttoError {
   AttoError(format!("loading input file '{}' failed with reason: '{}'", file_name, reason))
 }
+
+/// Creates an error indicating that saving output file failed.
+pub fn err_save_file(file_name: &str, reason: &str) -> AttoError {
+  AttoError(format!("saving output file '{}' failed with reason: '{}'", file_name, reason))
+}
+
+/// Creates an error indicating that the decision table could not be recognized.
+pub fn err_recognize(reason: &str) -> AttoError {
+  AttoError(format!("recognizing decision table failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating that converting the decision table into crosstab failed.
+pub fn err_crosstab(reason: &str) -> AttoError {
+  AttoError(format!("converting decision table into crosstab failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating that moving the cursor to the specified cell failed.
+pub fn err_go_to(target: &str, reason: &str) -> AttoError {
+  AttoError(format!("going to '{}' failed with reason: '{}'", target, reason))
+}
+
+/// Creates an error indicating that sorting rules by the specified clauses failed.
+pub fn err_sort(keys: &str, reason: &str) -> AttoError {
+  AttoError(format!("sorting rules by '{}' failed with reason: '{}'", keys, reason))
+}
+
+/// Creates an error indicating that merging cells failed.
+pub fn err_merge_cells(reason: &str) -> AttoError {
+  AttoError(format!("merging cells failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating that merging decision tables failed.
+pub fn err_merge_tables(reason: &str) -> AttoError {
+  AttoError(format!("merging decision tables failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating that splitting the merged cell failed.
+pub fn err_split_cell(reason: &str) -> AttoError {
+  AttoError(format!("splitting cell failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating an invalid hit policy.
+pub fn err_invalid_hit_policy(text: &str) -> AttoError {
+  AttoError(format!("invalid hit policy: '{}'", text))
+}
+
+/// Creates an error indicating that the FEEL text could not be parsed.
+pub fn err_parse_feel(text: &str, reason: &str) -> AttoError {
+  AttoError(format!("parsing '{}' failed with reason: '{}'", text, reason))
+}
+
+/// Creates an error indicating that evaluating the decision table failed.
+pub fn err_evaluate(reason: &str) -> AttoError {
+  AttoError(format!("evaluation failed with reason: '{}'", reason))
+}
+
+/// Creates an error indicating that test cases are invalid.
+pub fn err_invalid_test_cases(reason: &str) -> AttoError {
+  AttoError(format!("invalid test cases: '{}'", reason))
+}
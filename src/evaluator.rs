@@ -0,0 +1,246 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluator of decision tables.
+
+use crate::errors::*;
+use crate::feel::*;
+use crate::model::*;
+use std::cmp::Ordering;
+
+/// Result of evaluating a decision table.
+pub struct Evaluation {
+  /// Indexes of all rules that matched the input values.
+  pub matched_rules: Vec<usize>,
+  /// Indexes of rules with input entries that could not be parsed, with the reason; such rules never match.
+  pub invalid_rules: Vec<(usize, String)>,
+  /// Result of the decision table after applying the hit policy.
+  pub result: Result<Value>,
+}
+
+/// Evaluates the decision table for specified input values, one value per input clause.
+pub fn evaluate(decision_table: &DecisionTable, input_values: &[Value]) -> Result<Evaluation> {
+  if input_values.len() != decision_table.input_clauses.len() {
+    return Err(err_evaluate(&format!(
+      "expected {} input values, provided {}",
+      decision_table.input_clauses.len(),
+      input_values.len()
+    )));
+  }
+  let mut matched_rules = vec![];
+  let mut invalid_rules = vec![];
+  for (rule_index, rule) in decision_table.rules.iter().enumerate() {
+    // a rule with invalid input entries is reported and the remaining rules are evaluated
+    let tests = match rule
+      .input_entries
+      .iter()
+      .map(|input_entry| UnaryTests::parse(&input_entry.text()))
+      .collect::<Result<Vec<UnaryTests>>>()
+    {
+      Ok(tests) => tests,
+      Err(reason) => {
+        invalid_rules.push((rule_index, reason.to_string()));
+        continue;
+      }
+    };
+    if tests.iter().zip(input_values).all(|(tests, input_value)| tests.matches(input_value)) {
+      matched_rules.push(rule_index);
+    }
+  }
+  let mut outputs = vec![];
+  for rule_index in &matched_rules {
+    outputs.push(rule_output(decision_table, *rule_index)?);
+  }
+  let result = match check_allowed_values(decision_table, input_values) {
+    Ok(()) => apply_hit_policy(decision_table, &matched_rules, outputs),
+    Err(reason) => Err(reason),
+  };
+  Ok(Evaluation {
+    matched_rules,
+    invalid_rules,
+    result,
+  })
+}
+
+/// Checks if input values satisfy allowed values defined in input clauses.
+fn check_allowed_values(decision_table: &DecisionTable, input_values: &[Value]) -> Result<()> {
+  for (input_clause, input_value) in decision_table.input_clauses.iter().zip(input_values) {
+    if let Some(allowed_values) = input_clause.allowed_values.as_ref().filter(|cell| !cell.is_empty()) {
+      if !UnaryTests::parse(&allowed_values.text())?.matches(input_value) {
+        return Err(err_evaluate(&format!(
+          "value {} is not allowed for input '{}'",
+          input_value,
+          input_clause.input_expression.text()
+        )));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Returns output values of the rule; a context is returned when the table has multiple outputs.
+fn rule_output(decision_table: &DecisionTable, rule_index: usize) -> Result<Value> {
+  let output_entries = &decision_table.rules[rule_index].output_entries;
+  if output_entries.len() == 1 {
+    output_value(&output_entries[0])
+  } else {
+    let mut entries = vec![];
+    for (name, output_entry) in decision_table.output_names().into_iter().zip(output_entries) {
+      entries.push((name, output_value(output_entry)?));
+    }
+    Ok(Value::Context(entries))
+  }
+}
+
+/// Returns the value of the output entry, an empty entry or `-` is null.
+fn output_value(output_entry: &Cell) -> Result<Value> {
+  match output_entry.text().as_str() {
+    "" | "-" => Ok(Value::Null),
+    text => parse_value(text),
+  }
+}
+
+/// Returns output values of the rule as a list, one value per output clause.
+fn output_components(value: &Value) -> Vec<&Value> {
+  match value {
+    Value::Context(entries) => entries.iter().map(|(_, value)| value).collect(),
+    other => vec![other],
+  }
+}
+
+/// Calculates the priority of each output value, lower number means higher priority.
+fn priorities(decision_table: &DecisionTable, output: &Value) -> Result<Vec<usize>> {
+  let mut priorities = vec![];
+  for (output_clause, value) in decision_table.output_clauses.iter().zip(output_components(output)) {
+    let allowed_values = match &output_clause.allowed_values {
+      Some(cell) if !cell.is_empty() => parse_value_list(&cell.text())?,
+      _ => vec![],
+    };
+    let priority = allowed_values.iter().position(|allowed| allowed == value).unwrap_or(allowed_values.len());
+    priorities.push(priority);
+  }
+  Ok(priorities)
+}
+
+/// Applies the hit policy to outputs of matched rules.
+fn apply_hit_policy(decision_table: &DecisionTable, matched_rules: &[usize], outputs: Vec<Value>) -> Result<Value> {
+  let rule_numbers = || matched_rules.iter().map(|index| (index + 1).to_string()).collect::<Vec<String>>().join(", ");
+  match decision_table.hit_policy {
+    HitPolicy::Unique => match outputs.len() {
+      0 => Ok(Value::Null),
+      1 => Ok(outputs[0].clone()),
+      _ => Err(err_evaluate(&format!("hit policy U violated, matched rules: {}", rule_numbers()))),
+    },
+    HitPolicy::Any => {
+      if outputs.windows(2).any(|w| w[0] != w[1]) {
+        Err(err_evaluate(&format!("hit policy A violated, different outputs in rules: {}", rule_numbers())))
+      } else {
+        Ok(outputs.into_iter().next().unwrap_or(Value::Null))
+      }
+    }
+    HitPolicy::First => Ok(outputs.into_iter().next().unwrap_or(Value::Null)),
+    HitPolicy::Priority => {
+      let mut best: Option<(Vec<usize>, Value)> = None;
+      for output in outputs {
+        let priority = priorities(decision_table, &output)?;
+        if best.as_ref().is_none_or(|(best_priority, _)| priority < *best_priority) {
+          best = Some((priority, output));
+        }
+      }
+      Ok(best.map(|(_, output)| output).unwrap_or(Value::Null))
+    }
+    HitPolicy::OutputOrder => {
+      let mut prioritized = vec![];
+      for output in outputs {
+        prioritized.push((priorities(decision_table, &output)?, output));
+      }
+      prioritized.sort_by(|(a, _), (b, _)| a.cmp(b));
+      Ok(Value::List(prioritized.into_iter().map(|(_, output)| output).collect()))
+    }
+    HitPolicy::RuleOrder | HitPolicy::Collect(None) => Ok(Value::List(outputs)),
+    HitPolicy::Collect(Some(aggregator)) => {
+      if decision_table.output_clauses.len() > 1 {
+        return Err(err_evaluate("aggregation is allowed only for a single output"));
+      }
+      aggregate(aggregator, outputs)
+    }
+  }
+}
+
+/// Aggregates output values using built-in aggregator.
+fn aggregate(aggregator: BuiltinAggregator, outputs: Vec<Value>) -> Result<Value> {
+  match aggregator {
+    BuiltinAggregator::Count => {
+      let mut distinct: Vec<Value> = vec![];
+      for output in outputs {
+        if !distinct.contains(&output) {
+          distinct.push(output);
+        }
+      }
+      Ok(Value::Number(distinct.len() as f64))
+    }
+    BuiltinAggregator::Sum => {
+      if outputs.is_empty() {
+        return Ok(Value::Null);
+      }
+      let mut sum = 0.0;
+      for output in outputs {
+        if let Value::Number(value) = output {
+          sum += value;
+        } else {
+          return Err(err_evaluate(&format!("only numbers can be summed, found {}", output)));
+        }
+      }
+      Ok(Value::Number(sum))
+    }
+    BuiltinAggregator::Min | BuiltinAggregator::Max => {
+      let expected = if aggregator == BuiltinAggregator::Min {
+        Ordering::Less
+      } else {
+        Ordering::Greater
+      };
+      let mut result: Option<Value> = None;
+      for output in outputs {
+        if let Some(current) = &result {
+          match output.compare(current) {
+            Some(ordering) if ordering == expected => result = Some(output),
+            Some(_) => {}
+            None => return Err(err_evaluate(&format!("values {} and {} are not comparable", output, current))),
+          }
+        } else {
+          result = Some(output);
+        }
+      }
+      Ok(result.unwrap_or(Value::Null))
+    }
+  }
+}
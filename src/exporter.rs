@@ -0,0 +1,183 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Exporting decision tables to DMN.
+
+use crate::model::*;
+
+/// Namespace of the DMN model elements.
+const DMN_NAMESPACE: &str = "https://www.omg.org/spec/DMN/20191111/MODEL/";
+
+/// Prefix of the namespace of exported models, followed by the name of the model.
+const MODEL_NAMESPACE: &str = "urn:dmntk:atto:";
+
+/// Exports decision tables as decisions of a single DMN model with the specified name.
+pub fn export(name: &str, decision_tables: &[DecisionTable]) -> String {
+  let mut xml = vec![
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+    format!(
+      r#"<definitions xmlns="{}" id="_definitions" name="{}" namespace="{}{}">"#,
+      DMN_NAMESPACE,
+      attribute(name),
+      MODEL_NAMESPACE,
+      attribute(name)
+    ),
+  ];
+  for (index, decision_table) in decision_tables.iter().enumerate() {
+    export_decision(&mut xml, &format!("_{}", index + 1), &decision_name(decision_table, index), decision_table);
+  }
+  xml.push("</definitions>".to_string());
+  xml.join("\n") + "\n"
+}
+
+/// Returns the name of the decision: the name of the information item, the output label or a generated name.
+fn decision_name(decision_table: &DecisionTable, index: usize) -> String {
+  decision_table
+    .information_item_name
+    .iter()
+    .chain(decision_table.output_label.iter())
+    .map(Cell::text)
+    .find(|text| !text.is_empty())
+    .unwrap_or_else(|| format!("Decision {}", index + 1))
+}
+
+/// Exports a single decision table as a decision.
+fn export_decision(xml: &mut Vec<String>, id: &str, name: &str, dt: &DecisionTable) {
+  xml.push(format!(r#"  <decision id="{}" name="{}">"#, id, attribute(name)));
+  xml.push(format!(r#"    <variable name="{}"/>"#, attribute(name)));
+  let (hit_policy, aggregation) = hit_policy_attributes(dt.hit_policy);
+  let mut attributes = format!(r#"id="{}_dt" hitPolicy="{}""#, id, hit_policy);
+  if let Some(aggregation) = aggregation {
+    attributes.push_str(&format!(r#" aggregation="{}""#, aggregation));
+  }
+  if let Some(label) = dt.output_label.as_ref().filter(|label| !label.is_empty()) {
+    attributes.push_str(&format!(r#" outputLabel="{}""#, attribute(&label.text())));
+  }
+  attributes.push_str(&format!(r#" preferredOrientation="{}""#, orientation_attribute(dt.orientation)));
+  xml.push(format!("    <decisionTable {}>", attributes));
+  for (index, input_clause) in dt.input_clauses.iter().enumerate() {
+    let expression = input_clause.input_expression.text();
+    xml.push(format!(r#"      <input id="{}_i{}" label="{}">"#, id, index + 1, attribute(&expression)));
+    xml.push(format!(r#"        <inputExpression id="{}_ie{}">"#, id, index + 1));
+    xml.push(text_element("          ", &expression));
+    xml.push("        </inputExpression>".to_string());
+    if let Some(allowed_values) = input_clause.allowed_values.as_ref().filter(|cell| !cell.is_empty()) {
+      xml.push("        <inputValues>".to_string());
+      xml.push(text_element("          ", &allowed_values.text()));
+      xml.push("        </inputValues>".to_string());
+    }
+    xml.push("      </input>".to_string());
+  }
+  for (index, output_clause) in dt.output_clauses.iter().enumerate() {
+    let mut attributes = format!(r#"id="{}_o{}""#, id, index + 1);
+    if let Some(name) = output_clause.name.as_ref().filter(|name| !name.is_empty()) {
+      attributes.push_str(&format!(r#" name="{}""#, attribute(&name.text())));
+    }
+    if let Some(allowed_values) = output_clause.allowed_values.as_ref().filter(|cell| !cell.is_empty()) {
+      xml.push(format!("      <output {}>", attributes));
+      xml.push("        <outputValues>".to_string());
+      xml.push(text_element("          ", &allowed_values.text()));
+      xml.push("        </outputValues>".to_string());
+      xml.push("      </output>".to_string());
+    } else {
+      xml.push(format!("      <output {}/>", attributes));
+    }
+  }
+  for annotation_clause in &dt.annotation_clauses {
+    xml.push(format!(r#"      <annotation name="{}"/>"#, attribute(&annotation_clause.name.text())));
+  }
+  for (index, rule) in dt.rules.iter().enumerate() {
+    let rule_id = format!("{}_r{}", id, index + 1);
+    xml.push(format!(r#"      <rule id="{}">"#, rule_id));
+    for (entry_index, entry) in rule.input_entries.iter().enumerate() {
+      // empty input entries are irrelevant
+      let text = if entry.is_empty() { "-".to_string() } else { entry.text() };
+      xml.push(format!(r#"        <inputEntry id="{}_i{}">"#, rule_id, entry_index + 1));
+      xml.push(text_element("          ", &text));
+      xml.push("        </inputEntry>".to_string());
+    }
+    for (entry_index, entry) in rule.output_entries.iter().enumerate() {
+      xml.push(format!(r#"        <outputEntry id="{}_o{}">"#, rule_id, entry_index + 1));
+      xml.push(text_element("          ", &entry.text()));
+      xml.push("        </outputEntry>".to_string());
+    }
+    for entry in &rule.annotation_entries {
+      xml.push("        <annotationEntry>".to_string());
+      xml.push(text_element("          ", &entry.text()));
+      xml.push("        </annotationEntry>".to_string());
+    }
+    xml.push("      </rule>".to_string());
+  }
+  xml.push("    </decisionTable>".to_string());
+  xml.push("  </decision>".to_string());
+}
+
+/// Returns the values of `hitPolicy` and `aggregation` attributes.
+fn hit_policy_attributes(hit_policy: HitPolicy) -> (&'static str, Option<&'static str>) {
+  match hit_policy {
+    HitPolicy::Unique => ("UNIQUE", None),
+    HitPolicy::Any => ("ANY", None),
+    HitPolicy::Priority => ("PRIORITY", None),
+    HitPolicy::First => ("FIRST", None),
+    HitPolicy::Collect(None) => ("COLLECT", None),
+    HitPolicy::Collect(Some(BuiltinAggregator::Count)) => ("COLLECT", Some("COUNT")),
+    HitPolicy::Collect(Some(BuiltinAggregator::Sum)) => ("COLLECT", Some("SUM")),
+    HitPolicy::Collect(Some(BuiltinAggregator::Min)) => ("COLLECT", Some("MIN")),
+    HitPolicy::Collect(Some(BuiltinAggregator::Max)) => ("COLLECT", Some("MAX")),
+    HitPolicy::OutputOrder => ("OUTPUT ORDER", None),
+    HitPolicy::RuleOrder => ("RULE ORDER", None),
+  }
+}
+
+/// Returns the value of `preferredOrientation` attribute.
+fn orientation_attribute(orientation: Orientation) -> &'static str {
+  match orientation {
+    Orientation::RuleAsRow => "Rule-as-Row",
+    Orientation::RuleAsColumn => "Rule-as-Column",
+    Orientation::CrossTable => "CrossTable",
+  }
+}
+
+/// Returns the `text` element with escaped content.
+fn text_element(indent: &str, text: &str) -> String {
+  format!("{}<text>{}</text>", indent, escape(text))
+}
+
+/// Escapes characters having special meaning in XML text.
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Escapes characters having special meaning in XML attribute values.
+fn attribute(text: &str) -> String {
+  escape(text).replace('"', "&quot;")
+}
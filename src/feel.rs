@@ -0,0 +1,320 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Minimal subset of FEEL used in decision table cells.
+//!
+//! Supported are literals (numbers, strings, booleans and `null`),
+//! and unary tests: `-`, comparisons (`<`, `<=`, `>`, `>=`, `=`, `!=`),
+//! intervals like `[1..10)`, lists of tests separated with commas and negation `not(...)`.
+
+use crate::errors::*;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Display;
+
+/// FEEL value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Null,
+  Boolean(bool),
+  Number(f64),
+  String(String),
+  List(Vec<Value>),
+  Context(Vec<(String, Value)>),
+}
+
+impl Display for Value {
+  /// Converts a [Value] into its FEEL representation.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Value::Null => write!(f, "null"),
+      Value::Boolean(value) => write!(f, "{}", value),
+      Value::Number(value) => write!(f, "{}", number_to_string(*value)),
+      Value::String(value) => write!(f, "\"{}\"", value),
+      Value::List(items) => write!(f, "[{}]", items.iter().map(|item| item.to_string()).collect::<Vec<String>>().join(", ")),
+      Value::Context(entries) => write!(
+        f,
+        "{{{}}}",
+        entries
+          .iter()
+          .map(|(name, value)| format!("{}: {}", name, value))
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+    }
+  }
+}
+
+impl Value {
+  /// Compares two values, returns `None` when values are not comparable.
+  pub fn compare(&self, other: &Value) -> Option<Ordering> {
+    match (self, other) {
+      (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+      (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+      (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+      (Value::Null, Value::Null) => Some(Ordering::Equal),
+      _ => None,
+    }
+  }
+}
+
+/// Converts a number into text without trailing zeros and rounding noise.
+fn number_to_string(value: f64) -> String {
+  let text = format!("{:.12}", value);
+  let text = text.trim_end_matches('0').trim_end_matches('.');
+  if text == "-0" {
+    "0".to_string()
+  } else {
+    text.to_string()
+  }
+}
+
+/// Single unary test.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnaryTest {
+  /// Input value is equal to the specified value.
+  Equal(Value),
+  /// Input value is not equal to the specified value.
+  NotEqual(Value),
+  /// Input value is less than the specified value.
+  Less(Value),
+  /// Input value is less than or equal to the specified value.
+  LessOrEqual(Value),
+  /// Input value is greater than the specified value.
+  Greater(Value),
+  /// Input value is greater than or equal to the specified value.
+  GreaterOrEqual(Value),
+  /// Input value is in interval (start, start closed, end, end closed).
+  Interval(Value, bool, Value, bool),
+}
+
+impl UnaryTest {
+  /// Returns all values used in the test.
+  pub fn values(&self) -> Vec<&Value> {
+    match self {
+      UnaryTest::Equal(value)
+      | UnaryTest::NotEqual(value)
+      | UnaryTest::Less(value)
+      | UnaryTest::LessOrEqual(value)
+      | UnaryTest::Greater(value)
+      | UnaryTest::GreaterOrEqual(value) => vec![value],
+      UnaryTest::Interval(start, _, end, _) => vec![start, end],
+    }
+  }
+
+  /// Returns `true` when the value satisfies the test.
+  pub fn matches(&self, value: &Value) -> bool {
+    match self {
+      UnaryTest::Equal(expected) => value.compare(expected) == Some(Ordering::Equal),
+      UnaryTest::NotEqual(expected) => matches!(value.compare(expected), Some(Ordering::Less | Ordering::Greater)),
+      UnaryTest::Less(end) => value.compare(end) == Some(Ordering::Less),
+      UnaryTest::LessOrEqual(end) => matches!(value.compare(end), Some(Ordering::Less | Ordering::Equal)),
+      UnaryTest::Greater(start) => value.compare(start) == Some(Ordering::Greater),
+      UnaryTest::GreaterOrEqual(start) => matches!(value.compare(start), Some(Ordering::Greater | Ordering::Equal)),
+      UnaryTest::Interval(start, start_closed, end, end_closed) => {
+        let after_start = match value.compare(start) {
+          Some(Ordering::Greater) => true,
+          Some(Ordering::Equal) => *start_closed,
+          _ => false,
+        };
+        let before_end = match value.compare(end) {
+          Some(Ordering::Less) => true,
+          Some(Ordering::Equal) => *end_closed,
+          _ => false,
+        };
+        after_start && before_end
+      }
+    }
+  }
+}
+
+/// Unary tests placed in an input entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnaryTests {
+  /// Any value matches (`-`).
+  Any,
+  /// Value matches when at least one of the tests is satisfied.
+  Positive(Vec<UnaryTest>),
+  /// Value matches when none of the tests is satisfied (`not(...)`).
+  Negative(Vec<UnaryTest>),
+}
+
+impl UnaryTests {
+  /// Parses the text of an input entry.
+  pub fn parse(text: &str) -> Result<Self> {
+    let text = text.trim();
+    if text == "-" || text.is_empty() {
+      return Ok(UnaryTests::Any);
+    }
+    if let Some(inner) = text
+      .strip_prefix("not")
+      .map(|s| s.trim_start())
+      .and_then(|s| s.strip_prefix('('))
+      .and_then(|s| s.strip_suffix(')'))
+    {
+      return Ok(UnaryTests::Negative(parse_unary_test_list(inner)?));
+    }
+    Ok(UnaryTests::Positive(parse_unary_test_list(text)?))
+  }
+
+  /// Returns all values used in unary tests.
+  pub fn values(&self) -> Vec<&Value> {
+    match self {
+      UnaryTests::Any => vec![],
+      UnaryTests::Positive(tests) | UnaryTests::Negative(tests) => tests.iter().flat_map(|test| test.values()).collect(),
+    }
+  }
+
+  /// Returns `true` when the value satisfies unary tests.
+  pub fn matches(&self, value: &Value) -> bool {
+    match self {
+      UnaryTests::Any => true,
+      UnaryTests::Positive(tests) => tests.iter().any(|test| test.matches(value)),
+      UnaryTests::Negative(tests) => !tests.iter().any(|test| test.matches(value)),
+    }
+  }
+}
+
+/// Parses a list of unary tests separated with commas.
+fn parse_unary_test_list(text: &str) -> Result<Vec<UnaryTest>> {
+  split_list(text).iter().map(|item| parse_unary_test(item)).collect()
+}
+
+/// Parses a single unary test.
+fn parse_unary_test(text: &str) -> Result<UnaryTest> {
+  let text = text.trim();
+  for (prefix, constructor) in [
+    ("<=", UnaryTest::LessOrEqual as fn(Value) -> UnaryTest),
+    (">=", UnaryTest::GreaterOrEqual),
+    ("!=", UnaryTest::NotEqual),
+    ("<", UnaryTest::Less),
+    (">", UnaryTest::Greater),
+    ("=", UnaryTest::Equal),
+  ] {
+    if let Some(rest) = text.strip_prefix(prefix) {
+      return Ok(constructor(parse_value(rest)?));
+    }
+  }
+  let first = text.chars().next();
+  let last = text.chars().last();
+  if matches!(first, Some('[' | '(' | ']')) && matches!(last, Some(']' | ')' | '[')) && text.contains("..") {
+    let inner = &text[1..text.len() - 1];
+    if let Some((start, end)) = inner.split_once("..") {
+      return Ok(UnaryTest::Interval(
+        parse_value(start)?,
+        first == Some('['),
+        parse_value(end)?,
+        last == Some(']'),
+      ));
+    }
+  }
+  Ok(UnaryTest::Equal(parse_value(text)?))
+}
+
+/// Parses a FEEL literal value.
+pub fn parse_value(text: &str) -> Result<Value> {
+  let text = text.trim();
+  match text {
+    "" => Err(err_parse_feel(text, "expected value")),
+    "null" => Ok(Value::Null),
+    "true" => Ok(Value::Boolean(true)),
+    "false" => Ok(Value::Boolean(false)),
+    _ => {
+      if let Some(inner) = text.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        if text.len() >= 2 {
+          return Ok(Value::String(inner.replace("\\\"", "\"")));
+        }
+      }
+      if text.starts_with('[') && text.ends_with(']') {
+        return Ok(Value::List(
+          split_list(&text[1..text.len() - 1])
+            .iter()
+            .map(|item| parse_value(item))
+            .collect::<Result<Vec<Value>>>()?,
+        ));
+      }
+      if is_number(text) {
+        if let Ok(value) = text.parse::<f64>() {
+          return Ok(Value::Number(value));
+        }
+      }
+      Err(err_parse_feel(text, "unsupported expression"))
+    }
+  }
+}
+
+/// Parses a list of values separated with commas, like allowed values.
+pub fn parse_value_list(text: &str) -> Result<Vec<Value>> {
+  split_list(text).iter().map(|item| parse_value(item)).collect()
+}
+
+/// Returns `true` when the text is a FEEL number literal.
+fn is_number(text: &str) -> bool {
+  let digits = text.strip_prefix('-').unwrap_or(text);
+  let mut parts = digits.splitn(2, '.');
+  let integer = parts.next().unwrap_or("");
+  let fraction = parts.next();
+  let all_digits = |s: &str| s.chars().all(|ch| ch.is_ascii_digit());
+  match fraction {
+    Some(fraction) => all_digits(integer) && !fraction.is_empty() && all_digits(fraction),
+    None => !integer.is_empty() && all_digits(integer),
+  }
+}
+
+/// Splits the text into items separated with commas placed outside strings, parentheses and brackets.
+pub fn split_list(text: &str) -> Vec<String> {
+  let mut items = vec![];
+  let mut item = String::new();
+  let mut in_string = false;
+  let mut escaped = false;
+  let mut depth = 0_usize;
+  for ch in text.chars() {
+    match ch {
+      _ if escaped => escaped = false,
+      '\\' if in_string => escaped = true,
+      '"' => in_string = !in_string,
+      '(' | '[' if !in_string => depth += 1,
+      ')' | ']' if !in_string => depth = depth.saturating_sub(1),
+      ',' if !in_string && depth == 0 => {
+        items.push(item.trim().to_string());
+        item.clear();
+        continue;
+      }
+      _ => {}
+    }
+    item.push(ch);
+  }
+  if !item.trim().is_empty() || !items.is_empty() {
+    items.push(item.trim().to_string());
+  }
+  items
+}
@@ -0,0 +1,608 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Canonical rendering of decision tables.
+
+use crate::box_drawing::junction;
+use crate::feel::{parse_value, Value};
+use crate::model::*;
+use crate::unicode::{text_width, Clusters};
+
+/// Alignment of the text inside a cell.
+#[derive(Copy, Clone)]
+enum Alignment {
+  Left,
+  Center,
+  Right,
+}
+
+/// Rectangular area of characters with lines drawn between them.
+struct Canvas {
+  /// Characters placed on the canvas.
+  chars: Vec<Vec<char>>,
+  /// Weights of arms of lines crossing each position: up, right, down, left.
+  arms: Vec<Vec<[u8; 4]>>,
+  /// Text of clusters placed on the canvas.
+  clusters: Clusters,
+}
+
+impl Canvas {
+  /// Creates an empty canvas with the specified size.
+  fn new(height: usize, width: usize) -> Self {
+    Self {
+      chars: vec![vec![' '; width]; height],
+      arms: vec![vec![[0; 4]; width]; height],
+      clusters: Clusters::default(),
+    }
+  }
+
+  /// Draws horizontal line from `left` to `right` in the specified row.
+  fn horizontal(&mut self, row: usize, left: usize, right: usize, weight: u8) {
+    for col in left..right {
+      self.arms[row][col][1] = weight;
+      self.arms[row][col + 1][3] = weight;
+    }
+  }
+
+  /// Draws vertical line from `top` to `bottom` in the specified column.
+  fn vertical(&mut self, col: usize, top: usize, bottom: usize, weight: u8) {
+    for row in top..bottom {
+      self.arms[row][col][2] = weight;
+      self.arms[row + 1][col][0] = weight;
+    }
+  }
+
+  /// Places lines of text in the cell with the specified position and width (including padding).
+  fn text(&mut self, top: usize, left: usize, width: usize, lines: &[String], alignment: Alignment) {
+    for (offset, line) in lines.iter().enumerate() {
+      let line_alignment = if line.trim() == "-" { Alignment::Center } else { alignment };
+      for (col, ch) in self.clusters.columns(&align(line, width, line_alignment)).into_iter().enumerate() {
+        self.chars[top + offset][left + col] = ch;
+      }
+    }
+  }
+
+  /// Converts the canvas into lines of text.
+  fn lines(&self) -> Vec<String> {
+    self
+      .chars
+      .iter()
+      .zip(self.arms.iter())
+      .map(|(chars, arms)| {
+        let columns: Vec<char> = chars
+          .iter()
+          .zip(arms.iter())
+          .map(|(ch, arms)| if *arms == [0; 4] { *ch } else { junction(*arms).unwrap_or('?') })
+          .collect();
+        self.clusters.text(&columns).trim_end().to_string()
+      })
+      .collect()
+  }
+}
+
+/// Aligns the text in a cell having the specified width, one space is reserved on both sides.
+fn align(text: &str, width: usize, alignment: Alignment) -> String {
+  let space = width - 2 - text_width(text);
+  let (before, after) = match alignment {
+    Alignment::Left => (0, space),
+    Alignment::Center => (space / 2, space - space / 2),
+    Alignment::Right => (space, 0),
+  };
+  format!(" {}{}{} ", " ".repeat(before), text, " ".repeat(after))
+}
+
+/// Returns the alignment of the entry, numbers are aligned to the right, other values to the left.
+fn alignment_of(cell: &Cell) -> Alignment {
+  let is_number = |line: &String| matches!(parse_value(line.trim_end_matches(',')), Ok(Value::Number(_)));
+  if !cell.is_empty() && cell.lines.iter().filter(|line| !line.is_empty()).all(is_number) {
+    Alignment::Right
+  } else {
+    Alignment::Left
+  }
+}
+
+/// Returns the number of columns occupied by the longest line of the cell.
+fn width_of(cell: &Cell) -> usize {
+  cell.lines.iter().map(|line| text_width(line)).max().unwrap_or(0)
+}
+
+/// Returns the number of lines of the cell, at least one.
+fn height_of(cell: &Cell) -> usize {
+  cell.lines.len().max(1)
+}
+
+/// Returns the cell containing the hit policy, versions of the hit policy conflicting in merging are kept unchanged.
+fn hit_policy_of(dt: &DecisionTable) -> Cell {
+  let conflict = dt.hit_policy_cell.lines.iter().any(|line| line == CONFLICT_BEGIN);
+  Cell {
+    lines: if conflict {
+      dt.hit_policy_cell.lines.clone()
+    } else {
+      vec![dt.hit_policy.to_string()]
+    },
+    region: dt.hit_policy_cell.region,
+  }
+}
+
+/// Returns lines of the cell without leading empty lines.
+fn top_aligned(lines: &[String]) -> &[String] {
+  &lines[lines.iter().take_while(|line| line.is_empty()).count()..]
+}
+
+/// Returns the cell containing allowed values, when present and not empty.
+fn allowed(cell: &Option<Cell>) -> Option<&Cell> {
+  cell.as_ref().filter(|cell| !cell.is_empty())
+}
+
+/// Renders the decision table in canonical form: each column is as narrow as its widest cell
+/// plus one space of padding on both sides, numbers are aligned to the right, other values
+/// to the left, headers are centered and all junctions are drawn with matching characters.
+pub fn format(decision_table: &DecisionTable) -> String {
+  match decision_table.orientation {
+    Orientation::RuleAsRow => format_rules_as_rows(decision_table),
+    Orientation::RuleAsColumn => format_rules_as_columns(decision_table),
+    Orientation::CrossTable => format_crosstab(decision_table),
+  }
+}
+
+/// Renders the decision table with rules placed in rows.
+fn format_rules_as_rows(dt: &DecisionTable) -> String {
+  let inputs = dt.input_clauses.len();
+  let outputs = dt.output_clauses.len();
+  let multiple_outputs = outputs > 1;
+  let hit_policy = hit_policy_of(dt);
+  let empty = Cell {
+    lines: vec![],
+    region: dt.hit_policy_cell.region,
+  };
+  let label = dt.output_label.as_ref().unwrap_or(&empty);
+  // header cells of all columns following the hit policy column
+  let headers: Vec<&Cell> = dt
+    .input_clauses
+    .iter()
+    .map(|clause| &clause.input_expression)
+    .chain(
+      dt.output_clauses
+        .iter()
+        .map(|clause| if multiple_outputs { clause.name.as_ref().unwrap_or(&empty) } else { label }),
+    )
+    .chain(dt.annotation_clauses.iter().map(|clause| &clause.name))
+    .collect();
+  let allowed_values: Vec<Option<&Cell>> = dt
+    .input_clauses
+    .iter()
+    .map(|clause| allowed(&clause.allowed_values))
+    .chain(dt.output_clauses.iter().map(|clause| allowed(&clause.allowed_values)))
+    .chain(dt.annotation_clauses.iter().map(|_| None))
+    .collect();
+  let has_allowed_values = allowed_values.iter().any(Option::is_some);
+  let entries = |rule: &DecisionRule| -> Vec<Cell> { rule.entries().cloned().collect() };
+  // widths of columns, including padding
+  let mut widths = vec![dt.rules.iter().map(|rule| width_of(&rule.number)).fold(width_of(&hit_policy), usize::max) + 2];
+  for (index, header) in headers.iter().enumerate() {
+    let mut width = width_of(header);
+    width = width.max(allowed_values[index].map_or(0, width_of));
+    for rule in &dt.rules {
+      width = width.max(width_of(&entries(rule)[index]));
+    }
+    widths.push(width.max(1) + 2);
+  }
+  if multiple_outputs {
+    let span: usize = widths[1 + inputs..1 + inputs + outputs].iter().sum::<usize>() + outputs - 1;
+    let required = width_of(label) + 2;
+    if required > span {
+      widths[inputs + outputs] += required - span;
+    }
+  }
+  // horizontal positions of vertical lines
+  let mut borders = vec![0];
+  for width in &widths {
+    borders.push(borders.last().unwrap() + width + 1);
+  }
+  // vertical layout of the header
+  let label_height = if multiple_outputs { height_of(label) } else { 0 };
+  let mut header_height = headers.iter().map(|cell| height_of(cell)).max().unwrap_or(1);
+  if multiple_outputs {
+    let side_height = headers
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| !(inputs..inputs + outputs).contains(index))
+      .map(|(_, cell)| height_of(cell))
+      .max()
+      .unwrap_or(1);
+    let names_height = headers[inputs..inputs + outputs].iter().map(|cell| height_of(cell)).max().unwrap_or(1);
+    let names_height = names_height.max(side_height.saturating_sub(label_height + 1));
+    header_height = label_height + 1 + names_height;
+  }
+  header_height = header_height.max(height_of(&hit_policy));
+  let allowed_height = allowed_values.iter().flatten().map(|cell| height_of(cell)).max().unwrap_or(0);
+  // box with the name of the information item
+  let name = dt.information_item_name.as_ref().filter(|cell| !cell.is_empty());
+  let name_height = name.map_or(0, height_of);
+  let header_top = if name.is_some() { name_height + 1 } else { 0 };
+  let body_top = header_top + header_height + 1 + if has_allowed_values { allowed_height + 1 } else { 0 };
+  let rule_heights: Vec<usize> = dt
+    .rules
+    .iter()
+    .map(|rule| entries(rule).iter().chain(std::iter::once(&rule.number)).map(height_of).max().unwrap_or(1))
+    .collect();
+  let bottom = body_top + rule_heights.iter().map(|height| height + 1).sum::<usize>().max(1);
+  let doubles: Vec<usize> = borders.iter().copied().filter(|border| is_double(&borders, *border, inputs, outputs)).collect();
+  let name_right = name.map_or(0, |cell| name_right(cell, &mut widths, &mut borders, &doubles));
+  let right = *borders.last().unwrap();
+  let mut canvas = Canvas::new(bottom + 1, right + 1);
+  if let Some(cell) = name {
+    draw_name(&mut canvas, cell, name_right, header_top);
+  }
+  // outline of the table and horizontal lines
+  canvas.horizontal(header_top, 0, right, 1);
+  canvas.vertical(0, header_top, bottom, 1);
+  canvas.vertical(right, header_top, bottom, 1);
+  canvas.horizontal(body_top, 0, right, 2);
+  canvas.horizontal(bottom, 0, right, 1);
+  let label_bottom = header_top + label_height + 1;
+  if multiple_outputs {
+    canvas.horizontal(label_bottom, borders[1 + inputs], borders[1 + inputs + outputs], 1);
+  }
+  let allowed_top = header_top + header_height + 1;
+  if has_allowed_values {
+    canvas.horizontal(allowed_top, borders[1], right, 1);
+  }
+  let mut rule_tops = vec![];
+  let mut top = body_top;
+  for height in &rule_heights {
+    rule_tops.push(top + 1);
+    top += height + 1;
+    if top < bottom {
+      canvas.horizontal(top, 0, right, 1);
+    }
+  }
+  // vertical lines between columns
+  for (index, border) in borders.iter().enumerate().take(borders.len() - 1).skip(1) {
+    let inner_output = multiple_outputs && index > 1 + inputs && index < 1 + inputs + outputs;
+    let top = if inner_output { label_bottom } else { header_top };
+    let weight = if is_double(&borders, *border, inputs, outputs) { 2 } else { 1 };
+    canvas.vertical(*border, top, bottom, weight);
+  }
+  // content of the header
+  canvas.text(header_top + 1, 1, widths[0], &hit_policy.lines, Alignment::Left);
+  if multiple_outputs {
+    let left = borders[1 + inputs];
+    canvas.text(
+      header_top + 1,
+      left + 1,
+      borders[1 + inputs + outputs] - left - 1,
+      &label.lines,
+      Alignment::Center,
+    );
+  }
+  for (index, header) in headers.iter().enumerate() {
+    let is_output = (inputs..inputs + outputs).contains(&index);
+    let top = if multiple_outputs && is_output { label_bottom + 1 } else { header_top + 1 };
+    canvas.text(top, borders[index + 1] + 1, widths[index + 1], &header.lines, Alignment::Center);
+    if let Some(cell) = allowed_values[index] {
+      canvas.text(allowed_top + 1, borders[index + 1] + 1, widths[index + 1], &cell.lines, alignment_of(cell));
+    }
+  }
+  // content of rules
+  for (rule, top) in dt.rules.iter().zip(rule_tops) {
+    canvas.text(top, 1, widths[0], &rule.number.lines, Alignment::Left);
+    for (index, entry) in entries(rule).iter().enumerate() {
+      let alignment = if index < inputs + outputs { alignment_of(entry) } else { Alignment::Left };
+      canvas.text(top, borders[index + 1] + 1, widths[index + 1], &entry.lines, alignment);
+    }
+  }
+  canvas.lines().join("\n")
+}
+
+/// Renders the decision table with rules placed in columns.
+fn format_rules_as_columns(dt: &DecisionTable) -> String {
+  let inputs = dt.input_clauses.len();
+  let outputs = dt.output_clauses.len();
+  let hit_policy = hit_policy_of(dt);
+  let empty = Cell {
+    lines: vec![],
+    region: dt.hit_policy_cell.region,
+  };
+  // names of clauses placed in rows, the single output is named with the output label
+  let names: Vec<&Cell> = dt
+    .input_clauses
+    .iter()
+    .map(|clause| &clause.input_expression)
+    .chain(dt.output_clauses.iter().map(|clause| {
+      if outputs > 1 {
+        clause.name.as_ref().unwrap_or(&empty)
+      } else {
+        dt.output_label.as_ref().unwrap_or(&empty)
+      }
+    }))
+    .chain(dt.annotation_clauses.iter().map(|clause| &clause.name))
+    .collect();
+  let allowed_values: Vec<Option<&Cell>> = dt
+    .input_clauses
+    .iter()
+    .map(|clause| allowed(&clause.allowed_values))
+    .chain(dt.output_clauses.iter().map(|clause| allowed(&clause.allowed_values)))
+    .chain(dt.annotation_clauses.iter().map(|_| None))
+    .collect();
+  let has_allowed_values = allowed_values.iter().any(Option::is_some);
+  let entries = |rule: &DecisionRule| -> Vec<Cell> { rule.entries().cloned().collect() };
+  // widths of columns, including padding, the hit policy cell spans the name and allowed values columns
+  let mut widths = vec![names.iter().map(|cell| width_of(cell)).max().unwrap_or(0).max(1) + 2];
+  if has_allowed_values {
+    widths.push(allowed_values.iter().flatten().map(|cell| width_of(cell)).max().unwrap_or(0) + 2);
+  }
+  let header_width = widths.iter().sum::<usize>() + widths.len() - 1;
+  let required = width_of(&hit_policy) + 2;
+  if required > header_width {
+    widths[0] += required - header_width;
+  }
+  let header_columns = widths.len();
+  for rule in &dt.rules {
+    let width = entries(rule).iter().chain(std::iter::once(&rule.number)).map(width_of).max().unwrap_or(0);
+    widths.push(width.max(1) + 2);
+  }
+  let mut borders = vec![0];
+  for width in &widths {
+    borders.push(borders.last().unwrap() + width + 1);
+  }
+  let separator = borders[header_columns];
+  // vertical layout
+  let numbers_height = dt
+    .rules
+    .iter()
+    .map(|rule| height_of(&rule.number))
+    .max()
+    .unwrap_or(1)
+    .max(height_of(&hit_policy));
+  let clause_heights: Vec<usize> = names
+    .iter()
+    .enumerate()
+    .map(|(index, name)| {
+      dt.rules
+        .iter()
+        .map(|rule| height_of(&entries(rule)[index]))
+        .chain([top_aligned(&name.lines).len(), allowed_values[index].map_or(1, height_of)])
+        .max()
+        .unwrap_or(1)
+    })
+    .collect();
+  let name = dt.information_item_name.as_ref().filter(|cell| !cell.is_empty());
+  let header_top = name.map_or(0, |cell| height_of(cell) + 1);
+  let body_top = header_top + numbers_height + 1;
+  let bottom = body_top + clause_heights.iter().map(|height| height + 1).sum::<usize>().max(1);
+  let name_right = name.map_or(0, |cell| name_right(cell, &mut widths, &mut borders, &[separator]));
+  let right = *borders.last().unwrap();
+  let mut canvas = Canvas::new(bottom + 1, right + 1);
+  if let Some(cell) = name {
+    draw_name(&mut canvas, cell, name_right, header_top);
+  }
+  // outline of the table and lines between clauses, double lines separate inputs, outputs and annotations
+  canvas.horizontal(header_top, 0, right, 1);
+  canvas.vertical(0, header_top, bottom, 1);
+  canvas.vertical(right, header_top, bottom, 1);
+  canvas.horizontal(body_top, 0, right, 1);
+  canvas.horizontal(bottom, 0, right, 1);
+  let mut clause_tops = vec![];
+  let mut top = body_top;
+  for (index, height) in clause_heights.iter().enumerate() {
+    clause_tops.push(top + 1);
+    top += height + 1;
+    if top < bottom {
+      let weight = if index + 1 == inputs || index + 1 == inputs + outputs { 2 } else { 1 };
+      canvas.horizontal(top, 0, right, weight);
+    }
+  }
+  canvas.vertical(separator, header_top, bottom, 2);
+  if has_allowed_values {
+    canvas.vertical(borders[1], body_top, bottom, 1);
+  }
+  for border in borders.iter().take(borders.len() - 1).skip(header_columns + 1) {
+    canvas.vertical(*border, header_top, bottom, 1);
+  }
+  // content of the header and clauses
+  canvas.text(header_top + 1, 1, separator - 1, &hit_policy.lines, Alignment::Left);
+  for (index, (name, top)) in names.iter().zip(clause_tops.iter()).enumerate() {
+    canvas.text(*top, 1, widths[0], top_aligned(&name.lines), Alignment::Left);
+    if let Some(cell) = allowed_values[index] {
+      canvas.text(*top, borders[1] + 1, widths[1], &cell.lines, alignment_of(cell));
+    }
+  }
+  // content of rules
+  for (rule_index, rule) in dt.rules.iter().enumerate() {
+    let column = header_columns + rule_index;
+    canvas.text(header_top + 1, borders[column] + 1, widths[column], &rule.number.lines, Alignment::Left);
+    for (index, (entry, top)) in entries(rule).iter().zip(clause_tops.iter()).enumerate() {
+      let alignment = if index < inputs + outputs { alignment_of(entry) } else { Alignment::Left };
+      canvas.text(*top, borders[column] + 1, widths[column], &entry.lines, alignment);
+    }
+  }
+  canvas.lines().join("\n")
+}
+
+/// Renders the crosstab decision table, the input placed in rows is on the left side,
+/// the input placed in columns is above the grid of output entries.
+fn format_crosstab(dt: &DecisionTable) -> String {
+  let empty = Cell {
+    lines: vec![],
+    region: dt.hit_policy_cell.region,
+  };
+  let label = dt.output_label.as_ref().unwrap_or(&empty);
+  let row_name = &dt.input_clauses[0].input_expression;
+  let column_name = &dt.input_clauses[1].input_expression;
+  // values of inputs are shared by rules, the same region means the same value
+  let mut row_values: Vec<&Cell> = vec![];
+  let mut column_values: Vec<&Cell> = vec![];
+  for rule in &dt.rules {
+    if !row_values.iter().any(|cell| cell.region == rule.input_entries[0].region) {
+      row_values.push(&rule.input_entries[0]);
+    }
+    if !column_values.iter().any(|cell| cell.region == rule.input_entries[1].region) {
+      column_values.push(&rule.input_entries[1]);
+    }
+  }
+  let entry = |row: usize, column: usize| -> &Cell {
+    dt.rules
+      .iter()
+      .find(|rule| rule.input_entries[0].region == row_values[row].region && rule.input_entries[1].region == column_values[column].region)
+      .map_or(&empty, |rule| &rule.output_entries[0])
+  };
+  // widths of columns, including padding, the output label spans the name and values of the row input,
+  // the name of the column input spans all columns of the grid
+  let mut widths = vec![
+    width_of(row_name).max(1) + 2,
+    row_values.iter().map(|cell| width_of(cell)).max().unwrap_or(0).max(1) + 2,
+  ];
+  let required = width_of(label) + 2;
+  if required > widths[0] + widths[1] + 1 {
+    widths[1] = required - widths[0] - 1;
+  }
+  for (column, column_value) in column_values.iter().enumerate() {
+    let width = (0..row_values.len())
+      .map(|row| width_of(entry(row, column)))
+      .fold(width_of(column_value), usize::max);
+    widths.push(width.max(1) + 2);
+  }
+  let grid_width = widths[2..].iter().sum::<usize>() + widths.len() - 3;
+  let required = width_of(column_name) + 2;
+  if required > grid_width {
+    *widths.last_mut().unwrap() += required - grid_width;
+  }
+  let mut borders = vec![0];
+  for width in &widths {
+    borders.push(borders.last().unwrap() + width + 1);
+  }
+  let separator = borders[2];
+  // vertical layout
+  let name_height = height_of(column_name);
+  let mut values_height = column_values.iter().map(|cell| height_of(cell)).max().unwrap_or(1);
+  if height_of(label) > name_height + 1 + values_height {
+    values_height = height_of(label) - name_height - 1;
+  }
+  let mut row_heights: Vec<usize> = row_values
+    .iter()
+    .enumerate()
+    .map(|(row, row_value)| {
+      (0..column_values.len())
+        .map(|column| height_of(entry(row, column)))
+        .fold(height_of(row_value), usize::max)
+    })
+    .collect();
+  let rows_height = row_heights.iter().sum::<usize>() + row_heights.len() - 1;
+  let required = top_aligned(&row_name.lines).len();
+  if required > rows_height {
+    *row_heights.last_mut().unwrap() += required - rows_height;
+  }
+  let name = dt.information_item_name.as_ref().filter(|cell| !cell.is_empty());
+  let header_top = name.map_or(0, |cell| height_of(cell) + 1);
+  let values_top = header_top + name_height + 1;
+  let body_top = values_top + values_height + 1;
+  let bottom = body_top + row_heights.iter().map(|height| height + 1).sum::<usize>();
+  let name_right = name.map_or(0, |cell| name_right(cell, &mut widths, &mut borders, &[separator]));
+  let right = *borders.last().unwrap();
+  let mut canvas = Canvas::new(bottom + 1, right + 1);
+  if let Some(cell) = name {
+    draw_name(&mut canvas, cell, name_right, header_top);
+  }
+  // outline of the table, double lines separate the values of inputs from the grid of output entries
+  canvas.horizontal(header_top, 0, right, 1);
+  canvas.vertical(0, header_top, bottom, 1);
+  canvas.vertical(right, header_top, bottom, 1);
+  canvas.horizontal(bottom, 0, right, 1);
+  canvas.vertical(separator, header_top, bottom, 2);
+  canvas.horizontal(body_top, 0, right, 2);
+  canvas.horizontal(values_top, separator, right, 1);
+  canvas.vertical(borders[1], body_top, bottom, 1);
+  for border in borders.iter().take(borders.len() - 1).skip(3) {
+    canvas.vertical(*border, values_top, bottom, 1);
+  }
+  let mut row_tops = vec![];
+  let mut top = body_top;
+  for height in &row_heights {
+    row_tops.push(top + 1);
+    top += height + 1;
+    if top < bottom {
+      canvas.horizontal(top, borders[1], right, 1);
+    }
+  }
+  // content of the header and the grid
+  canvas.text(header_top + 1, 1, separator - 1, &label.lines, Alignment::Left);
+  canvas.text(header_top + 1, separator + 1, right - separator - 1, &column_name.lines, Alignment::Center);
+  canvas.text(body_top + 1, 1, widths[0], top_aligned(&row_name.lines), Alignment::Left);
+  for (column, column_value) in column_values.iter().enumerate() {
+    canvas.text(
+      values_top + 1,
+      borders[column + 2] + 1,
+      widths[column + 2],
+      &column_value.lines,
+      Alignment::Center,
+    );
+  }
+  for (row, (row_value, top)) in row_values.iter().zip(row_tops).enumerate() {
+    canvas.text(top, borders[1] + 1, widths[1], &row_value.lines, alignment_of(row_value));
+    for column in 0..column_values.len() {
+      let cell = entry(row, column);
+      canvas.text(top, borders[column + 2] + 1, widths[column + 2], &cell.lines, alignment_of(cell));
+    }
+  }
+  canvas.lines().join("\n")
+}
+
+/// Returns the position of the right border of the box containing the name of the information item,
+/// the border is moved to the right when it would meet a double line, the last column of the table
+/// is widened when the name is wider than the table.
+fn name_right(name: &Cell, widths: &mut [usize], borders: &mut [usize], doubles: &[usize]) -> usize {
+  let mut name_right = width_of(name) + 3;
+  while doubles.contains(&name_right) {
+    name_right += 1;
+  }
+  let right = *borders.last().unwrap();
+  if name_right > right {
+    *widths.last_mut().unwrap() += name_right - right;
+    *borders.last_mut().unwrap() = name_right;
+  }
+  name_right
+}
+
+/// Draws the box containing the name of the information item above the table.
+fn draw_name(canvas: &mut Canvas, name: &Cell, name_right: usize, header_top: usize) {
+  canvas.horizontal(0, 0, name_right, 1);
+  canvas.vertical(0, 0, header_top, 1);
+  canvas.vertical(name_right, 0, header_top, 1);
+  canvas.text(1, 1, name_right - 1, &name.lines, Alignment::Left);
+}
+
+/// Returns `true` when the vertical line at the specified position separates inputs from outputs or outputs from annotations.
+fn is_double(borders: &[usize], border: usize, inputs: usize, outputs: usize) -> bool {
+  let last = borders.len() - 1;
+  (border == borders[1 + inputs] || border == borders[1 + inputs + outputs]) && border != borders[last]
+}
@@ -0,0 +1,287 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Syntax highlighting of decision tables.
+
+use crate::box_drawing::is_box_drawing_character;
+use crate::model::{Cell, Orientation};
+use crate::plane::Plane;
+use crate::recognizer::recognize_structure;
+use ncurses::{COLOR_BLUE, COLOR_CYAN, COLOR_GREEN, COLOR_MAGENTA, COLOR_RED, COLOR_YELLOW};
+
+/// Default color of the terminal.
+const COLOR_DEFAULT: i16 = -1;
+
+/// Kind of the displayed character, the appearance of each kind is defined by the theme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Style {
+  /// Character without special meaning.
+  Text,
+  /// Box-drawing character of the table frame.
+  Frame,
+  /// Double line separating input, output and annotation clauses.
+  Separator,
+  /// Information item name, names of clauses and the output label.
+  Header,
+  /// Hit policy.
+  HitPolicy,
+  /// Rule number.
+  RuleNumber,
+  /// String literal.
+  StringLiteral,
+  /// Numeric literal.
+  NumberLiteral,
+  /// Comparison operator or range delimiter.
+  Operator,
+  /// Irrelevant input entry (`-`).
+  Wildcard,
+}
+
+/// All styles, the position of the style is its index in the theme.
+pub const STYLES: [Style; 10] = [
+  Style::Text,
+  Style::Frame,
+  Style::Separator,
+  Style::Header,
+  Style::HitPolicy,
+  Style::RuleNumber,
+  Style::StringLiteral,
+  Style::NumberLiteral,
+  Style::Operator,
+  Style::Wildcard,
+];
+
+/// Appearance of characters displayed in a single style.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Appearance {
+  /// Foreground color, `-1` is the default color of the terminal.
+  pub color: i16,
+  /// Flag indicating if characters are displayed in bold.
+  pub bold: bool,
+  /// Flag indicating if characters are underlined.
+  pub underline: bool,
+}
+
+/// Creates the appearance of characters.
+const fn look(color: i16, bold: bool, underline: bool) -> Appearance {
+  Appearance { color, bold, underline }
+}
+
+/// Color theme defining the appearance of each style.
+pub struct Theme {
+  /// Name of the theme.
+  pub name: &'static str,
+  /// Appearances of styles, in the order of [STYLES].
+  appearances: [Appearance; 10],
+}
+
+impl Theme {
+  /// Returns the appearance of characters displayed in the specified style.
+  pub fn appearance(&self, style: Style) -> Appearance {
+    self.appearances[style as usize]
+  }
+
+  /// Returns `true` when the theme uses no colors.
+  pub fn is_monochrome(&self) -> bool {
+    self.appearances.iter().all(|appearance| appearance.color == COLOR_DEFAULT)
+  }
+}
+
+/// Theme for terminals with dark background.
+const DARK: Theme = Theme {
+  name: "dark",
+  appearances: [
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_BLUE, false, false),
+    look(COLOR_CYAN, true, false),
+    look(COLOR_YELLOW, true, false),
+    look(COLOR_MAGENTA, true, false),
+    look(COLOR_CYAN, false, false),
+    look(COLOR_GREEN, false, false),
+    look(COLOR_MAGENTA, false, false),
+    look(COLOR_YELLOW, false, false),
+    look(COLOR_BLUE, true, false),
+  ],
+};
+
+/// Theme for terminals with light background.
+const LIGHT: Theme = Theme {
+  name: "light",
+  appearances: [
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_BLUE, false, false),
+    look(COLOR_BLUE, true, false),
+    look(COLOR_DEFAULT, true, false),
+    look(COLOR_MAGENTA, true, false),
+    look(COLOR_BLUE, false, false),
+    look(COLOR_GREEN, false, false),
+    look(COLOR_RED, false, false),
+    look(COLOR_MAGENTA, false, false),
+    look(COLOR_CYAN, false, false),
+  ],
+};
+
+/// Theme using only attributes, used also when the terminal does not support colors.
+pub const MONOCHROME: Theme = Theme {
+  name: "monochrome",
+  appearances: [
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_DEFAULT, true, false),
+    look(COLOR_DEFAULT, true, false),
+    look(COLOR_DEFAULT, true, true),
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_DEFAULT, false, true),
+    look(COLOR_DEFAULT, false, false),
+    look(COLOR_DEFAULT, true, false),
+    look(COLOR_DEFAULT, false, false),
+  ],
+};
+
+/// Available themes, the first one is the default.
+pub const THEMES: [&Theme; 3] = [&DARK, &LIGHT, &MONOCHROME];
+
+/// Returns the theme with the specified name, the default theme when there is no such theme.
+pub fn theme(name: &str) -> &'static Theme {
+  THEMES.iter().find(|theme| theme.name == name).unwrap_or(&THEMES[0])
+}
+
+/// Returns the style of each character in rows of the plane. Box-drawing characters
+/// are styled always, contents of cells only when the structure of the table is recognized.
+pub fn highlight(plane: &Plane) -> Vec<Vec<Style>> {
+  let rows = &plane.chars;
+  let mut styles: Vec<Vec<Style>> = rows
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|ch| match ch {
+          '║' | '╟' | '╢' | '╫' | '╥' | '╨' | '╬' => Style::Separator,
+          ch if is_box_drawing_character!(ch) => Style::Frame,
+          _ => Style::Text,
+        })
+        .collect()
+    })
+    .collect();
+  let Ok(decision_table) = recognize_structure(plane) else {
+    return styles;
+  };
+  let mut headers: Vec<&Cell> = vec![];
+  headers.extend(&decision_table.information_item_name);
+  headers.extend(&decision_table.output_label);
+  headers.extend(decision_table.input_clauses.iter().map(|clause| &clause.input_expression));
+  headers.extend(decision_table.output_clauses.iter().filter_map(|clause| clause.name.as_ref()));
+  headers.extend(decision_table.annotation_clauses.iter().map(|clause| &clause.name));
+  for cell in headers {
+    style_text(rows, &mut styles, cell, Style::Header);
+  }
+  let mut entries: Vec<&Cell> = vec![];
+  entries.extend(decision_table.input_clauses.iter().filter_map(|clause| clause.allowed_values.as_ref()));
+  entries.extend(decision_table.output_clauses.iter().filter_map(|clause| clause.allowed_values.as_ref()));
+  for rule in &decision_table.rules {
+    entries.extend(&rule.input_entries);
+    entries.extend(&rule.output_entries);
+  }
+  // in crosstab tables the hit policy cell contains the output label and rule numbers are not displayed
+  if decision_table.orientation != Orientation::CrossTable {
+    style_text(rows, &mut styles, &decision_table.hit_policy_cell, Style::HitPolicy);
+    for rule in &decision_table.rules {
+      style_text(rows, &mut styles, &rule.number, Style::RuleNumber);
+    }
+  }
+  for cell in entries {
+    if cell.text() == "-" {
+      style_text(rows, &mut styles, cell, Style::Wildcard);
+    } else {
+      let region = cell.region;
+      for r in region.top..=region.bottom {
+        let line = &rows[r][region.left..=region.right];
+        styles[r][region.left..=region.right].copy_from_slice(&tokens(line));
+      }
+    }
+  }
+  styles
+}
+
+/// Applies the style to characters in the cell, from the first to the last non-whitespace character in each line.
+fn style_text(rows: &[Vec<char>], styles: &mut [Vec<Style>], cell: &Cell, style: Style) {
+  let region = cell.region;
+  for r in region.top..=region.bottom {
+    let line = &rows[r][region.left..=region.right];
+    if let (Some(first), Some(last)) = (line.iter().position(|ch| !ch.is_whitespace()), line.iter().rposition(|ch| !ch.is_whitespace())) {
+      styles[r][region.left + first..=region.left + last].fill(style);
+    }
+  }
+}
+
+/// Returns styles of FEEL tokens in a single line of the cell: strings, numbers, comparison operators and range delimiters.
+fn tokens(line: &[char]) -> Vec<Style> {
+  let mut styles = vec![Style::Text; line.len()];
+  let mut index = 0;
+  while index < line.len() {
+    let ch = line[index];
+    let next = line.get(index + 1).copied();
+    let start = index;
+    let style = if ch == '"' {
+      index += 1;
+      while index < line.len() && line[index] != '"' {
+        index += if line[index] == '\\' { 2 } else { 1 };
+      }
+      index += 1;
+      Style::StringLiteral
+    } else if ch.is_alphabetic() || ch == '_' {
+      // names may contain digits, they are not numbers
+      while index < line.len() && (line[index].is_alphanumeric() || line[index] == '_') {
+        index += 1;
+      }
+      Style::Text
+    } else if ch.is_ascii_digit() || (matches!(ch, '-' | '.') && next.is_some_and(|next| next.is_ascii_digit())) {
+      index += 1;
+      while index < line.len() && (line[index].is_ascii_digit() || (line[index] == '.' && line.get(index + 1).is_some_and(|ch| ch.is_ascii_digit()))) {
+        index += 1;
+      }
+      Style::NumberLiteral
+    } else if ch == '.' && next == Some('.') {
+      index += 2;
+      Style::Operator
+    } else if matches!(ch, '<' | '>' | '=' | '!' | '[' | ']' | '(' | ')') {
+      index += 1;
+      Style::Operator
+    } else {
+      index += 1;
+      Style::Text
+    };
+    let end = index.min(line.len());
+    styles[start..end].fill(style);
+  }
+  styles
+}
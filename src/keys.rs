@@ -45,9 +45,30 @@ pub const KN_DELETE: &str = "KEY_DC";
 /// DOWN arrow
 pub const KN_DOWN: &str = "KEY_DOWN";
 
+/// ENTER
+pub const KN_ENTER: &str = "^J";
+
+/// ESCAPE
+pub const KN_ESCAPE: &str = "^[";
+
 /// END
 pub const KN_END: &str = "KEY_END";
 
+/// F2
+pub const KN_F2: &str = "KEY_F(2)";
+
+/// F3
+pub const KN_F3: &str = "KEY_F(3)";
+
+/// F5
+pub const KN_F5: &str = "KEY_F(5)";
+
+/// F6
+pub const KN_F6: &str = "KEY_F(6)";
+
+/// F7
+pub const KN_F7: &str = "KEY_F(7)";
+
 /// HOME
 pub const KN_HOME: &str = "KEY_HOME";
 
@@ -57,6 +78,12 @@ pub const KN_LEFT: &str = "KEY_LEFT";
 /// Resize
 pub const KN_RESIZE: &str = "KEY_RESIZE";
 
+/// PAGE DOWN
+pub const KN_PAGE_DOWN: &str = "KEY_NPAGE";
+
+/// PAGE UP
+pub const KN_PAGE_UP: &str = "KEY_PPAGE";
+
 /// RIGHT arrow
 pub const KN_RIGHT: &str = "KEY_RIGHT";
 
@@ -73,6 +100,15 @@ pub const KN_UP: &str = "KEY_UP";
 /// Shift + END
 pub const KN_SHIFT_END: &str = "KEY_SEND";
 
+/// Shift + F3
+pub const KN_SHIFT_F3: &str = "KEY_F(15)";
+
+/// Shift + F5
+pub const KN_SHIFT_F5: &str = "KEY_F(17)";
+
+/// Shift + F7
+pub const KN_SHIFT_F7: &str = "KEY_F(19)";
+
 /// Shift + HOME
 pub const KN_SHIFT_HOME: &str = "KEY_SHOME";
 
@@ -83,5 +119,77 @@ pub const KN_SHIFT_TAB: &str = "KEY_BTAB";
 // CTRL + key
 //--------------------------------------------------------------------------------------------------
 
+/// Ctrl + B
+pub const KN_CTRL_B: &str = "^B";
+
+/// Ctrl + C
+pub const KN_CTRL_C: &str = "^C";
+
+/// Ctrl + D
+pub const KN_CTRL_D: &str = "^D";
+
+/// Ctrl + E
+pub const KN_CTRL_E: &str = "^E";
+
+/// Ctrl + F
+pub const KN_CTRL_F: &str = "^F";
+
+/// Ctrl + G
+pub const KN_CTRL_G: &str = "^G";
+
+/// Ctrl + K
+pub const KN_CTRL_K: &str = "^K";
+
+/// Ctrl + L
+pub const KN_CTRL_L: &str = "^L";
+
+/// Ctrl + N
+pub const KN_CTRL_N: &str = "^N";
+
+/// Ctrl + O
+pub const KN_CTRL_O: &str = "^O";
+
 /// Ctrl + Q
 pub const KN_CTRL_Q: &str = "^Q";
+
+/// Ctrl + R
+pub const KN_CTRL_R: &str = "^R";
+
+/// Ctrl + S
+pub const KN_CTRL_S: &str = "^S";
+
+/// Ctrl + V
+pub const KN_CTRL_V: &str = "^V";
+
+/// Ctrl + X
+pub const KN_CTRL_X: &str = "^X";
+
+/// Ctrl + DOWN arrow
+pub const KN_CTRL_DOWN: &str = "kDN5";
+
+/// Ctrl + END
+pub const KN_CTRL_END: &str = "kEND5";
+
+/// Ctrl + HOME
+pub const KN_CTRL_HOME: &str = "kHOM5";
+
+/// Ctrl + PAGE DOWN
+pub const KN_CTRL_PAGE_DOWN: &str = "kNXT5";
+
+/// Ctrl + PAGE UP
+pub const KN_CTRL_PAGE_UP: &str = "kPRV5";
+
+/// Ctrl + UP arrow
+pub const KN_CTRL_UP: &str = "kUP5";
+
+/// Alt + DOWN arrow
+pub const KN_ALT_DOWN: &str = "kDN3";
+
+/// Alt + LEFT arrow
+pub const KN_ALT_LEFT: &str = "kLFT3";
+
+/// Alt + RIGHT arrow
+pub const KN_ALT_RIGHT: &str = "kRIT3";
+
+/// Alt + UP arrow
+pub const KN_ALT_UP: &str = "kUP3";
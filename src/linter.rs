@@ -0,0 +1,273 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Checking decision tables for structural problems.
+
+use crate::box_drawing::{arms_of, is_box_drawing_character, junction};
+use crate::model::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize_structure;
+use crate::unicode::text_width;
+
+/// Single problem found in the decision table.
+pub struct Issue {
+  /// Row index of the problem in the plane.
+  pub row: usize,
+  /// Column index of the problem in the plane.
+  pub col: usize,
+  /// Description of the problem.
+  pub message: String,
+  /// Flag indicating if the problem may be fixed mechanically.
+  pub fixable: bool,
+}
+
+impl Issue {
+  /// Creates a new issue.
+  fn new(row: usize, col: usize, message: String, fixable: bool) -> Self {
+    Self { row, col, message, fixable }
+  }
+}
+
+/// Returns the weights of arms of the character placed at the specified position.
+fn arms(rows: &[Vec<char>], row: usize, col: usize) -> [u8; 4] {
+  rows.get(row).and_then(|r| r.get(col)).map_or([0; 4], |ch| arms_of(*ch))
+}
+
+/// Checks rows of the plane for structural problems.
+pub fn lint(plane: &Plane) -> Vec<Issue> {
+  let rows = &plane.chars;
+  let mut issues = check_junctions(rows);
+  match recognize_structure(plane) {
+    Ok(decision_table) => {
+      issues.append(&mut check_rows(rows, &decision_table));
+      issues.append(&mut check_cells(&decision_table));
+    }
+    Err(reason) => issues.push(Issue::new(0, 0, reason.to_string(), false)),
+  }
+  issues.sort_by_key(|issue| (issue.row, issue.col));
+  issues
+}
+
+/// Checks if all lines drawn with box-drawing characters are properly connected.
+fn check_junctions(rows: &[Vec<char>]) -> Vec<Issue> {
+  let mut issues = vec![];
+  let names = ["above", "on the right", "below", "on the left"];
+  for r in 0..rows.len() {
+    for c in 0..rows[r].len() {
+      let ch = rows[r][c];
+      if !is_box_drawing_character!(ch) {
+        continue;
+      }
+      let own = arms(rows, r, c);
+      let neighbours = [
+        (r.checked_sub(1), Some(c)),
+        (Some(r), Some(c + 1)),
+        (Some(r + 1), Some(c)),
+        (Some(r), c.checked_sub(1)),
+      ];
+      for (direction, neighbour) in neighbours.iter().enumerate() {
+        let other = match neighbour {
+          (Some(nr), Some(nc)) => arms(rows, *nr, *nc)[(direction + 2) % 4],
+          _ => 0,
+        };
+        if own[direction] > 0 && other == 0 {
+          issues.push(Issue::new(r, c, format!("'{}' is not connected {}", ch, names[direction]), true));
+        } else if own[direction] > 0 && other > 0 && own[direction] != other && direction < 2 {
+          // different line styles are reported only once, when checking to the right and below
+          issues.push(Issue::new(
+            r,
+            c,
+            format!("'{}' and the line {} have different styles", ch, names[direction]),
+            true,
+          ));
+        }
+      }
+    }
+  }
+  issues
+}
+
+/// Checks if all rows of the decision table have the same length and the same number of cells.
+fn check_rows(rows: &[Vec<char>], decision_table: &DecisionTable) -> Vec<Issue> {
+  let mut issues = vec![];
+  let width = rows[decision_table.header_top].len();
+  for (r, row) in rows.iter().enumerate().take(decision_table.bottom + 1).skip(decision_table.header_top + 1) {
+    if row.len() != width {
+      issues.push(Issue::new(
+        r,
+        row.len().min(width).saturating_sub(1),
+        format!("row has {} characters, expected {}", row.len(), width),
+        false,
+      ));
+    }
+  }
+  let count = |row: &Vec<char>| row.iter().filter(|ch| arms_of(**ch)[0] > 0 && arms_of(**ch)[2] > 0).count();
+  // when rules are placed in columns, the line below rule numbers is crossed only by lines between rules,
+  // in crosstab tables the line below column values is not crossed by the line between row input name and values,
+  // so the first row of the body is the reference
+  let expected = match decision_table.orientation {
+    Orientation::RuleAsRow => count(&rows[decision_table.body_top]),
+    Orientation::RuleAsColumn | Orientation::CrossTable => count(&rows[decision_table.body_top + 1]),
+  };
+  for (r, row) in rows.iter().enumerate().take(decision_table.bottom).skip(decision_table.body_top + 1) {
+    let found = count(row);
+    // only rows with cell content are checked, broken horizontal lines are reported as junction problems
+    if row.first() == Some(&'│') && found != expected {
+      issues.push(Issue::new(
+        r,
+        0,
+        format!("row has {} cells, expected {}", found.saturating_sub(1), expected.saturating_sub(1)),
+        false,
+      ));
+    }
+  }
+  issues
+}
+
+/// Checks the content of cells: hit policy, empty required cells and rule numbers.
+fn check_cells(decision_table: &DecisionTable) -> Vec<Issue> {
+  let mut issues = vec![];
+  let at = |cell: &Cell, message: String, fixable: bool| Issue::new(cell.region.top, cell.region.left, message, fixable);
+  let hit_policy_text = decision_table.hit_policy_cell.text();
+  // the hit policy conflicting in merging is reported like other merge conflicts
+  let conflict = decision_table.hit_policy_cell.lines.iter().any(|line| line == CONFLICT_BEGIN);
+  if decision_table.orientation != Orientation::CrossTable && !conflict && HitPolicy::try_from(hit_policy_text.as_str()).is_err() {
+    issues.push(at(&decision_table.hit_policy_cell, format!("unknown hit policy '{}'", hit_policy_text), false));
+  }
+  for input_clause in &decision_table.input_clauses {
+    if input_clause.input_expression.is_empty() {
+      issues.push(at(&input_clause.input_expression, "empty input expression".to_string(), false));
+    }
+  }
+  if decision_table.output_clauses.len() > 1 {
+    for output_clause in &decision_table.output_clauses {
+      if let Some(name) = output_clause.name.as_ref().filter(|name| name.is_empty()) {
+        issues.push(at(name, "empty output name".to_string(), false));
+      }
+    }
+  }
+  let mut numbers: Vec<String> = vec![];
+  for (rule_index, rule) in decision_table.rules.iter().enumerate() {
+    let number = rule.number.text();
+    let expected = (rule_index + 1).to_string();
+    if numbers.contains(&number) {
+      issues.push(at(&rule.number, format!("duplicated rule number '{}', expected '{}'", number, expected), true));
+    } else if number != expected {
+      issues.push(at(&rule.number, format!("rule number '{}', expected '{}'", number, expected), true));
+    }
+    numbers.push(number);
+    for input_entry in rule.input_entries.iter().filter(|cell| cell.is_empty() && rule.owns(cell)) {
+      issues.push(at(input_entry, format!("empty input entry in rule {}", expected), true));
+    }
+    for output_entry in rule.output_entries.iter().filter(|cell| cell.is_empty() && rule.owns(cell)) {
+      issues.push(at(output_entry, format!("empty output entry in rule {}", expected), false));
+    }
+    for entry in rule
+      .entries()
+      .filter(|cell| rule.owns(cell) && cell.lines.iter().any(|line| line == CONFLICT_BEGIN))
+    {
+      issues.push(at(entry, format!("unresolved merge conflict in rule {}", expected), false));
+    }
+  }
+  let headers = decision_table
+    .information_item_name
+    .iter()
+    .chain([&decision_table.hit_policy_cell])
+    .chain(&decision_table.output_label)
+    .chain(decision_table.input_clauses.iter().filter_map(|clause| clause.allowed_values.as_ref()))
+    .chain(decision_table.output_clauses.iter().filter_map(|clause| clause.allowed_values.as_ref()));
+  for header in headers.filter(|cell| cell.lines.iter().any(|line| line == CONFLICT_BEGIN)) {
+    issues.push(at(header, "unresolved merge conflict".to_string(), false));
+  }
+  issues
+}
+
+/// Fixes mechanical problems: junction characters, rule numbers and empty input entries.
+/// Returns the number of fixed problems.
+pub fn fix(plane: &mut Plane) -> usize {
+  let mut fixed = 0;
+  // replace junctions with characters matching the neighbouring lines
+  let original = plane.chars.clone();
+  let rows = &mut plane.chars;
+  for r in 0..rows.len() {
+    for c in 0..rows[r].len() {
+      if !is_box_drawing_character!(original[r][c]) {
+        continue;
+      }
+      let expected = [
+        r.checked_sub(1).map_or(0, |nr| arms(&original, nr, c)[2]),
+        arms(&original, r, c + 1)[3],
+        arms(&original, r + 1, c)[0],
+        c.checked_sub(1).map_or(0, |nc| arms(&original, r, nc)[1]),
+      ];
+      if let Some(ch) = junction(expected) {
+        if ch != rows[r][c] {
+          rows[r][c] = ch;
+          fixed += 1;
+        }
+      }
+    }
+  }
+  if let Ok(decision_table) = recognize_structure(plane) {
+    for (rule_index, rule) in decision_table.rules.iter().enumerate() {
+      // renumber rules
+      let expected = (rule_index + 1).to_string();
+      if rule.number.text() != expected && write_cell(plane, &rule.number, &format!(" {}", expected)) {
+        fixed += 1;
+      }
+      // fill empty input entries with irrelevance symbol
+      for input_entry in rule.input_entries.iter().filter(|cell| cell.is_empty() && rule.owns(cell)) {
+        let padding = " ".repeat((input_entry.region.width() - 1) / 2);
+        if write_cell(plane, input_entry, &format!("{}-", padding)) {
+          fixed += 1;
+        }
+      }
+    }
+  }
+  fixed
+}
+
+/// Replaces the content of the cell with a single line of text, returns `false` when the text does not fit.
+fn write_cell(plane: &mut Plane, cell: &Cell, text: &str) -> bool {
+  let region = cell.region;
+  let text = if text_width(text) > region.width() { text.trim() } else { text };
+  if text_width(text) > region.width() {
+    return false;
+  }
+  for row in &mut plane.chars[region.top..=region.bottom] {
+    row[region.left..=region.right].fill(' ');
+  }
+  for (offset, ch) in plane.clusters.columns(text).into_iter().enumerate() {
+    plane.chars[region.top][region.left + offset] = ch;
+  }
+  true
+}
@@ -36,13 +36,30 @@ extern crate clap;
 extern crate ncurses;
 
 mod actions;
+mod analyzer;
+mod box_drawing;
+mod differ;
+mod document;
 mod editor;
 mod errors;
+mod evaluator;
+mod exporter;
+mod feel;
+mod formatter;
+mod highlighter;
 mod keys;
+mod linter;
+mod merger;
+mod model;
 mod plane;
+mod recognizer;
+mod sorter;
+mod testing;
 #[cfg(test)]
 mod tests;
+mod unicode;
 mod utils;
+mod views;
 
 use crate::actions::do_action;
 use editor::Editor;
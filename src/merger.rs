@@ -0,0 +1,526 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Three-way merging of decision tables.
+//!
+//! Both modified versions of the document are compared with their common ancestor (base).
+//! Clauses are paired by kind and name and rules are paired by content like in [diff](crate::differ),
+//! so each entry is merged separately: the entry changed on one side only is taken from that side,
+//! the entry changed differently on both sides is a conflict. Conflicting versions are placed
+//! inside the cell between conflict markers, so the merged table can be opened in the editor.
+//!
+//! Documents which can not be merged table by table (decision tables were added or removed,
+//! text outside tables was changed on both sides) and tables which can not be merged entry
+//! by entry (crosstab tables) are merged line by line, like text files merged by git.
+
+use crate::differ::{match_rules, pair_clauses};
+use crate::document::Document;
+use crate::errors::*;
+use crate::formatter::format;
+use crate::model::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Text replacing entries of the rule deleted on one side and modified on the other.
+const DELETED_RULE: &str = "(rule deleted)";
+
+/// Rule of the merged table, with indexes of corresponding rules in all versions.
+struct MergedRule {
+  /// Index of the rule in the base version.
+  base: Option<usize>,
+  /// Index of the rule in our version.
+  ours: Option<usize>,
+  /// Index of the rule in their version.
+  theirs: Option<usize>,
+}
+
+/// Clause of the merged table, with indexes of corresponding clauses in all versions.
+struct MergedClause {
+  /// Kind of the clause.
+  kind: ClauseKind,
+  /// Index of the clause in the base version.
+  base: Option<usize>,
+  /// Index of the clause in our version.
+  ours: Option<usize>,
+  /// Index of the clause in their version.
+  theirs: Option<usize>,
+}
+
+/// Merges two versions of the document modified independently from the common base version.
+/// Returns the merged content and the number of conflicts marked in cells or between lines.
+pub fn merge_documents(base: &str, ours: &str, theirs: &str) -> (String, usize) {
+  if ours == theirs || theirs == base {
+    return (ours.to_string(), 0);
+  }
+  if ours == base {
+    return (theirs.to_string(), 0);
+  }
+  let base_document = Document::new(base);
+  let ours_document = Document::new(ours);
+  let theirs_document = Document::new(theirs);
+  let count = base_document.table_count();
+  // decision tables are paired by their position, so documents with added or removed tables are merged line by line
+  if ours_document.table_count() != count || theirs_document.table_count() != count {
+    return merge_text(base, ours, theirs);
+  }
+  // text outside decision tables may be changed only on one side, otherwise documents are merged line by line
+  let mut merged = if theirs_document.texts() == base_document.texts() {
+    Document::new(ours)
+  } else if ours_document.texts() == base_document.texts() || ours_document.texts() == theirs_document.texts() {
+    Document::new(theirs)
+  } else {
+    return merge_text(base, ours, theirs);
+  };
+  let mut conflicts = 0;
+  let planes = base_document.planes().zip(ours_document.planes()).zip(theirs_document.planes());
+  for (index, ((base_plane, ours_plane), theirs_plane)) in planes.enumerate() {
+    let base_rows = base_document.table_rows(index);
+    let ours_rows = ours_document.table_rows(index);
+    let theirs_rows = theirs_document.table_rows(index);
+    // tables changed only on one side are taken unchanged, with indentation and trailing whitespace
+    if theirs_rows == base_rows {
+      merged.copy_table(index, &ours_document);
+    } else if ours_rows == base_rows || ours_rows == theirs_rows {
+      merged.copy_table(index, &theirs_document);
+    } else {
+      let merged_table = recognize(base_plane).and_then(|base_table| merge_tables(&base_table, &recognize(ours_plane)?, &recognize(theirs_plane)?));
+      let (content, table_conflicts) = match merged_table {
+        Ok((merged_table, table_conflicts)) => (format(&merged_table), table_conflicts),
+        // tables not recognized or not supported in merging are merged line by line
+        Err(_) => {
+          let (lines, table_conflicts) = merge_lines(&base_rows, &ours_rows, &theirs_rows);
+          (lines.join("\n"), table_conflicts)
+        }
+      };
+      conflicts += table_conflicts;
+      merged.replace_table(index, Plane::new(&content));
+    }
+  }
+  (merged.to_string(), conflicts)
+}
+
+/// Merges versions of the text line by line, the final line ending is taken from the merged side.
+/// Returns the merged text and the number of conflicts.
+fn merge_text(base: &str, ours: &str, theirs: &str) -> (String, usize) {
+  let lines = |text: &str| -> Vec<String> { text.strip_suffix('\n').unwrap_or(text).split('\n').map(|line| line.to_string()).collect() };
+  let (lines, conflicts) = merge_lines(&lines(base), &lines(ours), &lines(theirs));
+  let final_line_ending = if ours.ends_with('\n') == base.ends_with('\n') {
+    theirs.ends_with('\n')
+  } else {
+    ours.ends_with('\n')
+  };
+  let mut text = lines.join("\n");
+  if final_line_ending {
+    text.push('\n');
+  }
+  (text, conflicts)
+}
+
+/// Merges versions of lines: lines between lines unchanged on both sides are taken from the side where they were changed,
+/// lines changed differently on both sides are placed between conflict markers. Returns merged lines and the number of conflicts.
+fn merge_lines(base: &[String], ours: &[String], theirs: &[String]) -> (Vec<String>, usize) {
+  let base_in_ours = common_lines(base, ours);
+  let base_in_theirs = common_lines(base, theirs);
+  // conflict markers end with carriage return when lines do
+  let cr = if ours.first().is_some_and(|line| line.ends_with('\r')) { "\r" } else { "" };
+  let mut merged = vec![];
+  let mut conflicts = 0;
+  let (mut b, mut o, mut t) = (0, 0, 0);
+  loop {
+    // the next base line present unchanged on both sides, or the end of all versions
+    let stable = (b..base.len()).find_map(|index| Some((index, base_in_ours[index]?, base_in_theirs[index]?)));
+    let (next_b, next_o, next_t) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+    let (base_chunk, ours_chunk, theirs_chunk) = (&base[b..next_b], &ours[o..next_o], &theirs[t..next_t]);
+    if ours_chunk == theirs_chunk || theirs_chunk == base_chunk {
+      merged.extend_from_slice(ours_chunk);
+    } else if ours_chunk == base_chunk {
+      merged.extend_from_slice(theirs_chunk);
+    } else {
+      merged.push(format!("{}{}", CONFLICT_BEGIN, cr));
+      merged.extend_from_slice(ours_chunk);
+      merged.push(format!("{}{}", CONFLICT_SEPARATOR, cr));
+      merged.extend_from_slice(theirs_chunk);
+      merged.push(format!("{}{}", CONFLICT_END, cr));
+      conflicts += 1;
+    }
+    if stable.is_none() {
+      break;
+    }
+    merged.push(base[next_b].clone());
+    (b, o, t) = (next_b + 1, next_o + 1, next_t + 1);
+  }
+  (merged, conflicts)
+}
+
+/// Pairs lines of the first version with equal lines of the second version using the longest common subsequence,
+/// returns the index of the paired line for each line of the first version.
+fn common_lines(first: &[String], second: &[String]) -> Vec<Option<usize>> {
+  // lengths[i][j] is the length of the longest common subsequence of first[i..] and second[j..]
+  let mut lengths = vec![vec![0_usize; second.len() + 1]; first.len() + 1];
+  for i in (0..first.len()).rev() {
+    for j in (0..second.len()).rev() {
+      lengths[i][j] = if first[i] == second[j] {
+        lengths[i + 1][j + 1] + 1
+      } else {
+        lengths[i + 1][j].max(lengths[i][j + 1])
+      };
+    }
+  }
+  let mut pairs = vec![None; first.len()];
+  let (mut i, mut j) = (0, 0);
+  while i < first.len() && j < second.len() {
+    if first[i] == second[j] {
+      pairs[i] = Some(j);
+      i += 1;
+      j += 1;
+    } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+      i += 1;
+    } else {
+      j += 1;
+    }
+  }
+  pairs
+}
+
+/// Merges two versions of the decision table modified independently from the common base version.
+/// Returns the merged decision table and the number of conflicts marked in cells.
+pub fn merge_tables(base: &DecisionTable, ours: &DecisionTable, theirs: &DecisionTable) -> Result<(DecisionTable, usize)> {
+  if [base, ours, theirs].iter().any(|dt| dt.orientation == Orientation::CrossTable) {
+    return Err(err_merge_tables("crosstab tables can not be merged"));
+  }
+  let mut conflicts = 0;
+  // the hit policy changed differently on both sides is a conflict marked in the hit policy cell
+  let (hit_policy, hit_policy_lines) = if ours.hit_policy == base.hit_policy {
+    (theirs.hit_policy, vec![theirs.hit_policy.to_string()])
+  } else if theirs.hit_policy == base.hit_policy || theirs.hit_policy == ours.hit_policy {
+    (ours.hit_policy, vec![ours.hit_policy.to_string()])
+  } else {
+    conflicts += 1;
+    (
+      ours.hit_policy,
+      conflict_lines(&[ours.hit_policy.to_string()], &[theirs.hit_policy.to_string()]),
+    )
+  };
+  // the region of the hit policy cell is used for all cells created during merging, regions are not used in formatting
+  let region = ours.hit_policy_cell.region;
+  let mut merge = |base_cell: Option<&Cell>, ours_cell: Option<&Cell>, theirs_cell: Option<&Cell>| -> Option<Cell> {
+    let (lines, conflict) = merge_cell(base_cell, ours_cell, theirs_cell);
+    conflicts += usize::from(conflict);
+    Some(Cell { lines, region }).filter(|cell| !cell.is_empty())
+  };
+  let information_item_name = merge(
+    base.information_item_name.as_ref(),
+    ours.information_item_name.as_ref(),
+    theirs.information_item_name.as_ref(),
+  );
+  let output_label = merge(base.output_label.as_ref(), ours.output_label.as_ref(), theirs.output_label.as_ref());
+  let (base_clauses, ours_clauses, theirs_clauses) = (base.clauses(), ours.clauses(), theirs.clauses());
+  let merged_clauses = merge_clauses(&base_clauses, &ours_clauses, &theirs_clauses);
+  let mut input_clauses = vec![];
+  let mut output_clauses = vec![];
+  let mut annotation_clauses = vec![];
+  for clause in &merged_clauses {
+    let base_clause = clause.base.map(|index| &base_clauses[index]);
+    let ours_clause = clause.ours.map(|index| &ours_clauses[index]);
+    let theirs_clause = clause.theirs.map(|index| &theirs_clauses[index]);
+    // the name of the clause is the same in all versions, the clause is taken from the side where it is present
+    let (source, source_clause) = match ours_clause {
+      Some(ours_clause) => (ours, ours_clause),
+      None => (theirs, theirs_clause.unwrap()),
+    };
+    let allowed_values = merge(
+      base_clause.and_then(|clause| clause.allowed_values),
+      ours_clause.and_then(|clause| clause.allowed_values),
+      theirs_clause.and_then(|clause| clause.allowed_values),
+    );
+    match clause.kind {
+      ClauseKind::Input => input_clauses.push(InputClause {
+        input_expression: source.input_clauses[source_clause.index].input_expression.clone(),
+        allowed_values,
+      }),
+      ClauseKind::Output => output_clauses.push(OutputClause {
+        name: source.output_clauses[source_clause.index].name.clone(),
+        allowed_values,
+      }),
+      ClauseKind::Annotation => annotation_clauses.push(AnnotationClause {
+        name: source.annotation_clauses[source_clause.index].name.clone(),
+      }),
+    }
+  }
+  let mut rules = vec![];
+  for merged_rule in merge_rules(base, ours, theirs, &base_clauses, &ours_clauses, &theirs_clauses) {
+    let base_rule = merged_rule.base.map(|index| &base.rules[index]);
+    let ours_rule = merged_rule.ours.map(|index| &ours.rules[index]);
+    let theirs_rule = merged_rule.theirs.map(|index| &theirs.rules[index]);
+    let mut rule = DecisionRule {
+      number: Cell {
+        lines: vec![(rules.len() + 1).to_string()],
+        region,
+      },
+      input_entries: vec![],
+      output_entries: vec![],
+      annotation_entries: vec![],
+      region,
+    };
+    // the rule deleted on one side and modified on the other is a single conflict
+    let deleted = base_rule.is_some() && (ours_rule.is_none() || theirs_rule.is_none());
+    conflicts += usize::from(deleted);
+    for clause in &merged_clauses {
+      let base_cell = cell_of(base_rule, clause.base, &base_clauses);
+      let ours_cell = cell_of(ours_rule, clause.ours, &ours_clauses);
+      let theirs_cell = cell_of(theirs_rule, clause.theirs, &theirs_clauses);
+      let lines = if deleted {
+        let changed = if ours_rule.is_none() { theirs_cell } else { ours_cell };
+        if text_of(changed) == text_of(base_cell) {
+          lines_of(changed)
+        } else if ours_rule.is_none() {
+          conflict_lines(&[DELETED_RULE.to_string()], &lines_of(changed))
+        } else {
+          conflict_lines(&lines_of(changed), &[DELETED_RULE.to_string()])
+        }
+      } else {
+        let (lines, conflict) = merge_cell(base_cell, ours_cell, theirs_cell);
+        conflicts += usize::from(conflict);
+        lines
+      };
+      let cell = Cell { lines, region };
+      match clause.kind {
+        ClauseKind::Input => rule.input_entries.push(cell),
+        ClauseKind::Output => rule.output_entries.push(cell),
+        ClauseKind::Annotation => rule.annotation_entries.push(cell),
+      }
+    }
+    rules.push(rule);
+  }
+  let merged = DecisionTable {
+    orientation: ours.orientation,
+    information_item_name,
+    hit_policy,
+    hit_policy_cell: Cell {
+      lines: hit_policy_lines,
+      region,
+    },
+    output_label,
+    input_clauses,
+    output_clauses,
+    annotation_clauses,
+    rules,
+    header_top: ours.header_top,
+    body_top: ours.body_top,
+    bottom: ours.bottom,
+  };
+  Ok((merged, conflicts))
+}
+
+/// Merges lists of clauses: clauses removed on any side are removed, clauses added on any side are added,
+/// clauses added on their side are placed after the last clause of the same kind.
+fn merge_clauses(base: &[Clause], ours: &[Clause], theirs: &[Clause]) -> Vec<MergedClause> {
+  let ours_in_base = pair_clauses(ours, base);
+  let ours_in_theirs = pair_clauses(ours, theirs);
+  let theirs_in_base = pair_clauses(theirs, base);
+  let mut merged: Vec<MergedClause> = vec![];
+  for (index, clause) in ours.iter().enumerate() {
+    if ours_in_base[index].is_some() && ours_in_theirs[index].is_none() {
+      // removed on their side
+      continue;
+    }
+    merged.push(MergedClause {
+      kind: clause.kind,
+      base: ours_in_base[index],
+      ours: Some(index),
+      theirs: ours_in_theirs[index],
+    });
+  }
+  for (index, clause) in theirs.iter().enumerate() {
+    if ours_in_theirs.contains(&Some(index)) || theirs_in_base[index].is_some() {
+      // present on both sides or removed on our side
+      continue;
+    }
+    let position = merged.iter().rposition(|merged_clause| merged_clause.kind == clause.kind).map_or_else(
+      || {
+        merged
+          .iter()
+          .filter(|merged_clause| kind_rank(merged_clause.kind) < kind_rank(clause.kind))
+          .count()
+      },
+      |position| position + 1,
+    );
+    merged.insert(
+      position,
+      MergedClause {
+        kind: clause.kind,
+        base: None,
+        ours: None,
+        theirs: Some(index),
+      },
+    );
+  }
+  merged
+}
+
+/// Returns the position of clauses of the specified kind in the table.
+fn kind_rank(kind: ClauseKind) -> usize {
+  match kind {
+    ClauseKind::Input => 0,
+    ClauseKind::Output => 1,
+    ClauseKind::Annotation => 2,
+  }
+}
+
+/// Merges lists of rules: rules are placed in our order, rules added on their side are placed
+/// after the rule preceding them on their side. Rules deleted on one side and not modified
+/// on the other are deleted, rules added on both sides with equal entries are added once.
+fn merge_rules(
+  base: &DecisionTable,
+  ours: &DecisionTable,
+  theirs: &DecisionTable,
+  base_clauses: &[Clause],
+  ours_clauses: &[Clause],
+  theirs_clauses: &[Clause],
+) -> Vec<MergedRule> {
+  let common = |first: &[Clause], second: &[Clause]| -> Vec<(usize, usize)> {
+    pair_clauses(first, second)
+      .iter()
+      .enumerate()
+      .filter_map(|(index, other)| other.map(|other| (index, other)))
+      .collect()
+  };
+  let pairs = |first_clauses: &[Clause], second_clauses: &[Clause], first: &DecisionTable, second: &DecisionTable| {
+    let indexes = common(first_clauses, second_clauses);
+    let common: Vec<(&Clause, &Clause)> = indexes.iter().map(|(a, b)| (&first_clauses[*a], &second_clauses[*b])).collect();
+    match_rules(first, second, &common)
+  };
+  let base_in_ours = pairs(base_clauses, ours_clauses, base, ours);
+  let base_in_theirs = pairs(base_clauses, theirs_clauses, base, theirs);
+  let ours_in_theirs = pairs(ours_clauses, theirs_clauses, ours, theirs);
+  let unchanged = |base_index: usize, dt: &DecisionTable, clauses: &[Clause], index: usize| -> bool {
+    let common = common(base_clauses, clauses);
+    common.len() == base_clauses.len()
+      && common.len() == clauses.len()
+      && common
+        .iter()
+        .all(|(a, b)| text_of(Some(base.rules[base_index].entry(&base_clauses[*a]))) == text_of(Some(dt.rules[index].entry(&clauses[*b]))))
+  };
+  let mut merged: Vec<MergedRule> = vec![];
+  for (ours_index, in_theirs) in ours_in_theirs.iter().enumerate() {
+    let base_index = base_in_ours.iter().position(|index| *index == Some(ours_index));
+    match base_index {
+      Some(base_index) => {
+        let theirs_index = base_in_theirs[base_index];
+        if theirs_index.is_none() && unchanged(base_index, ours, ours_clauses, ours_index) {
+          // deleted on their side
+          continue;
+        }
+        merged.push(MergedRule {
+          base: Some(base_index),
+          ours: Some(ours_index),
+          theirs: theirs_index,
+        });
+      }
+      None => {
+        // added on our side, the same rule may be added on their side
+        let theirs_index = in_theirs.filter(|theirs_index| !base_in_theirs.contains(&Some(*theirs_index)));
+        merged.push(MergedRule {
+          base: None,
+          ours: Some(ours_index),
+          theirs: theirs_index,
+        });
+      }
+    }
+  }
+  for theirs_index in 0..theirs.rules.len() {
+    if merged.iter().any(|rule| rule.theirs == Some(theirs_index)) {
+      continue;
+    }
+    let base_index = base_in_theirs.iter().position(|index| *index == Some(theirs_index));
+    if let Some(base_index) = base_index {
+      if unchanged(base_index, theirs, theirs_clauses, theirs_index) {
+        // deleted on our side
+        continue;
+      }
+    }
+    let position = (0..theirs_index)
+      .rev()
+      .find_map(|preceding| merged.iter().position(|rule| rule.theirs == Some(preceding)))
+      .map_or(0, |position| position + 1);
+    merged.insert(
+      position,
+      MergedRule {
+        base: base_index,
+        ours: None,
+        theirs: Some(theirs_index),
+      },
+    );
+  }
+  merged
+}
+
+/// Returns the entry of the rule in the clause with the specified index, when both are present.
+fn cell_of<'a>(rule: Option<&'a DecisionRule>, index: Option<usize>, clauses: &[Clause]) -> Option<&'a Cell> {
+  match (rule, index) {
+    (Some(rule), Some(index)) => Some(rule.entry(&clauses[index])),
+    _ => None,
+  }
+}
+
+/// Merges versions of the cell, a missing cell is treated like an empty one.
+/// Returns lines of the merged cell and the flag indicating the conflict.
+fn merge_cell(base: Option<&Cell>, ours: Option<&Cell>, theirs: Option<&Cell>) -> (Vec<String>, bool) {
+  let (base_text, ours_text, theirs_text) = (text_of(base), text_of(ours), text_of(theirs));
+  if ours_text == theirs_text || theirs_text == base_text {
+    (lines_of(ours), false)
+  } else if ours_text == base_text {
+    (lines_of(theirs), false)
+  } else {
+    (conflict_lines(&lines_of(ours), &lines_of(theirs)), true)
+  }
+}
+
+/// Returns normalized text of the cell, empty when the cell is missing.
+fn text_of(cell: Option<&Cell>) -> String {
+  cell.map_or(String::new(), |cell| normalized(&cell.text()))
+}
+
+/// Returns lines of the cell without trailing empty lines, no lines when the cell is missing.
+fn lines_of(cell: Option<&Cell>) -> Vec<String> {
+  cell.map_or(vec![], |cell| cell.lines.clone())
+}
+
+/// Returns lines of the cell containing both conflicting versions between conflict markers.
+fn conflict_lines(ours: &[String], theirs: &[String]) -> Vec<String> {
+  let mut lines = vec![CONFLICT_BEGIN.to_string()];
+  lines.extend(ours.iter().cloned());
+  lines.push(CONFLICT_SEPARATOR.to_string());
+  lines.extend(theirs.iter().cloned());
+  lines.push(CONFLICT_END.to_string());
+  lines
+}
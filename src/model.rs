@@ -0,0 +1,458 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Model of the decision table recognized in the plane.
+
+use crate::errors::*;
+use std::fmt;
+use std::fmt::Display;
+
+/// Marker starting the conflicting version of the cell from our side.
+pub const CONFLICT_BEGIN: &str = "<<<<<<< ours";
+
+/// Marker separating conflicting versions of the cell.
+pub const CONFLICT_SEPARATOR: &str = "=======";
+
+/// Marker ending the conflicting version of the cell from their side.
+pub const CONFLICT_END: &str = ">>>>>>> theirs";
+
+/// Rectangular region of the plane occupied by a single cell.
+///
+/// All coordinates are inclusive and point to characters inside the cell,
+/// box-drawing characters surrounding the cell are not included.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Region {
+  /// Index of the first row of the cell.
+  pub top: usize,
+  /// Index of the first column of the cell.
+  pub left: usize,
+  /// Index of the last row of the cell.
+  pub bottom: usize,
+  /// Index of the last column of the cell.
+  pub right: usize,
+}
+
+impl Region {
+  /// Returns the width of the region.
+  pub fn width(&self) -> usize {
+    self.right - self.left + 1
+  }
+}
+
+/// Single cell of the decision table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+  /// Trimmed lines of text in the cell, trailing empty lines are removed.
+  pub lines: Vec<String>,
+  /// Region of the plane occupied by the cell.
+  pub region: Region,
+}
+
+impl Cell {
+  /// Returns the text of the cell, lines are joined with single space.
+  pub fn text(&self) -> String {
+    self.lines.iter().filter(|line| !line.is_empty()).cloned().collect::<Vec<String>>().join(" ")
+  }
+
+  /// Returns `true` when the cell contains no text.
+  pub fn is_empty(&self) -> bool {
+    self.lines.iter().all(|line| line.is_empty())
+  }
+}
+
+/// Built-in aggregators used in decision tables with collect hit policy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuiltinAggregator {
+  /// The result is the count of distinct output values.
+  Count,
+  /// The result is the sum of all output values.
+  Sum,
+  /// The result is the smallest output value.
+  Min,
+  /// The result is the largest output value.
+  Max,
+}
+
+/// Hit policies defined in DMN.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HitPolicy {
+  /// `U` - only one rule may match.
+  Unique,
+  /// `A` - multiple rules may match, but they must have equal outputs.
+  Any,
+  /// `P` - the rule with the highest output priority is selected.
+  Priority,
+  /// `F` - the first matching rule is selected.
+  First,
+  /// `C`, `C+`, `C#`, `C<`, `C>` - outputs of all matching rules are collected.
+  Collect(Option<BuiltinAggregator>),
+  /// `O` - outputs of all matching rules are returned in decreasing priority order.
+  OutputOrder,
+  /// `R` - outputs of all matching rules are returned in rule order.
+  RuleOrder,
+}
+
+impl TryFrom<&str> for HitPolicy {
+  type Error = AttoError;
+  /// Converts a text placed in the hit policy cell into [HitPolicy].
+  fn try_from(text: &str) -> Result<Self, Self::Error> {
+    match text.split_whitespace().collect::<String>().as_str() {
+      "U" => Ok(Self::Unique),
+      "A" => Ok(Self::Any),
+      "P" => Ok(Self::Priority),
+      "F" => Ok(Self::First),
+      "C" => Ok(Self::Collect(None)),
+      "C#" => Ok(Self::Collect(Some(BuiltinAggregator::Count))),
+      "C+" => Ok(Self::Collect(Some(BuiltinAggregator::Sum))),
+      "C<" => Ok(Self::Collect(Some(BuiltinAggregator::Min))),
+      "C>" => Ok(Self::Collect(Some(BuiltinAggregator::Max))),
+      "O" => Ok(Self::OutputOrder),
+      "R" => Ok(Self::RuleOrder),
+      _ => Err(err_invalid_hit_policy(text)),
+    }
+  }
+}
+
+impl Display for HitPolicy {
+  /// Converts a [HitPolicy] into its textual notation.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let text = match self {
+      Self::Unique => "U",
+      Self::Any => "A",
+      Self::Priority => "P",
+      Self::First => "F",
+      Self::Collect(None) => "C",
+      Self::Collect(Some(BuiltinAggregator::Count)) => "C#",
+      Self::Collect(Some(BuiltinAggregator::Sum)) => "C+",
+      Self::Collect(Some(BuiltinAggregator::Min)) => "C<",
+      Self::Collect(Some(BuiltinAggregator::Max)) => "C>",
+      Self::OutputOrder => "O",
+      Self::RuleOrder => "R",
+    };
+    write!(f, "{}", text)
+  }
+}
+
+/// Input clause of the decision table.
+#[derive(Debug, Clone)]
+pub struct InputClause {
+  /// Input expression.
+  pub input_expression: Cell,
+  /// Optional list of allowed input values.
+  pub allowed_values: Option<Cell>,
+}
+
+/// Output clause of the decision table.
+#[derive(Debug, Clone)]
+pub struct OutputClause {
+  /// Name of the output component, present only when the table has more than one output.
+  pub name: Option<Cell>,
+  /// Optional list of allowed output values.
+  pub allowed_values: Option<Cell>,
+}
+
+/// Annotation clause of the decision table.
+#[derive(Debug, Clone)]
+pub struct AnnotationClause {
+  /// Name of the annotation.
+  pub name: Cell,
+}
+
+/// Kind of the clause.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClauseKind {
+  Input,
+  Output,
+  Annotation,
+}
+
+impl Display for ClauseKind {
+  /// Converts a [ClauseKind] into its name used in reports.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let text = match self {
+      Self::Input => "input",
+      Self::Output => "output",
+      Self::Annotation => "annotation",
+    };
+    write!(f, "{}", text)
+  }
+}
+
+/// Clause of any kind, see [DecisionTable::clauses].
+pub struct Clause<'a> {
+  /// Kind of the clause.
+  pub kind: ClauseKind,
+  /// Index of the clause among clauses of the same kind.
+  pub index: usize,
+  /// Name of the clause: the input expression, the name of the output or the name of the annotation.
+  pub name: String,
+  /// Cell containing the name of the clause, for the only output it may be the output label.
+  pub name_cell: Option<&'a Cell>,
+  /// Cell containing allowed values.
+  pub allowed_values: Option<&'a Cell>,
+}
+
+/// Single decision rule.
+#[derive(Debug, Clone)]
+pub struct DecisionRule {
+  /// Cell containing the rule number.
+  pub number: Cell,
+  /// Input entries, one per input clause.
+  pub input_entries: Vec<Cell>,
+  /// Output entries, one per output clause.
+  pub output_entries: Vec<Cell>,
+  /// Annotation entries, one per annotation clause.
+  pub annotation_entries: Vec<Cell>,
+  /// Region occupied by the rule, including box-drawing characters between its cells.
+  pub region: Region,
+}
+
+impl DecisionRule {
+  /// Returns `true` when the cell starts within this rule (is not merged with cells of preceding rules).
+  pub fn owns(&self, cell: &Cell) -> bool {
+    cell.region.top >= self.region.top && cell.region.left >= self.region.left
+  }
+
+  /// Returns all entries of the rule in the order of clauses: input, output and annotation entries.
+  pub fn entries(&self) -> impl Iterator<Item = &Cell> {
+    self.input_entries.iter().chain(&self.output_entries).chain(&self.annotation_entries)
+  }
+
+  /// Returns the entry of the rule in the specified clause.
+  pub fn entry(&self, clause: &Clause) -> &Cell {
+    match clause.kind {
+      ClauseKind::Input => &self.input_entries[clause.index],
+      ClauseKind::Output => &self.output_entries[clause.index],
+      ClauseKind::Annotation => &self.annotation_entries[clause.index],
+    }
+  }
+}
+
+/// Orientation of the decision table, named like `preferredOrientation` in DMN.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Orientation {
+  /// Rules are placed in rows, clauses in columns.
+  RuleAsRow,
+  /// Rules are placed in columns, clauses in rows.
+  RuleAsColumn,
+  /// Inputs are placed in rows and columns, output entries in the grid between them.
+  CrossTable,
+}
+
+/// Decision table recognized in the plane.
+#[derive(Debug, Clone)]
+pub struct DecisionTable {
+  /// Orientation of rules.
+  pub orientation: Orientation,
+  /// Optional name of the information item (name of the decision).
+  pub information_item_name: Option<Cell>,
+  /// Hit policy.
+  pub hit_policy: HitPolicy,
+  /// Cell containing the hit policy, in crosstab tables the cell containing the output label.
+  pub hit_policy_cell: Cell,
+  /// Label of the output, spans all output columns.
+  pub output_label: Option<Cell>,
+  /// Input clauses.
+  pub input_clauses: Vec<InputClause>,
+  /// Output clauses.
+  pub output_clauses: Vec<OutputClause>,
+  /// Annotation clauses.
+  pub annotation_clauses: Vec<AnnotationClause>,
+  /// Decision rules.
+  pub rules: Vec<DecisionRule>,
+  /// Index of the row containing the top border of the table body (the line with `╥` characters).
+  pub header_top: usize,
+  /// Index of the row separating the header from rules (the line starting with `╞`),
+  /// when rules are placed in columns, the line below rule numbers,
+  /// in crosstab tables the line below the values of the column input.
+  pub body_top: usize,
+  /// Index of the row containing the bottom border of the table.
+  pub bottom: usize,
+}
+
+impl DecisionTable {
+  /// Converts the decision table with two inputs and a single output into a crosstab table.
+  /// Values of the first input are placed in rows, values of the second input in columns,
+  /// each combination of input values must be covered by exactly one rule. Allowed values and annotations are not converted.
+  pub fn to_crosstab(&self) -> Result<DecisionTable> {
+    if self.input_clauses.len() != 2 || self.output_clauses.len() != 1 {
+      return Err(err_crosstab("only decision tables with two inputs and a single output can be converted"));
+    }
+    if self.hit_policy != HitPolicy::Unique {
+      return Err(err_crosstab("only decision tables with unique hit policy can be converted"));
+    }
+    // distinct values of inputs in the order of rules, placed in separate regions
+    let mut row_values: Vec<Cell> = vec![];
+    let mut column_values: Vec<Cell> = vec![];
+    for rule in &self.rules {
+      let (row_value, column_value) = (&rule.input_entries[0], &rule.input_entries[1]);
+      if !row_values.iter().any(|cell| cell.text() == row_value.text()) {
+        let top = row_values.len();
+        let region = Region {
+          top,
+          left: 0,
+          bottom: top,
+          right: 0,
+        };
+        row_values.push(Cell {
+          lines: row_value.lines.clone(),
+          region,
+        });
+      }
+      if !column_values.iter().any(|cell| cell.text() == column_value.text()) {
+        let left = column_values.len();
+        let region = Region {
+          top: 0,
+          left,
+          bottom: 0,
+          right: left,
+        };
+        column_values.push(Cell {
+          lines: column_value.lines.clone(),
+          region,
+        });
+      }
+    }
+    let mut rules = vec![];
+    for row_value in &row_values {
+      for column_value in &column_values {
+        let mut matching = self
+          .rules
+          .iter()
+          .filter(|rule| rule.input_entries[0].text() == row_value.text() && rule.input_entries[1].text() == column_value.text());
+        let (Some(rule), None) = (matching.next(), matching.next()) else {
+          return Err(err_crosstab(&format!(
+            "combination of '{}' and '{}' must be covered by exactly one rule",
+            row_value.text(),
+            column_value.text()
+          )));
+        };
+        let output_entry = rule.output_entries[0].clone();
+        rules.push(DecisionRule {
+          number: Cell {
+            lines: vec![(rules.len() + 1).to_string()],
+            region: output_entry.region,
+          },
+          input_entries: vec![row_value.clone(), column_value.clone()],
+          region: output_entry.region,
+          output_entries: vec![output_entry],
+          annotation_entries: vec![],
+        });
+      }
+    }
+    let label = self.output_label.clone().unwrap_or_else(|| Cell {
+      lines: vec![],
+      region: self.hit_policy_cell.region,
+    });
+    Ok(DecisionTable {
+      orientation: Orientation::CrossTable,
+      information_item_name: self.information_item_name.clone(),
+      hit_policy: HitPolicy::Unique,
+      hit_policy_cell: label.clone(),
+      output_label: Some(label),
+      input_clauses: self
+        .input_clauses
+        .iter()
+        .map(|clause| InputClause {
+          input_expression: clause.input_expression.clone(),
+          allowed_values: None,
+        })
+        .collect(),
+      output_clauses: vec![OutputClause {
+        name: None,
+        allowed_values: None,
+      }],
+      annotation_clauses: vec![],
+      rules,
+      header_top: self.header_top,
+      body_top: self.body_top,
+      bottom: self.bottom,
+    })
+  }
+
+  /// Returns names of all output components, when the name is not present the output label is used.
+  pub fn output_names(&self) -> Vec<String> {
+    self
+      .output_clauses
+      .iter()
+      .enumerate()
+      .map(|(index, output_clause)| {
+        if let Some(name) = &output_clause.name {
+          name.text()
+        } else if let Some(label) = self.output_label.as_ref().filter(|_| self.output_clauses.len() == 1) {
+          label.text()
+        } else {
+          format!("output {}", index + 1)
+        }
+      })
+      .collect()
+  }
+
+  /// Returns all clauses in the order of entries of rules: input, output and annotation clauses.
+  pub fn clauses(&self) -> Vec<Clause<'_>> {
+    let inputs = self.input_clauses.iter().enumerate().map(|(index, input_clause)| Clause {
+      kind: ClauseKind::Input,
+      index,
+      name: input_clause.input_expression.text(),
+      name_cell: Some(&input_clause.input_expression),
+      allowed_values: input_clause.allowed_values.as_ref(),
+    });
+    let outputs = self
+      .output_clauses
+      .iter()
+      .zip(self.output_names())
+      .enumerate()
+      .map(|(index, (output_clause, name))| Clause {
+        kind: ClauseKind::Output,
+        index,
+        name,
+        name_cell: output_clause
+          .name
+          .as_ref()
+          .or(self.output_label.as_ref().filter(|_| self.output_clauses.len() == 1)),
+        allowed_values: output_clause.allowed_values.as_ref(),
+      });
+    let annotations = self.annotation_clauses.iter().enumerate().map(|(index, annotation_clause)| Clause {
+      kind: ClauseKind::Annotation,
+      index,
+      name: annotation_clause.name.text(),
+      name_cell: Some(&annotation_clause.name),
+      allowed_values: None,
+    });
+    inputs.chain(outputs).chain(annotations).collect()
+  }
+}
+
+/// Returns the text with whitespace collapsed to single spaces, so reformatting does not change it.
+pub fn normalized(text: &str) -> String {
+  text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
@@ -32,21 +32,20 @@
 
 //! Implementation of an editing plane.
 
+use crate::box_drawing::{arms_of, is_box_drawing_character, junction};
+use crate::errors::*;
+use crate::feel::split_list;
+use crate::model::{normalized, Clause, DecisionRule, DecisionTable, HitPolicy, Orientation, Region};
+use crate::recognizer::{cell, recognize_structure, Regions};
+use crate::sorter::SortKey;
+use crate::unicode::{char_width, column_char_count, compose, text_width, Clusters, CH_WIDE};
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Display;
+use std::ops::Range;
 
 const CH_WS: char = ' ';
 
-/// Checks if the specified character is a box-drawing character.
-macro_rules! is_box_drawing_character {
-  ($ch:expr) => {
-    match $ch {
-      '┌' | '┐' | '└' | '┘' | '─' | '│' | '├' | '┤' | '┴' | '┬' | '┼' | '╪' | '╫' | '╬' | '╞' | '╡' | '╥' | '╨' | '═' | '║' | '╟' | '╢' => true,
-      _ => false,
-    }
-  };
-}
-
 /// Checks if the specified character is a vertical line seen from the left side.
 macro_rules! is_vert_line_left {
   ($ch:expr) => {
@@ -71,7 +70,9 @@ macro_rules! is_vert_line_right {
 macro_rules! is_vert_line_crossing {
   ($ch:expr) => {
     match $ch {
-      '│' | '┼' | '┬' | '┴' | '╪' | '┐' | '┘' | '├' | '║' | '╟' | '╬' | '╥' | '╨' | '╫' | '╢' | '┤' | '╡' => true,
+      '│' | '┼' | '┬' | '┴' | '╪' | '┐' | '┘' | '├' | '║' | '╟' | '╬' | '╥' | '╨' | '╫' | '╢' | '┤' | '╡' | '╤' | '╧' => {
+        true
+      }
       _ => false,
     }
   };
@@ -81,7 +82,7 @@ macro_rules! is_vert_line_crossing {
 macro_rules! is_horz_line_top {
   ($ch:expr) => {
     match $ch {
-      '─' | '┬' | '═' | '╥' => true,
+      '─' | '┬' | '═' | '╥' | '╤' => true,
       _ => false,
     }
   };
@@ -102,16 +103,70 @@ enum Op {
   Delete,
 }
 
+/// Modes of selecting the content of the plane.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SelectionMode {
+  /// Characters within a single cell.
+  Characters,
+  /// Rectangular range of cells.
+  Cells,
+  /// Entire decision rules.
+  Rules,
+}
+
+/// Scope of searching the content of the plane.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SearchScope {
+  /// All cells of the decision table.
+  Table,
+  /// Cells of the clause under the cursor: the header and entries of all rules.
+  Clause,
+}
+
+/// Content copied from the plane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Register {
+  /// Lines of characters copied from a single cell.
+  Text(Vec<String>),
+  /// Rows of cells, each cell contains trimmed lines of text.
+  Cells(Vec<Vec<Vec<String>>>),
+  /// Decision rules, each rule contains its entries, without the rule number.
+  Rules(Vec<Vec<Vec<String>>>),
+}
+
+impl Display for Register {
+  /// Converts the register into plain text, cells are separated with tabulators.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Text(lines) => write!(f, "{}", lines.join("\n")),
+      Self::Cells(rows) | Self::Rules(rows) => {
+        let lines: Vec<String> = rows
+          .iter()
+          .map(|cells| cells.iter().map(|lines| lines.join(" ")).collect::<Vec<String>>().join("\t"))
+          .collect();
+        write!(f, "{}", lines.join("\n"))
+      }
+    }
+  }
+}
+
 /// Plane containing rows of characters.
+#[derive(Clone)]
 pub struct Plane {
   /// Rows in plane.
   pub chars: Vec<Vec<char>>,
+  /// Text of clusters placed in rows.
+  pub clusters: Clusters,
   /// Current vertical cursor position (row index).
   row: usize,
   /// Current horizontal cursor position (column index).
   col: usize,
   /// Information item height (0 when not present).
   iih: usize,
+  /// Line index and column offset in the loaded content, for each row.
+  origins: Vec<(usize, usize)>,
+  /// Selection mode and the position where the selection started.
+  selection: Option<(SelectionMode, usize, usize)>,
 }
 
 impl Display for Plane {
@@ -123,11 +178,7 @@ impl Display for Plane {
       self
         .chars
         .iter()
-        .fold("".to_string(), |plane, row| format!(
-          "{}\n{}",
-          plane,
-          row.iter().fold("".to_string(), |line, ch| format!("{}{}", line, ch))
-        ))
+        .fold("".to_string(), |plane, row| format!("{}\n{}", plane, self.clusters.text(row)))
         .trim()
     )
   }
@@ -137,25 +188,36 @@ impl Plane {
   /// Creates a plane from text.
   pub fn new(content: &str) -> Self {
     let mut rows = vec![];
-    for content_line in content.lines() {
+    let mut clusters = Clusters::default();
+    let mut origins = vec![];
+    for (line_index, content_line) in content.lines().enumerate() {
       let line = content_line.trim();
       if !line.is_empty() {
-        let mut columns = vec![];
-        for ch in line.chars() {
-          columns.push(ch);
-        }
-        rows.push(columns);
+        rows.push(clusters.columns(line));
+        origins.push((line_index, content_line.chars().take_while(|ch| ch.is_whitespace()).count()));
       }
     }
     let iih = information_item_height(&rows);
     Self {
       chars: rows,
+      clusters,
       row: 1,
       col: 1,
       iih,
+      origins,
+      selection: None,
     }
   }
 
+  /// Returns the line number and column number (both starting from 1)
+  /// in the loaded content, corresponding to the specified plane position.
+  pub fn origin(&self, row: usize, col: usize) -> (usize, usize) {
+    self
+      .origins
+      .get(row)
+      .map_or((row + 1, col + 1), |(line_index, offset)| (line_index + 1, offset + col + 1))
+  }
+
   /// Returns the vertical position of the cursor in plane coordinates.
   pub fn cursor_row(&self) -> usize {
     self.row
@@ -168,7 +230,605 @@ impl Plane {
 
   /// Returns `true` if the current cursor position is valid.
   pub fn is_valid_cursor_pos(&self) -> bool {
-    (1..self.chars.len() - 1).contains(&self.row) && (1..self.chars[self.row].len() - 1).contains(&self.col)
+    (1..self.chars.len().saturating_sub(1)).contains(&self.row) && (1..self.chars[self.row].len() - 1).contains(&self.col)
+  }
+
+  /// Places cursor at the specified position, when this position is allowed.
+  pub fn cursor_move_to(&mut self, row: usize, col: usize) -> bool {
+    if (1..self.chars.len().saturating_sub(1)).contains(&row)
+      && (1..self.chars[row].len() - 1).contains(&col)
+      && !is_box_drawing_character!(self.chars[row][col])
+    {
+      self.row = row;
+      self.col = col;
+      self.skip_wide_filler();
+      return true;
+    }
+    false
+  }
+
+  /// Returns `true` when the cursor is placed in the information item name cell.
+  pub fn is_cursor_in_information_item_name(&self) -> bool {
+    self.row < self.iih
+  }
+
+  /// Returns the name of the information item, lines are joined with single space.
+  pub fn information_item_name(&self) -> String {
+    self.chars[..self.iih]
+      .iter()
+      .skip(1)
+      .map(|row| self.clusters.text(&row[1..row.len() - 1]).trim().to_string())
+      .filter(|line| !line.is_empty())
+      .collect::<Vec<String>>()
+      .join(" ")
+  }
+
+  /// Replaces the information item name cell with a single line cell containing the specified name,
+  /// the cell is removed when the name is empty. The bottom border of the cell is joined with the top
+  /// border of the decision table, the last column of the table is widened when the name is wider than the table.
+  pub fn set_information_item_name(&mut self, name: &str) {
+    let name = name.trim();
+    let in_name = self.row < self.iih;
+    // remove the old cell and disconnect the top border of the table
+    self.chars.drain(..self.iih);
+    if !in_name {
+      self.row -= self.iih;
+    }
+    for ch in &mut self.chars[0] {
+      let mut arms = arms_of(*ch);
+      arms[0] = 0;
+      *ch = junction(arms).unwrap_or(*ch);
+    }
+    self.iih = 0;
+    if !name.is_empty() {
+      // the right border of the cell never meets a double line
+      let mut name_right = text_width(name) + 3;
+      while self.chars[0].get(name_right).is_some_and(|ch| arms_of(*ch)[2] == 2) {
+        name_right += 1;
+      }
+      let right = self.chars[0].len() - 1;
+      if name_right > right {
+        for row in &mut self.chars {
+          let weight = row.last().map_or(0, |ch| arms_of(*ch)[3]);
+          let fill = junction([0, weight, 0, weight]).unwrap_or(CH_WS);
+          let at = row.len() - 1;
+          row.splice(at..at, vec![fill; name_right - right]);
+        }
+      }
+      for col_index in [0, name_right] {
+        let ch = &mut self.chars[0][col_index];
+        let mut arms = arms_of(*ch);
+        arms[0] = 1;
+        *ch = junction(arms).unwrap_or(*ch);
+      }
+      let mut top = vec!['─'; name_right + 1];
+      top[0] = '┌';
+      top[name_right] = '┐';
+      let mut line = vec![CH_WS; name_right + 1];
+      line[0] = '│';
+      line[name_right] = '│';
+      let columns = self.clusters.columns(name);
+      line.splice(2..2 + columns.len(), columns);
+      self.chars.insert(0, line);
+      self.chars.insert(0, top);
+      self.iih = 2;
+      if !in_name {
+        self.row += 2;
+      }
+    }
+    if in_name {
+      // the cursor is placed at the beginning of the name, or in the hit policy cell when the name was removed
+      self.row = 1;
+      self.col = 1;
+    }
+  }
+
+  /// Starts selecting in the specified mode, the selection starts at the cursor position.
+  /// When the selection is already started, only the mode is changed.
+  pub fn begin_selection(&mut self, mode: SelectionMode) {
+    let (row, col) = self.selection.map_or((self.row, self.col), |(_, row, col)| (row, col));
+    self.selection = Some((mode, row, col));
+  }
+
+  /// Returns the current selection mode, `None` when nothing is selected.
+  pub fn selection_mode(&self) -> Option<SelectionMode> {
+    self.selection.map(|(mode, _, _)| mode)
+  }
+
+  /// Cancels the selection.
+  pub fn clear_selection(&mut self) {
+    self.selection = None;
+  }
+
+  /// Returns regions of the plane covered by the selection.
+  pub fn selected_regions(&self) -> Vec<Region> {
+    let Some((mode, row, col)) = self.selection else {
+      return vec![];
+    };
+    match mode {
+      SelectionMode::Characters => self
+        .selected_characters(row, col)
+        .into_iter()
+        .map(|(top, left, right)| Region { top, left, bottom: top, right })
+        .collect(),
+      SelectionMode::Cells => self.selected_cells(row, col).into_iter().flatten().collect(),
+      SelectionMode::Rules => self
+        .selected_rules(row, col)
+        .map(|(decision_table, range)| decision_table.rules[range].iter().map(|rule| rule.region).collect())
+        .unwrap_or_default(),
+    }
+  }
+
+  /// Copies the selected content into the register, the selection is cancelled.
+  pub fn copy(&mut self) -> Option<Register> {
+    let (mode, row, col) = self.selection.take()?;
+    let register = match mode {
+      SelectionMode::Characters => Register::Text(
+        self
+          .selected_characters(row, col)
+          .into_iter()
+          .map(|(r, left, right)| self.clusters.text(&self.chars[r][left..=right]).trim_end().to_string())
+          .collect(),
+      ),
+      SelectionMode::Cells => Register::Cells(
+        self
+          .selected_cells(row, col)
+          .into_iter()
+          .map(|regions| regions.into_iter().map(|region| cell(self, region).lines).collect())
+          .collect(),
+      ),
+      SelectionMode::Rules => {
+        let (decision_table, range) = self.selected_rules(row, col)?;
+        Register::Rules(
+          decision_table.rules[range]
+            .iter()
+            .map(|rule| rule.entries().map(|entry| entry.lines.clone()).collect())
+            .collect(),
+        )
+      }
+    };
+    Some(register)
+  }
+
+  /// Copies the selected content into the register and removes it from the plane: selected characters
+  /// are deleted, selected cells are cleared and selected rules are deleted (the last remaining rule is only cleared).
+  pub fn cut(&mut self) -> Option<Register> {
+    let (mode, row, col) = self.selection?;
+    let characters = self.selected_characters(row, col);
+    let cells = self.selected_cells(row, col);
+    let rules = self.selected_rules(row, col);
+    let register = self.copy()?;
+    match mode {
+      SelectionMode::Characters => {
+        for (r, left, right) in characters.iter().rev() {
+          // double-width characters are deleted at once, so characters are counted instead of columns
+          let count = column_char_count(&self.chars[*r][*left..=*right]);
+          for _ in 0..count {
+            self.row = *r;
+            self.col = *left;
+            if is_box_drawing_character!(self.chars[*r][*left]) {
+              break;
+            }
+            self.delete_char();
+          }
+        }
+        if let Some((r, left, _)) = characters.first() {
+          self.row = *r;
+          self.col = *left;
+        }
+      }
+      SelectionMode::Cells => {
+        for region in cells.iter().flatten() {
+          for row in &mut self.chars[region.top..=region.bottom] {
+            row[region.left..=region.right].fill(CH_WS);
+          }
+        }
+      }
+      SelectionMode::Rules => {
+        let (decision_table, range) = rules?;
+        for rule in decision_table.rules[range].iter().rev() {
+          self.row = rule.region.top;
+          self.col = rule.region.left;
+          if !self.delete_rule() {
+            for entry in rule.entries() {
+              self.write_cell(entry.region, &[]);
+            }
+          }
+        }
+      }
+    }
+    if !self.is_allowed_position(0, 0) {
+      self.cursor_move_cell_start();
+    }
+    Some(register)
+  }
+
+  /// Pastes the content of the register at the cursor position: text is inserted in the current cell,
+  /// cells are written starting from the current cell and rules are inserted after the current rule.
+  /// Cells grow when the pasted content does not fit. Returns `false` when nothing was pasted.
+  pub fn paste(&mut self, register: &Register) -> bool {
+    self.selection = None;
+    match register {
+      Register::Text(lines) => self.paste_text(lines),
+      Register::Cells(rows) => self.paste_cells(rows),
+      Register::Rules(rules) => self.paste_rules(rules),
+    }
+  }
+
+  /// Inserts text pasted into the terminal at the cursor position, new lines are placed in following
+  /// lines of the cell and tabs move to the next cell to the right. Text that does not fit into cells
+  /// to the right is dropped. Returns `false` when nothing was inserted.
+  pub fn insert_text(&mut self, text: &str) -> bool {
+    self.selection = None;
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut inserted = false;
+    for (index, segment) in text.split('\t').enumerate() {
+      if index > 0 {
+        // the cursor stays in the same cell when there is no cell to the right
+        let border = self.get_vert_line_offset_right().map(|offset| self.col as i32 + offset);
+        if !self.cursor_move_cell_right() || border.is_none_or(|border| (self.col as i32) < border) {
+          break;
+        }
+      }
+      let lines: Vec<String> = segment.split('\n').map(|line| line.chars().filter(|ch| !ch.is_control()).collect()).collect();
+      if !self.paste_text(&lines) {
+        break;
+      }
+      inserted = true;
+    }
+    inserted
+  }
+
+  /// Returns single line regions of text matching the pattern, in the order of rows and columns.
+  /// Matches never span more than one cell, box-drawing characters are never matched.
+  pub fn find(&self, pattern: &str, scope: SearchScope) -> Vec<Region> {
+    // a pattern containing a cluster not present in the plane is never found
+    let Some(pattern) = self.clusters.existing_columns(pattern) else {
+      return vec![];
+    };
+    if pattern.is_empty() {
+      return vec![];
+    }
+    let cells = match scope {
+      SearchScope::Table => None,
+      SearchScope::Clause => match self.clause_regions() {
+        Some(cells) => Some(cells),
+        None => return vec![],
+      },
+    };
+    let regions = Regions::new(&self.chars);
+    let mut found = vec![];
+    for (r, row) in self.chars.iter().enumerate() {
+      let mut c = 0;
+      while c + pattern.len() <= row.len() {
+        let region = regions.region_at(r, c);
+        if row[c..c + pattern.len()] == pattern[..]
+          && region.is_some()
+          && (c..c + pattern.len()).all(|col| regions.region_at(r, col) == region)
+          && cells.as_ref().is_none_or(|cells| region.is_some_and(|region| cells.contains(&region)))
+        {
+          found.push(Region {
+            top: r,
+            left: c,
+            bottom: r,
+            right: c + pattern.len() - 1,
+          });
+          c += pattern.len();
+        } else {
+          c += 1;
+        }
+      }
+    }
+    found
+  }
+
+  /// Replaces the text in the found region with another text, the cursor is placed after the replacement.
+  /// The column is widened or narrowed like when the text was typed or deleted.
+  pub fn replace(&mut self, found: Region, replacement: &str) -> bool {
+    if !self.cursor_move_to(found.top, found.left) {
+      return false;
+    }
+    self.selection = None;
+    let count = column_char_count(&self.chars[found.top][found.left..=found.right]);
+    // the replacement is inserted before the found text, so deleting never leaves the cell
+    for ch in replacement.chars() {
+      self.insert_char(ch);
+    }
+    for _ in 0..count {
+      self.delete_char();
+    }
+    true
+  }
+
+  /// Returns regions of cells belonging to the clause under the cursor: the header cell(s)
+  /// and the corresponding entries in all rules.
+  fn clause_regions(&self) -> Option<Vec<Region>> {
+    let decision_table = recognize_structure(self).ok()?;
+    let index = match self.cursor_position(&decision_table)? {
+      Position::Header(index) | Position::Rule(_, Some(index)) => index,
+      Position::Rule(_, None) => return None,
+    };
+    let mut regions = headers(&decision_table, &decision_table.clauses()[index]);
+    regions.extend(decision_table.rules.iter().filter_map(|rule| entry_region(rule, index)));
+    Some(regions)
+  }
+
+  /// Returns the position of the cursor in the structure of the decision table.
+  fn cursor_position(&self, decision_table: &DecisionTable) -> Option<Position> {
+    let contains = |region: &Region| (region.top..=region.bottom).contains(&self.row) && (region.left..=region.right).contains(&self.col);
+    if let Some(index) = decision_table
+      .clauses()
+      .iter()
+      .position(|clause| headers(decision_table, clause).iter().any(contains))
+    {
+      return Some(Position::Header(index));
+    }
+    decision_table.rules.iter().enumerate().find_map(|(rule_index, rule)| {
+      // numbers of rules in crosstab tables are not placed in the plane
+      if decision_table.orientation != Orientation::CrossTable && contains(&rule.number.region) {
+        return Some(Position::Rule(rule_index, None));
+      }
+      rule
+        .entries()
+        .position(|entry| contains(&entry.region))
+        .map(|index| Position::Rule(rule_index, Some(index)))
+    })
+  }
+
+  /// Returns the location of the cursor in terms of rules and clauses, accepted by [Plane::go_to]:
+  /// `12:Order size` in the entry of the rule, `rule 12` in the rule number and `input Order size` in the header.
+  pub fn cursor_location(&self) -> Option<String> {
+    let decision_table = recognize_structure(self).ok()?;
+    let clauses = decision_table.clauses();
+    let number = |rule_index: usize| {
+      let number = decision_table.rules[rule_index].number.text();
+      if number.is_empty() || decision_table.orientation == Orientation::CrossTable {
+        (rule_index + 1).to_string()
+      } else {
+        number
+      }
+    };
+    Some(match self.cursor_position(&decision_table)? {
+      Position::Header(index) => format!("{} {}", clauses[index].kind, clauses[index].name),
+      Position::Rule(rule_index, None) => format!("rule {}", number(rule_index)),
+      Position::Rule(rule_index, Some(index)) => format!("{}:{}", number(rule_index), clauses[index].name),
+    })
+  }
+
+  /// Places the cursor in the cell specified by the target: `rule 12` (the first entry of the rule),
+  /// `input Customer type`, `output Discount`, `annotation Description` (the header of the clause)
+  /// or `12:Order size` (the entry of the rule in the clause). Names of clauses are case-insensitive.
+  pub fn go_to(&mut self, target: &str) -> Result<()> {
+    let decision_table = recognize_structure(self).map_err(|e| err_go_to(target, &e.to_string()))?;
+    let clauses = decision_table.clauses();
+    let rule_index = |number: &str| {
+      let number = number.trim();
+      decision_table
+        .rules
+        .iter()
+        .position(|rule| decision_table.orientation != Orientation::CrossTable && rule.number.text() == number)
+        .or_else(|| {
+          number
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=decision_table.rules.len()).contains(n))
+            .map(|n| n - 1)
+        })
+        .ok_or_else(|| err_go_to(target, &format!("rule {} not found", number)))
+    };
+    let clause_index = |kind: Option<&str>, name: &str| {
+      clauses
+        .iter()
+        .position(|clause| kind.is_none_or(|kind| clause.kind.to_string() == kind) && same_name(&clause.name, name))
+        .ok_or_else(|| err_go_to(target, &format!("{} '{}' not found", kind.unwrap_or("clause"), name.trim())))
+    };
+    let region = match target.trim().split_once(char::is_whitespace) {
+      Some(("rule", number)) => {
+        let rule = &decision_table.rules[rule_index(number)?];
+        entry_region(rule, 0).unwrap_or(rule.number.region)
+      }
+      Some((kind @ ("input" | "output" | "annotation"), name)) => headers(&decision_table, &clauses[clause_index(Some(kind), name)?])[0],
+      _ => match target.split_once(':') {
+        Some((number, name)) => {
+          let rule = &decision_table.rules[rule_index(number)?];
+          entry_region(rule, clause_index(None, name)?).unwrap_or(rule.number.region)
+        }
+        None => {
+          return Err(err_go_to(
+            target,
+            "expected 'rule N', 'input NAME', 'output NAME', 'annotation NAME' or 'N:NAME'",
+          ))
+        }
+      },
+    };
+    self.cursor_move_to_cell(region);
+    Ok(())
+  }
+
+  /// Places the cursor in the same cell of the next (or previous) rule, rules spanning multiple lines
+  /// are skipped at once. From the header the cursor moves to the first rule, and back.
+  pub fn cursor_move_rule(&mut self, forward: bool) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    let rules = &decision_table.rules;
+    let region = match self.cursor_position(&decision_table) {
+      Some(Position::Header(index)) if forward => rules.first().and_then(|rule| entry_region(rule, index)),
+      Some(Position::Rule(rule_index, index)) => {
+        let next = if forward {
+          rules.get(rule_index + 1)
+        } else {
+          rule_index.checked_sub(1).and_then(|i| rules.get(i))
+        };
+        match (next, index) {
+          (Some(rule), Some(index)) => entry_region(rule, index),
+          (Some(rule), None) => Some(rule.number.region),
+          (None, Some(index)) if !forward => Some(headers(&decision_table, &decision_table.clauses()[index])[0]),
+          _ => None,
+        }
+      }
+      _ => None,
+    };
+    region.is_some_and(|region| self.cursor_move_to_cell(region))
+  }
+
+  /// Places the cursor in the header of the clause under the cursor,
+  /// or in the hit policy cell when the cursor is not in any clause.
+  pub fn cursor_move_header(&mut self) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    let region = match self.cursor_position(&decision_table) {
+      Some(Position::Header(index) | Position::Rule(_, Some(index))) => headers(&decision_table, &decision_table.clauses()[index])[0],
+      _ => decision_table.hit_policy_cell.region,
+    };
+    self.cursor_move_to_cell(region)
+  }
+
+  /// Places the cursor in the first cell of the rule body: the first entry of the first rule.
+  pub fn cursor_move_body(&mut self) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    match decision_table.rules.first().and_then(|rule| entry_region(rule, 0)) {
+      Some(region) => self.cursor_move_to_cell(region),
+      None => false,
+    }
+  }
+
+  /// Places the cursor at the first character of the text in the cell, or at the beginning of an empty cell.
+  fn cursor_move_to_cell(&mut self, region: Region) -> bool {
+    let (row, col) = (region.top..=region.bottom)
+      .find_map(|row| (region.left..=region.right).find(|col| self.chars[row][*col] != CH_WS).map(|col| (row, col)))
+      .unwrap_or((region.top, region.left + 1.min(region.width() - 1)));
+    self.cursor_move_to(row, col)
+  }
+
+  /// Returns ranges of selected characters in rows: row index, first and last column index.
+  /// Only characters in the cell where the selection started are selected.
+  fn selected_characters(&self, row: usize, col: usize) -> Vec<(usize, usize, usize)> {
+    let Some(region) = Regions::new(&self.chars).region_at(row, col) else {
+      return vec![];
+    };
+    let (start, end) = if (row, col) <= (self.row, self.col) {
+      ((row, col), (self.row, self.col))
+    } else {
+      ((self.row, self.col), (row, col))
+    };
+    let mut ranges = vec![];
+    for r in start.0.max(region.top)..=end.0.min(region.bottom) {
+      let left = if r == start.0 { start.1.max(region.left) } else { region.left };
+      let right = if r == end.0 { end.1.min(region.right) } else { region.right };
+      if left <= right {
+        ranges.push((r, left, right));
+      }
+    }
+    ranges
+  }
+
+  /// Returns rows of selected cells, all cells starting in the rectangle spanning the cell
+  /// where the selection started and the cell under the cursor are selected.
+  fn selected_cells(&self, row: usize, col: usize) -> Vec<Vec<Region>> {
+    let regions = Regions::new(&self.chars);
+    let (Some(first), Some(last)) = (regions.region_at(row, col), regions.region_at(self.row, self.col)) else {
+      return vec![];
+    };
+    let mut rows = vec![];
+    for r in first.top.min(last.top)..=first.bottom.max(last.bottom) {
+      let cells: Vec<Region> = (first.left.min(last.left)..=first.right.max(last.right))
+        .filter_map(|c| regions.region_at(r, c).filter(|region| region.top == r && region.left == c))
+        .collect();
+      if !cells.is_empty() {
+        rows.push(cells);
+      }
+    }
+    rows
+  }
+
+  /// Returns the recognized decision table and the range of indexes of rules between the rule
+  /// where the selection started and the rule under the cursor. Rules of crosstab tables are not selected.
+  fn selected_rules(&self, row: usize, col: usize) -> Option<(DecisionTable, Range<usize>)> {
+    let decision_table = recognize_structure(self).ok()?;
+    if decision_table.orientation == Orientation::CrossTable {
+      return None;
+    }
+    let index_at = |row: usize, col: usize| {
+      decision_table.rules.iter().position(|rule| {
+        let region = rule.region;
+        (region.top..=region.bottom).contains(&row) && (region.left..=region.right).contains(&col)
+      })
+    };
+    let (first, last) = (index_at(row, col)?, index_at(self.row, self.col)?);
+    Some((decision_table, first.min(last)..first.max(last) + 1))
+  }
+
+  /// Inserts lines of text at the cursor position, following lines are placed in following lines of the cell.
+  fn paste_text(&mut self, lines: &[String]) -> bool {
+    if !self.is_allowed_position(0, 0) {
+      return false;
+    }
+    for (index, line) in lines.iter().enumerate() {
+      if index > 0 {
+        let Some(region) = Regions::new(&self.chars).region_at(self.row, self.col) else {
+          return true;
+        };
+        if self.row == region.bottom {
+          self.grow_row(region.bottom);
+        }
+        self.row += 1;
+        self.col = region.left + 1.min(region.width() - 1);
+      }
+      for ch in line.chars() {
+        self.insert_char(ch);
+      }
+    }
+    true
+  }
+
+  /// Writes rows of cells starting from the cell under the cursor, cells outside the table are skipped.
+  fn paste_cells(&mut self, rows: &[Vec<Vec<String>>]) -> bool {
+    let Some(start) = Regions::new(&self.chars).region_at(self.row, self.col) else {
+      return false;
+    };
+    let mut top = start.top;
+    for cells in rows {
+      let mut col = start.left;
+      for lines in cells {
+        let Some(region) = Regions::new(&self.chars).region_at(top, col) else {
+          break;
+        };
+        col = self.write_cell(region, lines).right + 2;
+      }
+      match Regions::new(&self.chars).region_at(top, start.left) {
+        Some(region) => top = region.bottom + 2,
+        None => break,
+      }
+    }
+    true
+  }
+
+  /// Inserts rules after the rule under the cursor, the cursor is placed in the last inserted rule.
+  fn paste_rules(&mut self, rules: &[Vec<Vec<String>>]) -> bool {
+    for entries in rules {
+      if !self.insert_rule() {
+        return false;
+      }
+      let Ok(decision_table) = recognize_structure(self) else {
+        return false;
+      };
+      let Some(index) = self.rule_under_cursor(&decision_table) else {
+        return false;
+      };
+      let rule = &decision_table.rules[index];
+      let positions: Vec<(usize, usize)> = rule.entries().map(|entry| (entry.region.top, entry.region.left)).collect();
+      // entries are written from the last one, so growing cells does not move entries not written yet
+      for ((top, left), lines) in positions.into_iter().zip(entries.iter()).rev() {
+        if let Some(region) = Regions::new(&self.chars).region_at(top, left) {
+          self.write_cell(region, lines);
+        }
+      }
+    }
+    true
   }
 
   /// Moves cursor up.
@@ -187,11 +847,11 @@ impl Plane {
   /// Moves cursor down.
   pub fn cursor_move_down(&mut self) -> bool {
     if self.is_allowed_position(1, 0) {
-      self.row += 1;
+      self.cursor_move(1, 0);
       return true;
     }
     if self.is_horz_line(1, 0) && self.is_allowed_position(2, 0) {
-      self.row += 2;
+      self.cursor_move(2, 0);
       return true;
     }
     false
@@ -201,10 +861,12 @@ impl Plane {
   pub fn cursor_move_left(&mut self) -> bool {
     if self.is_allowed_position(0, -1) {
       self.col -= 1;
+      self.skip_wide_filler();
       return true;
     }
     if self.is_vert_line(0, -1) && self.is_allowed_position(0, -2) {
       self.col -= 2;
+      self.skip_wide_filler();
       return true;
     }
     false
@@ -212,12 +874,14 @@ impl Plane {
 
   /// Moves cursor right.
   pub fn cursor_move_right(&mut self) -> bool {
-    if self.is_allowed_position(0, 1) {
-      self.col += 1;
+    // double-width character under the cursor is skipped together with its second column
+    let step = if self.is_wide_filler(0, 1) { 2 } else { 1 };
+    if self.is_allowed_position(0, step) {
+      self.col += step as usize;
       return true;
     }
-    if self.is_vert_line(0, 1) && self.is_allowed_position(0, 2) {
-      self.col += 2;
+    if self.is_vert_line(0, step) && self.is_allowed_position(0, step + 1) {
+      self.col += step as usize + 1;
       return true;
     }
     false
@@ -247,7 +911,7 @@ impl Plane {
 
   /// Places cursor at the first character in the decision table (same row).
   pub fn cursor_move_table_start(&mut self) -> bool {
-    if (1..self.chars.len() - 1).contains(&self.row) {
+    if (1..self.chars.len().saturating_sub(1)).contains(&self.row) {
       return if is_box_drawing_character!(self.chars[self.row][1]) {
         self.cursor_move_cell_start()
       } else {
@@ -260,12 +924,13 @@ impl Plane {
 
   /// Places cursor at the last character in the cell (same row).
   pub fn cursor_move_table_end(&mut self) -> bool {
-    if (1..self.chars.len() - 1).contains(&self.row) {
+    if (1..self.chars.len().saturating_sub(1)).contains(&self.row) {
       let index = self.chars[self.row].len() - 2;
       return if is_box_drawing_character!(self.chars[self.row][index]) {
         self.cursor_move_cell_end()
       } else {
         self.col = self.chars[self.row].len() - 2;
+        self.skip_wide_filler();
         true
       };
     }
@@ -302,8 +967,42 @@ impl Plane {
     false
   }
 
-  /// Inserts a character at the current position.
+  /// Inserts a character at the current position, a double-width character occupies two columns
+  /// and a combining mark is composed with the character before the cursor.
   pub fn insert_char(&mut self, ch: char) {
+    if char_width(ch) == 0 {
+      self.compose_char_before(ch);
+      return;
+    }
+    for column in self.clusters.columns(&ch.to_string()) {
+      self.insert_column_char(column);
+    }
+  }
+
+  /// Composes the character before the cursor with the combining mark, the mark is placed
+  /// in a cluster with the character before the cursor when there is no precomposed character.
+  fn compose_char_before(&mut self, mark: char) {
+    if self.is_allowed_position(0, -1) {
+      let mut col = self.col - 1;
+      if self.chars[self.row][col] == CH_WIDE && col > 0 {
+        col -= 1;
+      }
+      let base = self.chars[self.row][col];
+      if let Some(composed) = compose(base, mark) {
+        self.chars[self.row][col] = composed;
+        return;
+      }
+      let text = format!("{}{}", self.clusters.text(&[base]), mark);
+      let columns = self.clusters.columns(&text);
+      // the cluster occupies the same number of columns as the base character, unless all clusters are used
+      if columns.len() == self.clusters.char_width(base) {
+        self.chars[self.row].splice(col..col + columns.len(), columns);
+      }
+    }
+  }
+
+  /// Inserts a character occupying a single column at the current position.
+  fn insert_column_char(&mut self, ch: char) {
     if self.is_valid_cursor_pos() {
       let pos = self.last_col_before_vert_line_right();
       let (found, offset) = self.is_whitespace_before_vert_line();
@@ -321,6 +1020,14 @@ impl Plane {
 
   /// Deletes a character placed *before* the cursor.
   pub fn delete_char_before(&mut self) {
+    let width = if self.is_wide_filler(0, -1) { 2 } else { 1 };
+    for _ in 0..width {
+      self.delete_column_char_before();
+    }
+  }
+
+  /// Deletes a single column placed *before* the cursor.
+  fn delete_column_char_before(&mut self) {
     if self.is_allowed_position(0, -1) {
       let pos = self.last_col_before_vert_line_right();
       self.chars[self.row].insert(pos + 1, CH_WS);
@@ -335,6 +1042,14 @@ impl Plane {
 
   /// Deletes a character placed *under* the cursor.
   pub fn delete_char(&mut self) {
+    let width = if self.is_wide_filler(0, 1) { 2 } else { 1 };
+    for _ in 0..width {
+      self.delete_column_char();
+    }
+  }
+
+  /// Deletes a single column placed *under* the cursor.
+  fn delete_column_char(&mut self) {
     let pos = self.last_col_before_vert_line_right();
     self.chars[self.row].insert(pos + 1, CH_WS);
     self.chars[self.row].remove(self.col);
@@ -368,17 +1083,569 @@ impl Plane {
     self.col = col_first;
   }
 
+  /// Inserts an empty rule after the rule under the cursor, the cursor is placed in the new rule.
+  /// In crosstab tables a row or a column of output entries is inserted, depending on the cursor position.
+  pub fn insert_rule(&mut self) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    if decision_table.orientation == Orientation::CrossTable {
+      return self.insert_crosstab_value(&decision_table);
+    }
+    let Some(index) = self.rule_under_cursor(&decision_table) else {
+      return false;
+    };
+    let region = decision_table.rules[index].region;
+    match decision_table.orientation {
+      Orientation::RuleAsRow => {
+        // new rule is a copy of the first row of the current rule, preceded by a horizontal line
+        let template = &self.chars[region.top];
+        let last = template.len() - 1;
+        let line: Vec<char> = template
+          .iter()
+          .enumerate()
+          .map(|(col_index, ch)| {
+            let weight = arms_of(*ch)[0];
+            let left = if col_index == 0 { 0 } else { 1 };
+            let right = if col_index == last { 0 } else { 1 };
+            junction([weight, right, weight, left]).unwrap_or('─')
+          })
+          .collect();
+        let empty: Vec<char> = template.iter().map(|ch| if is_box_drawing_character!(ch) { *ch } else { CH_WS }).collect();
+        self.chars.insert(region.bottom + 1, empty);
+        self.chars.insert(region.bottom + 1, line);
+        self.row = region.bottom + 2;
+      }
+      Orientation::RuleAsColumn => {
+        // new rule is a copy of the current rule column, preceded by a vertical line
+        for row in self.chars.iter_mut().take(decision_table.bottom + 1).skip(decision_table.header_top) {
+          let weight = arms_of(row[region.right])[1];
+          let border = arms_of(row[region.right + 1]);
+          let mut column = vec![junction([border[0], weight, border[2], weight]).unwrap_or('│')];
+          column.extend(
+            row[region.left..=region.right]
+              .iter()
+              .map(|ch| if is_box_drawing_character!(ch) { *ch } else { CH_WS }),
+          );
+          row.splice(region.right + 1..region.right + 1, column);
+        }
+        self.col = region.right + 2;
+        self.update_joins();
+      }
+      // rows and columns of crosstab tables are inserted separately
+      Orientation::CrossTable => {}
+    }
+    true
+  }
+
+  /// Deletes the rule under the cursor, the last remaining rule is never deleted.
+  /// In crosstab tables a row or a column of output entries is deleted, depending on the cursor position.
+  pub fn delete_rule(&mut self) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    if decision_table.orientation == Orientation::CrossTable {
+      return self.delete_crosstab_value(&decision_table);
+    }
+    let Some(index) = self.rule_under_cursor(&decision_table) else {
+      return false;
+    };
+    if decision_table.rules.len() < 2 {
+      return false;
+    }
+    let region = decision_table.rules[index].region;
+    let is_last = index + 1 == decision_table.rules.len();
+    match decision_table.orientation {
+      Orientation::RuleAsRow => {
+        // the line above the rule is deleted, the line below when the first rule is deleted
+        let (first, last) = if index > 0 {
+          (region.top - 1, region.bottom)
+        } else {
+          (region.top, region.bottom + 1)
+        };
+        self.chars.drain(first..=last);
+        self.row = if index > 0 { region.top - 2 } else { region.top };
+      }
+      Orientation::RuleAsColumn => {
+        // the line on the right side is deleted, the line on the left side when the last rule is deleted
+        let (first, last) = if is_last {
+          (region.left - 1, region.right)
+        } else {
+          (region.left, region.right + 1)
+        };
+        for row in self.chars.iter_mut().take(decision_table.bottom + 1).skip(decision_table.header_top) {
+          row.drain(first..=last);
+        }
+        self.col = if is_last { region.left - 2 } else { region.left };
+        self.update_joins();
+      }
+      // rows and columns of crosstab tables are deleted separately
+      Orientation::CrossTable => {}
+    }
+    if !self.is_allowed_position(0, 0) {
+      self.cursor_move_cell_start();
+    }
+    true
+  }
+
+  /// Sorts rules by entries of the clauses listed in keys, like `Age, Score desc`, and renumbers rules.
+  /// Rules of tables with hit policy `F` are not sorted, because the order of rules decides the result.
+  /// Returns a warning when the order of rules changes the order of outputs, like with hit policy `R`.
+  pub fn sort_rules(&mut self, keys: &str) -> Result<Option<String>> {
+    let sort_keys = SortKey::parse_list(keys)?;
+    let decision_table = recognize_structure(self)?;
+    if decision_table.orientation == Orientation::CrossTable {
+      return Err(err_sort(keys, "rules of crosstab tables can not be sorted"));
+    }
+    if decision_table.hit_policy == HitPolicy::First {
+      return Err(err_sort(keys, "the order of rules is significant for hit policy F"));
+    }
+    if has_merged_cells(&decision_table) {
+      return Err(err_sort(keys, "merged cells must be split before sorting"));
+    }
+    let clauses = decision_table.clauses();
+    let mut columns = vec![];
+    for key in &sort_keys {
+      let Some(index) = clauses.iter().position(|clause| same_name(&clause.name, &key.name)) else {
+        return Err(err_sort(keys, &format!("unknown clause '{}'", key.name)));
+      };
+      let allowed_values = match clauses[index].allowed_values {
+        Some(allowed_values) => split_list(&allowed_values.text()),
+        None => vec![],
+      };
+      columns.push((key, index, allowed_values));
+    }
+    let texts: Vec<Vec<String>> = decision_table
+      .rules
+      .iter()
+      .map(|rule| rule.entries().map(|entry| entry.text()).collect())
+      .collect();
+    let mut order: Vec<usize> = (0..texts.len()).collect();
+    order.sort_by(|a, b| {
+      columns
+        .iter()
+        .map(|(key, index, allowed_values)| key.compare(&texts[*a][*index], &texts[*b][*index], allowed_values))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+    });
+    let position = self.cursor_in_rule(&decision_table);
+    self.reorder_rules(&decision_table, &order);
+    self.renumber_rules();
+    if let Some((index, entry, offset)) = position {
+      if let Some(new_index) = order.iter().position(|old_index| *old_index == index) {
+        self.cursor_move_to_rule(new_index, entry, offset);
+      }
+    }
+    Ok((decision_table.hit_policy == HitPolicy::RuleOrder).then(|| "hit policy R returns outputs in the new order of rules".to_string()))
+  }
+
+  /// Places rules in the specified order, the rule with index `order[i]` is placed at index `i`.
+  /// Lines between rules stay in place, rules of different heights or widths are moved as units.
+  fn reorder_rules(&mut self, decision_table: &DecisionTable, order: &[usize]) {
+    let regions: Vec<Region> = decision_table.rules.iter().map(|rule| rule.region).collect();
+    let (Some(first), Some(last)) = (regions.first().copied(), regions.last().copied()) else {
+      return;
+    };
+    match decision_table.orientation {
+      Orientation::RuleAsRow => {
+        let mut rows = vec![];
+        for (position, index) in order.iter().enumerate() {
+          if position > 0 {
+            rows.push(self.chars[regions[position].top - 1].clone());
+          }
+          rows.extend_from_slice(&self.chars[regions[*index].top..=regions[*index].bottom]);
+        }
+        self.chars.splice(first.top..=last.bottom, rows);
+      }
+      Orientation::RuleAsColumn => {
+        for row in self.chars.iter_mut().take(decision_table.bottom + 1).skip(decision_table.header_top) {
+          let mut columns = vec![];
+          for (position, index) in order.iter().enumerate() {
+            if position > 0 {
+              columns.push(row[regions[position].left - 1]);
+            }
+            columns.extend_from_slice(&row[regions[*index].left..=regions[*index].right]);
+          }
+          row.splice(first.left..=last.right, columns);
+        }
+        self.update_joins();
+      }
+      // crosstab tables have no rules to reorder
+      Orientation::CrossTable => {}
+    }
+  }
+
+  /// Writes consecutive numbers into rule number cells, numbers keep their position in cells when they fit.
+  fn renumber_rules(&mut self) {
+    // the table is recognized again after each number, because widening a cell moves other cells
+    while let Ok(decision_table) = recognize_structure(self) {
+      let Some((index, rule)) = decision_table
+        .rules
+        .iter()
+        .enumerate()
+        .find(|(index, rule)| rule.number.text() != (index + 1).to_string())
+      else {
+        return;
+      };
+      let expected = (index + 1).to_string();
+      let region = rule.number.region;
+      let start = (region.top..=region.bottom).find_map(|row| (region.left..=region.right).find(|col| self.chars[row][*col] != CH_WS).map(|col| (row, col)));
+      match start {
+        Some((row, col)) if col + expected.len() <= region.right => {
+          for line in &mut self.chars[region.top..=region.bottom] {
+            line[region.left..=region.right].fill(CH_WS);
+          }
+          for (offset, ch) in expected.chars().enumerate() {
+            self.chars[row][col + offset] = ch;
+          }
+        }
+        _ => {
+          self.write_cell(region, &[expected]);
+        }
+      }
+    }
+  }
+
+  /// Swaps the rule under the cursor with the next or the previous rule, and renumbers rules.
+  /// The cursor stays at the same position in the cell of the moved rule.
+  pub fn move_rule(&mut self, forward: bool) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    // crosstab tables have no rules to move, merged cells would join other rules after moving
+    if decision_table.orientation == Orientation::CrossTable || has_merged_cells(&decision_table) {
+      return false;
+    }
+    let Some((index, entry, offset)) = self.cursor_in_rule(&decision_table) else {
+      return false;
+    };
+    let other = if forward { index + 1 } else { index.wrapping_sub(1) };
+    if other >= decision_table.rules.len() {
+      return false;
+    }
+    let mut order: Vec<usize> = (0..decision_table.rules.len()).collect();
+    order.swap(index, other);
+    self.reorder_rules(&decision_table, &order);
+    self.renumber_rules();
+    self.cursor_move_to_rule(other, entry, offset)
+  }
+
+  /// Merges the entry under the cursor with the entry of the same clause in the next rule, the merged entry
+  /// is shared by both rules. Entries must have the same text or one of them must be empty.
+  /// Cells are merged only when rules are placed in rows.
+  pub fn merge_cells(&mut self) -> Result<()> {
+    let decision_table = recognize_structure(self)?;
+    if decision_table.orientation != Orientation::RuleAsRow {
+      return Err(err_merge_cells("cells are merged only when rules are placed in rows"));
+    }
+    let Some(Position::Rule(_, Some(entry))) = self.cursor_position(&decision_table) else {
+      return Err(err_merge_cells("the cursor is not placed in the rule entry"));
+    };
+    let Some(upper) = Regions::new(&self.chars).region_at(self.row, self.col) else {
+      return Err(err_merge_cells("the cursor is not placed in the rule entry"));
+    };
+    let Some(lower) = decision_table
+      .rules
+      .iter()
+      .filter_map(|rule| entry_region(rule, entry))
+      .find(|region| region.top == upper.bottom + 2)
+    else {
+      return Err(err_merge_cells("there is no entry below"));
+    };
+    let (upper_text, lower_text) = (cell(self, upper).text(), cell(self, lower).text());
+    if !upper_text.is_empty() && !lower_text.is_empty() && upper_text != lower_text {
+      return Err(err_merge_cells("entries have different text"));
+    }
+    let source = if upper_text.is_empty() { lower } else { upper };
+    let lines: Vec<Vec<char>> = (source.top..=source.bottom)
+      .map(|row| self.chars[row][source.left..=source.right].to_vec())
+      .collect();
+    let merged = Region { bottom: lower.bottom, ..upper };
+    self.fill_region(merged, &lines);
+    self.fix_junction(upper.bottom + 1, upper.left - 1);
+    self.fix_junction(upper.bottom + 1, upper.right + 1);
+    Ok(())
+  }
+
+  /// Splits the merged entry under the cursor into entries of individual rules,
+  /// each of them gets the text of the merged entry.
+  pub fn split_cell(&mut self) -> Result<()> {
+    let decision_table = recognize_structure(self)?;
+    if decision_table.orientation != Orientation::RuleAsRow {
+      return Err(err_split_cell("cells are merged only when rules are placed in rows"));
+    }
+    let Some(merged) = Regions::new(&self.chars).region_at(self.row, self.col) else {
+      return Err(err_split_cell("the cursor is not placed in the rule entry"));
+    };
+    let pieces: Vec<Region> = decision_table
+      .rules
+      .iter()
+      .filter(|rule| rule.entries().any(|entry| entry.region == merged))
+      .map(|rule| Region {
+        top: rule.region.top.max(merged.top),
+        bottom: rule.region.bottom.min(merged.bottom),
+        ..merged
+      })
+      .collect();
+    if pieces.len() < 2 {
+      return Err(err_split_cell("the entry is not merged"));
+    }
+    let mut lines: Vec<Vec<char>> = (merged.top..=merged.bottom)
+      .map(|row| self.chars[row][merged.left..=merged.right].to_vec())
+      .collect();
+    while lines.len() > 1 && lines.last().is_some_and(|line| line.iter().all(|ch| *ch == CH_WS)) {
+      lines.pop();
+    }
+    // pieces are filled from the last one, so growing a piece does not move pieces not filled yet
+    for (index, piece) in pieces.iter().enumerate().rev() {
+      if index + 1 < pieces.len() {
+        let row = piece.bottom + 1;
+        self.chars[row][piece.left..=piece.right].fill('─');
+        self.fix_junction(row, piece.left - 1);
+        self.fix_junction(row, piece.right + 1);
+      }
+      let mut piece = *piece;
+      while piece.bottom - piece.top + 1 < lines.len() {
+        self.grow_row(piece.bottom);
+        piece.bottom += 1;
+      }
+      self.fill_region(piece, &lines);
+    }
+    if !self.is_allowed_position(0, 0) {
+      self.cursor_move_to_cell(pieces[0]);
+    }
+    Ok(())
+  }
+
+  /// Replaces the content of the region with lines of characters, missing lines are filled with spaces.
+  fn fill_region(&mut self, region: Region, lines: &[Vec<char>]) {
+    for (offset, row) in self.chars[region.top..=region.bottom].iter_mut().enumerate() {
+      let target = &mut row[region.left..=region.right];
+      target.fill(CH_WS);
+      if let Some(line) = lines.get(offset) {
+        let len = line.len().min(target.len());
+        target[..len].copy_from_slice(&line[..len]);
+      }
+    }
+  }
+
+  /// Replaces the box-drawing character at the specified position with the junction matching neighbouring lines.
+  fn fix_junction(&mut self, row: usize, col: usize) {
+    let arms = |r: usize, c: usize| self.chars.get(r).and_then(|line| line.get(c)).map_or([0; 4], |ch| arms_of(*ch));
+    let expected = [
+      row.checked_sub(1).map_or(0, |r| arms(r, col)[2]),
+      arms(row, col + 1)[3],
+      arms(row + 1, col)[0],
+      col.checked_sub(1).map_or(0, |c| arms(row, c)[1]),
+    ];
+    if let Some(ch) = junction(expected) {
+      self.chars[row][col] = ch;
+    }
+  }
+
+  /// Returns the index of the rule under the cursor, the index of the entry under the cursor
+  /// (`None` in the rule number) and the offset of the cursor from the top left corner of the cell.
+  fn cursor_in_rule(&self, decision_table: &DecisionTable) -> Option<(usize, Option<usize>, (usize, usize))> {
+    let Some(Position::Rule(index, entry)) = self.cursor_position(decision_table) else {
+      return None;
+    };
+    let region = rule_cell(&decision_table.rules[index], entry)?;
+    Some((index, entry, (self.row - region.top, self.col - region.left)))
+  }
+
+  /// Places the cursor in the entry with the specified index of the rule with the specified index,
+  /// or in the rule number when no entry is specified, at the specified offset when it fits in the cell.
+  fn cursor_move_to_rule(&mut self, index: usize, entry: Option<usize>, offset: (usize, usize)) -> bool {
+    let Ok(decision_table) = recognize_structure(self) else {
+      return false;
+    };
+    let Some(region) = decision_table.rules.get(index).and_then(|rule| rule_cell(rule, entry)) else {
+      return false;
+    };
+    let row = region.top + offset.0.min(region.bottom - region.top);
+    let col = region.left + offset.1.min(region.right - region.left);
+    self.cursor_move_to(row, col) || self.cursor_move_to_cell(region)
+  }
+
+  /// Returns the index of the rule under the cursor.
+  fn rule_under_cursor(&self, decision_table: &DecisionTable) -> Option<usize> {
+    let contains = |rule: &DecisionRule| {
+      let region = rule.region;
+      (region.top..=region.bottom).contains(&self.row) && (region.left..=region.right).contains(&self.col)
+    };
+    decision_table.rules.iter().position(contains)
+  }
+
+  /// Returns the regions of values of the input placed in rows and the input placed in columns of the crosstab table.
+  fn crosstab_values(decision_table: &DecisionTable) -> (Vec<Region>, Vec<Region>) {
+    let mut row_values: Vec<Region> = vec![];
+    let mut column_values: Vec<Region> = vec![];
+    for rule in &decision_table.rules {
+      if !row_values.contains(&rule.input_entries[0].region) {
+        row_values.push(rule.input_entries[0].region);
+      }
+      if !column_values.contains(&rule.input_entries[1].region) {
+        column_values.push(rule.input_entries[1].region);
+      }
+    }
+    (row_values, column_values)
+  }
+
+  /// Inserts an empty row of output entries below the row under the cursor, or an empty column
+  /// of output entries after the column under the cursor, when the cursor is in the header.
+  fn insert_crosstab_value(&mut self, decision_table: &DecisionTable) -> bool {
+    let (row_values, column_values) = Self::crosstab_values(decision_table);
+    if let Some(region) = row_values.iter().find(|region| (region.top..=region.bottom).contains(&self.row)) {
+      // new row is a copy of the first line of the current row, the name of the input is not crossed
+      let inner = region.left - 1;
+      let template = &self.chars[region.top];
+      let last = template.len() - 1;
+      let empty: Vec<char> = template
+        .iter()
+        .enumerate()
+        .map(|(col_index, ch)| {
+          if (col_index > 0 && col_index < inner) || !is_box_drawing_character!(ch) {
+            CH_WS
+          } else {
+            *ch
+          }
+        })
+        .collect();
+      let line: Vec<char> = empty
+        .iter()
+        .enumerate()
+        .map(|(col_index, ch)| {
+          let weight = arms_of(*ch)[0];
+          let left = if col_index <= inner { 0 } else { 1 };
+          let right = if col_index == last || col_index < inner { 0 } else { 1 };
+          junction([weight, right, weight, left]).unwrap_or(CH_WS)
+        })
+        .collect();
+      self.chars.insert(region.bottom + 1, empty);
+      self.chars.insert(region.bottom + 1, line);
+      self.row = region.bottom + 2;
+      return true;
+    }
+    let Some(region) = column_values
+      .iter()
+      .find(|region| self.row < decision_table.body_top && (region.left..=region.right).contains(&self.col))
+    else {
+      return false;
+    };
+    // new column is a copy of the current column, the name of the input is widened at the right side
+    let width = region.width() + 1;
+    for (row_index, row) in self
+      .chars
+      .iter_mut()
+      .enumerate()
+      .take(decision_table.bottom + 1)
+      .skip(decision_table.header_top)
+    {
+      if row_index + 1 < region.top {
+        let fill = if row_index == decision_table.header_top { '─' } else { CH_WS };
+        let at = row.len() - 1;
+        row.splice(at..at, vec![fill; width]);
+        continue;
+      }
+      let weight = arms_of(row[region.right])[1];
+      let border = arms_of(row[region.right + 1]);
+      let up = if row_index + 1 == region.top { 0 } else { border[0] };
+      let mut column = vec![junction([up, weight, border[2], weight]).unwrap_or('│')];
+      column.extend(
+        row[region.left..=region.right]
+          .iter()
+          .map(|ch| if is_box_drawing_character!(ch) { *ch } else { CH_WS }),
+      );
+      row.splice(region.right + 1..region.right + 1, column);
+    }
+    self.col = region.right + 2;
+    self.update_joins();
+    true
+  }
+
+  /// Deletes the row of output entries under the cursor, or the column of output entries under the cursor,
+  /// when the cursor is in the header. The last remaining row or column is never deleted.
+  fn delete_crosstab_value(&mut self, decision_table: &DecisionTable) -> bool {
+    let (row_values, column_values) = Self::crosstab_values(decision_table);
+    if let Some(index) = row_values.iter().position(|region| (region.top..=region.bottom).contains(&self.row)) {
+      if row_values.len() < 2 {
+        return false;
+      }
+      let region = row_values[index];
+      let (first, last) = if index > 0 {
+        (region.top - 1, region.bottom)
+      } else {
+        (region.top, region.bottom + 1)
+      };
+      self.chars.drain(first..=last);
+      self.row = if index > 0 { region.top - 2 } else { region.top };
+    } else if let Some(index) = column_values
+      .iter()
+      .position(|region| self.row < decision_table.body_top && (region.left..=region.right).contains(&self.col))
+    {
+      if column_values.len() < 2 {
+        return false;
+      }
+      // the line on the right side is deleted, the line on the left side when the last column is deleted,
+      // the name of the input is narrowed at the right side
+      let region = column_values[index];
+      let is_last = index + 1 == column_values.len();
+      let (first, last) = if is_last {
+        (region.left - 1, region.right)
+      } else {
+        (region.left, region.right + 1)
+      };
+      for (row_index, row) in self
+        .chars
+        .iter_mut()
+        .enumerate()
+        .take(decision_table.bottom + 1)
+        .skip(decision_table.header_top)
+      {
+        if row_index + 1 < region.top {
+          let at = row.len() - 1;
+          row.drain(at - (last - first + 1)..at);
+        } else {
+          row.drain(first..=last);
+        }
+      }
+      self.col = if is_last { region.left - 2 } else { region.left };
+      self.update_joins();
+    } else {
+      return false;
+    }
+    if !self.is_allowed_position(0, 0) {
+      self.cursor_move_cell_start();
+    }
+    true
+  }
+
   /// Moves the cursor to new position.
   fn cursor_move(&mut self, row_offset: i32, col_offset: i32) {
     if self.is_allowed_position(row_offset, col_offset) {
       let (row, col) = self.adjusted_position(row_offset, col_offset);
-      if (1..self.chars.len() - 1).contains(&row) && (1..self.chars[row].len() - 1).contains(&col) {
+      if (1..self.chars.len().saturating_sub(1)).contains(&row) && (1..self.chars[row].len() - 1).contains(&col) {
         self.row = row;
         self.col = col;
+        self.skip_wide_filler();
       }
     }
   }
 
+  /// Returns `true` when the position at the specified offset from the cursor
+  /// is the second column of a double-width character.
+  fn is_wide_filler(&self, row_offset: i32, col_offset: i32) -> bool {
+    let (r, c) = self.adjusted_position(row_offset, col_offset);
+    self.chars.get(r).and_then(|row| row.get(c)) == Some(&CH_WIDE)
+  }
+
+  /// Places the cursor at the double-width character, when the cursor is in its second column.
+  fn skip_wide_filler(&mut self) {
+    if self.is_wide_filler(0, 0) {
+      self.col -= 1;
+    }
+  }
+
   /// Updated join character between information item name cell and the body of the decision table.
   fn update_joins(&mut self) {
     if self.iih > 0 {
@@ -440,7 +1707,8 @@ impl Plane {
     self.row
   }
 
-  ///
+  /// Checks if there is a whitespace before the next vertical line to the right
+  /// from current cursor position, returns the offset of the checked range.
   fn is_whitespace_before_vert_line(&self) -> (bool, usize) {
     let mut count = 0;
     let mut offset = 0;
@@ -457,30 +1725,61 @@ impl Plane {
     (count > 0, offset)
   }
 
-  ///
+  /// Inserts a single character before the next vertical line to the right
+  /// from the specified position.
   fn insert_column_before_vert_line(&mut self, col_pos: usize) {
     let (skip, take) = self.rows_skip_and_take(Op::Insert);
     for (row_index, row) in self.chars.iter_mut().enumerate().skip(skip).take(take) {
-      if row_index != self.row && col_pos < row.len() - 1 {
-        let mut found_char = CH_WS;
-        let mut found_index = 0;
-        for (col_index, ch) in row[col_pos..].iter().enumerate() {
-          if is_vert_line_crossing!(ch) {
-            found_char = *ch;
-            found_index = col_pos + col_index;
-            break;
-          }
-        }
-        match found_char {
-          '│' | '├' | '║' | '╟' => row.insert(found_index, CH_WS),
-          '┼' | '┬' | '┴' | '┐' | '┘' | '┤' | '╥' | '╨' | '╫' | '╢' => row.insert(found_index, '─'),
-          '╪' | '╬' | '╡' => row.insert(found_index, '═'),
-          _ => {}
-        }
+      if row_index != self.row {
+        insert_before_vert_line(row, col_pos);
       }
     }
   }
 
+  /// Widens the column containing the specified position by a single character in all rows of the table.
+  fn widen_column(&mut self, col_pos: usize) {
+    for row in self.chars.iter_mut().skip(self.iih) {
+      insert_before_vert_line(row, col_pos);
+    }
+    self.update_joins();
+  }
+
+  /// Inserts an empty line below the specified row, vertical lines crossing the row are extended.
+  fn grow_row(&mut self, row_index: usize) {
+    let line = self.chars[row_index]
+      .iter()
+      .map(|ch| {
+        let down = arms_of(*ch)[2];
+        junction([down, 0, down, 0]).unwrap_or(CH_WS)
+      })
+      .collect();
+    self.chars.insert(row_index + 1, line);
+  }
+
+  /// Replaces the content of the cell with lines of text, preceded with a single space.
+  /// The column is widened and the row grows when the text does not fit in the cell.
+  /// Returns the region of the cell after the change.
+  fn write_cell(&mut self, mut region: Region, lines: &[String]) -> Region {
+    let width = lines.iter().map(|line| text_width(line)).max().unwrap_or(0) + 2;
+    while region.width() < width {
+      self.widen_column(region.right);
+      region.right += 1;
+    }
+    while region.bottom - region.top + 1 < lines.len() {
+      self.grow_row(region.bottom);
+      region.bottom += 1;
+    }
+    for row in &mut self.chars[region.top..=region.bottom] {
+      row[region.left..=region.right].fill(CH_WS);
+    }
+    for (offset, line) in lines.iter().enumerate() {
+      for (col_offset, ch) in self.clusters.columns(line).into_iter().enumerate() {
+        self.chars[region.top + offset][region.left + 1 + col_offset] = ch;
+      }
+    }
+    region
+  }
+
   /// Returns `true` if there is a whitespace is before the next vertical line
   /// to the right from the specified position in each checked row.
   fn is_whitespace_column_before_vert_line(&self, pos: usize, op: Op) -> bool {
@@ -557,7 +1856,7 @@ impl Plane {
   /// Returns `true` when the cursor position is allowed according to horizontal and vertical offset.
   fn is_allowed_position(&self, row_offset: i32, col_offset: i32) -> bool {
     let (r, c) = self.adjusted_position(row_offset, col_offset);
-    if r > 0 && r < self.chars.len() - 1 && c > 0 && c < self.chars[r].len() - 1 {
+    if r > 0 && r < self.chars.len().saturating_sub(1) && c > 0 && c < self.chars[r].len() - 1 {
       !is_box_drawing_character!(self.chars[r][c])
     } else {
       false
@@ -647,6 +1946,23 @@ impl Plane {
   }
 }
 
+/// Inserts a single character before the next vertical line to the right from the specified position,
+/// horizontal lines crossed by the vertical line are extended.
+fn insert_before_vert_line(row: &mut Vec<char>, col_pos: usize) {
+  if col_pos >= row.len() - 1 {
+    return;
+  }
+  let Some(found_index) = row[col_pos..].iter().position(|ch| is_vert_line_crossing!(ch)).map(|index| col_pos + index) else {
+    return;
+  };
+  match row[found_index] {
+    '│' | '├' | '║' | '╟' => row.insert(found_index, CH_WS),
+    '┼' | '┬' | '┴' | '┐' | '┘' | '┤' | '╥' | '╨' | '╫' | '╢' => row.insert(found_index, '─'),
+    '╪' | '╬' | '╡' | '╤' | '╧' => row.insert(found_index, '═'),
+    _ => {}
+  }
+}
+
 /// Calculates the height of the information item cell at the beginning of the decision table.
 fn information_item_height(rows: &[Vec<char>]) -> usize {
   for (row_index, row) in rows.iter().enumerate() {
@@ -663,3 +1979,43 @@ fn information_item_height(rows: &[Vec<char>]) -> usize {
   }
   0
 }
+
+/// Position of the cursor in the structure of the decision table.
+enum Position {
+  /// Header of the clause with the specified index.
+  Header(usize),
+  /// Rule with the specified index, in the entry with the specified index or in the rule number.
+  Rule(usize, Option<usize>),
+}
+
+/// Returns regions of header cells of the clause, the first one contains the name
+/// (the hit policy for outputs without names), the second one contains allowed values.
+fn headers(decision_table: &DecisionTable, clause: &Clause) -> Vec<Region> {
+  std::iter::once(clause.name_cell.unwrap_or(&decision_table.hit_policy_cell))
+    .chain(clause.allowed_values)
+    .map(|cell| cell.region)
+    .collect()
+}
+
+/// Returns the region of the entry with the specified index of the rule.
+fn entry_region(rule: &DecisionRule, index: usize) -> Option<Region> {
+  rule.entries().nth(index).map(|entry| entry.region)
+}
+
+/// Returns `true` when any entry of the decision table is shared by several rules.
+fn has_merged_cells(decision_table: &DecisionTable) -> bool {
+  decision_table.rules.iter().any(|rule| rule.entries().any(|entry| !rule.owns(entry)))
+}
+
+/// Returns the region of the entry with the specified index of the rule, or the region of the rule number.
+fn rule_cell(rule: &DecisionRule, entry: Option<usize>) -> Option<Region> {
+  match entry {
+    Some(entry) => entry_region(rule, entry),
+    None => Some(rule.number.region),
+  }
+}
+
+/// Returns `true` when names are equal, ignoring case and whitespace.
+fn same_name(a: &str, b: &str) -> bool {
+  normalized(a).to_lowercase() == normalized(b).to_lowercase()
+}
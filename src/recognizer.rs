@@ -0,0 +1,490 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Recognizer of the decision table structure drawn with box-drawing characters.
+
+use crate::box_drawing::is_box_drawing_character;
+use crate::errors::*;
+use crate::model::*;
+use crate::plane::Plane;
+
+/// Kinds of columns in the decision table.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ColumnKind {
+  Input,
+  Output,
+  Annotation,
+}
+
+/// Cells (regions bounded by box-drawing characters) found in rows of characters.
+pub struct Regions {
+  /// Index of the region for each character, `None` for box-drawing characters and characters outside cells.
+  map: Vec<Vec<Option<usize>>>,
+  /// All regions found.
+  regions: Vec<Region>,
+}
+
+impl Regions {
+  /// Finds all regions fully enclosed with box-drawing characters.
+  pub fn new(rows: &[Vec<char>]) -> Self {
+    let mut map: Vec<Vec<Option<usize>>> = rows.iter().map(|row| vec![None; row.len()]).collect();
+    let mut visited: Vec<Vec<bool>> = rows.iter().map(|row| vec![false; row.len()]).collect();
+    let mut regions = vec![];
+    for row_index in 0..rows.len() {
+      for col_index in 0..rows[row_index].len() {
+        if visited[row_index][col_index] || is_box_drawing_character!(rows[row_index][col_index]) {
+          continue;
+        }
+        let mut enclosed = true;
+        let mut positions = vec![];
+        let mut stack = vec![(row_index, col_index)];
+        visited[row_index][col_index] = true;
+        while let Some((r, c)) = stack.pop() {
+          positions.push((r, c));
+          let mut neighbours = vec![];
+          if r > 0 {
+            neighbours.push((r - 1, c));
+          } else {
+            enclosed = false;
+          }
+          if r + 1 < rows.len() {
+            neighbours.push((r + 1, c));
+          } else {
+            enclosed = false;
+          }
+          if c > 0 {
+            neighbours.push((r, c - 1));
+          } else {
+            enclosed = false;
+          }
+          neighbours.push((r, c + 1));
+          for (nr, nc) in neighbours {
+            if nc >= rows[nr].len() {
+              enclosed = false;
+            } else if !visited[nr][nc] && !is_box_drawing_character!(rows[nr][nc]) {
+              visited[nr][nc] = true;
+              stack.push((nr, nc));
+            }
+          }
+        }
+        if enclosed {
+          let region = Region {
+            top: positions.iter().map(|(r, _)| *r).min().unwrap_or(row_index),
+            left: positions.iter().map(|(_, c)| *c).min().unwrap_or(col_index),
+            bottom: positions.iter().map(|(r, _)| *r).max().unwrap_or(row_index),
+            right: positions.iter().map(|(_, c)| *c).max().unwrap_or(col_index),
+          };
+          for (r, c) in positions {
+            map[r][c] = Some(regions.len());
+          }
+          regions.push(region);
+        }
+      }
+    }
+    Self { map, regions }
+  }
+
+  /// Returns the region containing the specified position.
+  pub fn region_at(&self, row: usize, col: usize) -> Option<Region> {
+    self
+      .map
+      .get(row)
+      .and_then(|r| r.get(col))
+      .and_then(|index| index.map(|index| self.regions[index]))
+  }
+}
+
+/// Creates a cell from the text placed in the specified region.
+pub fn cell(plane: &Plane, region: Region) -> Cell {
+  let mut lines: Vec<String> = (region.top..=region.bottom)
+    .map(|r| {
+      let row = &plane.chars[r];
+      let right = region.right.min(row.len().saturating_sub(1));
+      if region.left <= right {
+        plane.clusters.text(&row[region.left..=right]).trim().to_string()
+      } else {
+        String::new()
+      }
+    })
+    .collect();
+  while lines.last().is_some_and(|line| line.is_empty()) {
+    lines.pop();
+  }
+  Cell { lines, region }
+}
+
+/// Recognizes the decision table placed in the plane.
+pub fn recognize(plane: &Plane) -> Result<DecisionTable> {
+  recognize_table(plane, true)
+}
+
+/// Recognizes the structure of the decision table, invalid hit policy is accepted
+/// and replaced with [HitPolicy::Unique], so the table can be checked by the caller.
+pub fn recognize_structure(plane: &Plane) -> Result<DecisionTable> {
+  recognize_table(plane, false)
+}
+
+/// Recognizes the decision table, when `strict` is `true` the hit policy must be valid.
+fn recognize_table(plane: &Plane, strict: bool) -> Result<DecisionTable> {
+  let rows = &plane.chars;
+  let header_top = (0..rows.len())
+    .find(|r| starts_with(rows, *r, &['┌', '├']) && rows[*r].contains(&'╥'))
+    .ok_or_else(|| err_recognize("missing top border of the decision table body"))?;
+  let regions = Regions::new(rows);
+  // information item name
+  let information_item_name = if header_top > 0 {
+    Some(cell_at(plane, &regions, 1, 1, "information item name")?)
+  } else {
+    None
+  };
+  // hit policy, crosstab tables have the output label in this place and the hit policy is always unique
+  let hit_policy_cell = cell_at(plane, &regions, header_top + 1, 1, "hit policy")?;
+  let closed_with_double_line = rows[header_top + 1].get(hit_policy_cell.region.right + 1) == Some(&'║');
+  let crosstab = closed_with_double_line && starts_with(rows, hit_policy_cell.region.bottom + 1, &['╞']);
+  let hit_policy = match HitPolicy::try_from(hit_policy_cell.text().as_str()) {
+    _ if crosstab => HitPolicy::Unique,
+    Ok(hit_policy) => hit_policy,
+    Err(reason) if strict => return Err(reason),
+    Err(_) => HitPolicy::Unique,
+  };
+  let mut decision_table = DecisionTable {
+    orientation: Orientation::RuleAsRow,
+    information_item_name,
+    hit_policy,
+    hit_policy_cell,
+    output_label: None,
+    input_clauses: vec![],
+    output_clauses: vec![],
+    annotation_clauses: vec![],
+    rules: vec![],
+    header_top,
+    body_top: 0,
+    bottom: 0,
+  };
+  // when the hit policy cell is closed with double line, rules are placed in columns
+  // or the table is a crosstab, when the double line below the cell follows it directly
+  if crosstab {
+    decision_table.orientation = Orientation::CrossTable;
+    recognize_crosstab(plane, &regions, &mut decision_table)?;
+  } else if closed_with_double_line {
+    decision_table.orientation = Orientation::RuleAsColumn;
+    recognize_rules_as_columns(plane, &regions, &mut decision_table)?;
+  } else {
+    recognize_rules_as_rows(plane, &regions, &mut decision_table)?;
+  }
+  Ok(decision_table)
+}
+
+/// Returns `true` when the specified row starts with one of the specified characters.
+fn starts_with(rows: &[Vec<char>], row_index: usize, chars: &[char]) -> bool {
+  rows[row_index].first().is_some_and(|ch| chars.contains(ch))
+}
+
+/// Returns the cell containing the specified position.
+fn cell_at(plane: &Plane, regions: &Regions, row: usize, col: usize, what: &str) -> Result<Cell> {
+  regions
+    .region_at(row, col)
+    .map(|region| cell(plane, region))
+    .ok_or_else(|| err_recognize(&format!("missing {} at {}:{}", what, col, row)))
+}
+
+/// Returns the kind of clauses following the double line separating clauses of the specified kind.
+fn next_kind(kind: ColumnKind) -> ColumnKind {
+  match kind {
+    ColumnKind::Input => ColumnKind::Output,
+    _ => ColumnKind::Annotation,
+  }
+}
+
+/// Recognizes clauses and rules of the decision table having rules placed in rows.
+fn recognize_rules_as_rows(plane: &Plane, regions: &Regions, decision_table: &mut DecisionTable) -> Result<()> {
+  let rows = &plane.chars;
+  let header_top = decision_table.header_top;
+  let body_top = (header_top + 1..rows.len())
+    .find(|r| starts_with(rows, *r, &['╞']))
+    .ok_or_else(|| err_recognize("missing line separating the header from rules"))?;
+  let bottom = (body_top + 1..rows.len())
+    .find(|r| starts_with(rows, *r, &['└']))
+    .ok_or_else(|| err_recognize("missing bottom border of the decision table"))?;
+  // recognize columns using the line separating the header from rules
+  let boundaries: Vec<usize> = rows[body_top]
+    .iter()
+    .enumerate()
+    .filter_map(|(index, ch)| if matches!(ch, '╞' | '╪' | '╬' | '╡') { Some(index) } else { None })
+    .collect();
+  if boundaries.len() < 3 {
+    return Err(err_recognize("too few columns"));
+  }
+  let columns: Vec<(usize, usize)> = boundaries.windows(2).map(|w| (w[0] + 1, w[1] - 1)).collect();
+  let mut kinds = vec![];
+  let mut kind = ColumnKind::Input;
+  for (left, _) in &columns[1..] {
+    if rows[body_top][left - 1] == '╬' {
+      kind = next_kind(kind);
+    }
+    kinds.push(kind);
+  }
+  // header cells placed in each column
+  let header_regions = |left: usize| -> Vec<Region> {
+    let mut found: Vec<Region> = vec![];
+    for r in header_top + 1..body_top {
+      if let Some(region) = regions.region_at(r, left) {
+        if region.left == left && !found.contains(&region) {
+          found.push(region);
+        }
+      }
+    }
+    found
+  };
+  let output_count = kinds.iter().filter(|kind| **kind == ColumnKind::Output).count();
+  for ((left, right), kind) in columns[1..].iter().zip(kinds.iter()) {
+    let mut found = header_regions(*left);
+    if let Some(first) = found.first() {
+      if first.right > *right {
+        // region spanning several output columns contains the output label
+        decision_table.output_label = Some(cell(plane, *first));
+        found.remove(0);
+      }
+    }
+    let mut cells = found.iter().filter(|region| region.right == *right).map(|region| cell(plane, *region));
+    let first = cells.next().ok_or_else(|| err_recognize(&format!("missing header cell in column {}", left)))?;
+    let second = cells.next();
+    push_clause(decision_table, *kind, output_count, first, second);
+  }
+  // decision rules
+  let (first_left, _) = columns[0];
+  let (_, last_right) = columns[columns.len() - 1];
+  let mut top = body_top + 1;
+  for r in body_top + 1..=bottom {
+    if starts_with(rows, r, &['├', '╞', '└']) {
+      if r > top {
+        let number = cell_at(plane, regions, top, first_left, "rule number")?;
+        let mut entries = vec![];
+        for (left, _) in &columns[1..] {
+          entries.push(cell_at(plane, regions, top, *left, "rule entry")?);
+        }
+        let region = Region {
+          top,
+          left: first_left,
+          bottom: r - 1,
+          right: last_right,
+        };
+        push_rule(decision_table, &kinds, number, entries, region);
+      }
+      top = r + 1;
+    }
+  }
+  decision_table.body_top = body_top;
+  decision_table.bottom = bottom;
+  Ok(())
+}
+
+/// Recognizes clauses and rules of the decision table having rules placed in columns.
+fn recognize_rules_as_columns(plane: &Plane, regions: &Regions, decision_table: &mut DecisionTable) -> Result<()> {
+  let rows = &plane.chars;
+  let header_top = decision_table.header_top;
+  let numbers_row = header_top + 1;
+  let body_top = decision_table.hit_policy_cell.region.bottom + 1;
+  let bottom = (body_top + 1..rows.len())
+    .find(|r| starts_with(rows, *r, &['└']))
+    .ok_or_else(|| err_recognize("missing bottom border of the decision table"))?;
+  // recognize rule columns using the line containing rule numbers
+  let separator = decision_table.hit_policy_cell.region.right + 1;
+  let boundaries: Vec<usize> = rows[numbers_row]
+    .iter()
+    .enumerate()
+    .skip(separator)
+    .filter_map(|(index, ch)| if matches!(ch, '│' | '║') { Some(index) } else { None })
+    .collect();
+  if boundaries.len() < 2 {
+    return Err(err_recognize("too few columns"));
+  }
+  let columns: Vec<(usize, usize)> = boundaries.windows(2).map(|w| (w[0] + 1, w[1] - 1)).collect();
+  // recognize clauses placed in rows
+  let mut clause_rows = vec![];
+  let mut kind = ColumnKind::Input;
+  let mut top = body_top + 1;
+  for r in body_top + 1..=bottom {
+    if starts_with(rows, r, &['├', '╞', '└']) {
+      if r > top {
+        clause_rows.push((top, kind));
+      }
+      if starts_with(rows, r, &['╞']) {
+        kind = next_kind(kind);
+      }
+      top = r + 1;
+    }
+  }
+  let output_count = clause_rows.iter().filter(|(_, kind)| *kind == ColumnKind::Output).count();
+  let mut kinds = vec![];
+  let mut entries: Vec<Vec<Cell>> = vec![vec![]; columns.len()];
+  for (top, kind) in &clause_rows {
+    let name = cell_at(plane, regions, *top, 1, "clause name")?;
+    let allowed_values = if name.region.right + 2 < separator {
+      Some(cell_at(plane, regions, *top, name.region.right + 2, "allowed values")?)
+    } else {
+      None
+    };
+    push_clause(decision_table, *kind, output_count, name, allowed_values);
+    for (rule_entries, (left, _)) in entries.iter_mut().zip(columns.iter()) {
+      rule_entries.push(cell_at(plane, regions, *top, *left, "rule entry")?);
+    }
+    kinds.push(*kind);
+  }
+  // decision rules
+  for ((left, right), rule_entries) in columns.iter().zip(entries) {
+    let number = cell_at(plane, regions, numbers_row, *left, "rule number")?;
+    let region = Region {
+      top: numbers_row,
+      left: *left,
+      bottom: bottom - 1,
+      right: *right,
+    };
+    push_rule(decision_table, &kinds, number, rule_entries, region);
+  }
+  decision_table.body_top = body_top;
+  decision_table.bottom = bottom;
+  Ok(())
+}
+
+/// Recognizes the crosstab decision table, having a single input placed in rows, a single input
+/// placed in columns and output entries in the grid between them. Each cell of the grid is a rule,
+/// numbered row by row.
+fn recognize_crosstab(plane: &Plane, regions: &Regions, decision_table: &mut DecisionTable) -> Result<()> {
+  let rows = &plane.chars;
+  let header_top = decision_table.header_top;
+  let body_top = decision_table.hit_policy_cell.region.bottom + 1;
+  let separator = decision_table.hit_policy_cell.region.right + 1;
+  let bottom = (body_top + 1..rows.len())
+    .find(|r| starts_with(rows, *r, &['└']))
+    .ok_or_else(|| err_recognize("missing bottom border of the decision table"))?;
+  let unsupported = || err_recognize("crosstab with more than one input on each axis is not supported");
+  // input placed in columns, its name is above the values
+  let column_name = cell_at(plane, regions, header_top + 1, separator + 1, "column input name")?;
+  if column_name.region.bottom + 2 >= body_top {
+    return Err(err_recognize("missing values of the column input"));
+  }
+  let mut column_values: Vec<Cell> = vec![];
+  for col in separator + 1..rows[body_top - 1].len() {
+    if let Some(region) = regions.region_at(body_top - 1, col) {
+      if region.left == col {
+        if region.top != column_name.region.bottom + 2 {
+          return Err(unsupported());
+        }
+        column_values.push(cell(plane, region));
+      }
+    }
+  }
+  // input placed in rows, its name is on the left side of the values
+  let row_name = cell_at(plane, regions, body_top + 1, 1, "row input name")?;
+  let values_left = row_name.region.right + 2;
+  let mut row_values: Vec<Cell> = vec![];
+  for r in body_top + 1..bottom {
+    if let Some(region) = regions.region_at(r, values_left) {
+      if region.top == r {
+        if region.right + 1 != separator {
+          return Err(unsupported());
+        }
+        row_values.push(cell(plane, region));
+      }
+    }
+  }
+  if column_values.is_empty() || row_values.is_empty() {
+    return Err(err_recognize("missing values of the row input"));
+  }
+  push_clause(decision_table, ColumnKind::Input, 1, row_name, None);
+  push_clause(decision_table, ColumnKind::Input, 1, column_name, None);
+  decision_table.output_label = Some(decision_table.hit_policy_cell.clone());
+  decision_table.output_clauses.push(OutputClause {
+    name: None,
+    allowed_values: None,
+  });
+  // each cell of the grid is a single rule
+  let kinds = [ColumnKind::Input, ColumnKind::Input, ColumnKind::Output];
+  for row_value in &row_values {
+    for column_value in &column_values {
+      let entry = cell_at(plane, regions, row_value.region.top, column_value.region.left, "output entry")?;
+      let number = Cell {
+        lines: vec![(decision_table.rules.len() + 1).to_string()],
+        region: entry.region,
+      };
+      let region = entry.region;
+      push_rule(decision_table, &kinds, number, vec![row_value.clone(), column_value.clone(), entry], region);
+    }
+  }
+  decision_table.body_top = body_top;
+  decision_table.bottom = bottom;
+  Ok(())
+}
+
+/// Adds a clause of the specified kind to the decision table, the name of the single output is its label.
+fn push_clause(decision_table: &mut DecisionTable, kind: ColumnKind, output_count: usize, name: Cell, allowed_values: Option<Cell>) {
+  match kind {
+    ColumnKind::Input => decision_table.input_clauses.push(InputClause {
+      input_expression: name,
+      allowed_values,
+    }),
+    ColumnKind::Output => {
+      if output_count == 1 && decision_table.output_label.is_none() {
+        decision_table.output_label = Some(name);
+        decision_table.output_clauses.push(OutputClause { name: None, allowed_values });
+      } else {
+        decision_table.output_clauses.push(OutputClause {
+          name: Some(name),
+          allowed_values,
+        });
+      }
+    }
+    ColumnKind::Annotation => decision_table.annotation_clauses.push(AnnotationClause { name }),
+  }
+}
+
+/// Adds a rule to the decision table, entries are split into input, output and annotation entries according to clause kinds.
+fn push_rule(decision_table: &mut DecisionTable, kinds: &[ColumnKind], number: Cell, entries: Vec<Cell>, region: Region) {
+  let mut input_entries = vec![];
+  let mut output_entries = vec![];
+  let mut annotation_entries = vec![];
+  for (entry, kind) in entries.into_iter().zip(kinds.iter()) {
+    match kind {
+      ColumnKind::Input => input_entries.push(entry),
+      ColumnKind::Output => output_entries.push(entry),
+      ColumnKind::Annotation => annotation_entries.push(entry),
+    }
+  }
+  decision_table.rules.push(DecisionRule {
+    number,
+    input_entries,
+    output_entries,
+    annotation_entries,
+    region,
+  });
+}
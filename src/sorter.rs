@@ -0,0 +1,172 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Ordering of rules by entries of chosen clauses.
+
+use crate::errors::*;
+use crate::feel::{split_list, UnaryTest, UnaryTests, Value};
+use std::cmp::Ordering;
+
+/// Clause used for ordering rules, parsed from text like `Age desc`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+  /// Name of the clause.
+  pub name: String,
+  /// Flag indicating if rules are ordered starting from the greatest entry.
+  pub descending: bool,
+}
+
+impl SortKey {
+  /// Parses sort keys separated with commas, each name may be followed by `asc` or `desc`.
+  pub fn parse_list(text: &str) -> Result<Vec<SortKey>> {
+    let mut keys = vec![];
+    for item in text.split(',') {
+      let mut words: Vec<&str> = item.split_whitespace().collect();
+      let descending = match words.last().map(|word| word.to_lowercase()).as_deref() {
+        Some("desc") => {
+          words.pop();
+          true
+        }
+        Some("asc") => {
+          words.pop();
+          false
+        }
+        _ => false,
+      };
+      if words.is_empty() {
+        return Err(err_sort(text, "expected clause name"));
+      }
+      keys.push(SortKey {
+        name: words.join(" "),
+        descending,
+      });
+    }
+    Ok(keys)
+  }
+
+  /// Compares two entries of the clause, irrelevant and empty entries are always placed last.
+  /// When allowed values are specified, entries are ordered like allowed values.
+  pub fn compare(&self, a: &str, b: &str, allowed_values: &[String]) -> Ordering {
+    let (a, b) = (a.trim(), b.trim());
+    let is_irrelevant = |text: &str| text.is_empty() || text == "-";
+    match (is_irrelevant(a), is_irrelevant(b)) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      _ => {
+        let ordering = compare_entries(a, b, allowed_values);
+        if self.descending {
+          ordering.reverse()
+        } else {
+          ordering
+        }
+      }
+    }
+  }
+}
+
+/// Bound of the range of values matched by an entry.
+enum Bound {
+  /// Below all values.
+  Below,
+  /// At the specified value.
+  At(Value),
+  /// Above all values.
+  Above,
+}
+
+/// Compares two entries by the position of their first item in allowed values,
+/// then by the range of values matched by the first unary test, finally by text.
+fn compare_entries(a: &str, b: &str, allowed_values: &[String]) -> Ordering {
+  if !allowed_values.is_empty() {
+    let position = |text: &str| {
+      let first = split_list(text).into_iter().next().unwrap_or_default();
+      allowed_values.iter().position(|value| *value == first).unwrap_or(allowed_values.len())
+    };
+    let ordering = position(a).cmp(&position(b));
+    if ordering.is_ne() {
+      return ordering;
+    }
+  }
+  match (bounds(a), bounds(b)) {
+    (Some((a_start, a_end)), Some((b_start, b_end))) => compare_bounds(&a_start, &b_start)
+      .then_with(|| compare_bounds(&a_end, &b_end))
+      .then_with(|| a.cmp(b)),
+    (Some(_), None) => Ordering::Less,
+    (None, Some(_)) => Ordering::Greater,
+    (None, None) => a.cmp(b),
+  }
+}
+
+/// Returns the start and the end of the range of values matched by the first unary test in the entry.
+/// Negated tests and entries that are not valid FEEL have no range.
+fn bounds(text: &str) -> Option<(Bound, Bound)> {
+  let UnaryTests::Positive(tests) = UnaryTests::parse(text).ok()? else {
+    return None;
+  };
+  match tests.into_iter().next()? {
+    UnaryTest::Equal(value) => Some((Bound::At(value.clone()), Bound::At(value))),
+    UnaryTest::NotEqual(_) => None,
+    UnaryTest::Less(end) | UnaryTest::LessOrEqual(end) => Some((Bound::Below, Bound::At(end))),
+    UnaryTest::Greater(start) | UnaryTest::GreaterOrEqual(start) => Some((Bound::At(start), Bound::Above)),
+    UnaryTest::Interval(start, _, end, _) => Some((Bound::At(start), Bound::At(end))),
+  }
+}
+
+/// Compares bounds, values of different types are ordered by type.
+fn compare_bounds(a: &Bound, b: &Bound) -> Ordering {
+  match (a, b) {
+    (Bound::At(a), Bound::At(b)) => a.compare(b).unwrap_or_else(|| type_rank(a).cmp(&type_rank(b))),
+    _ => bound_rank(a).cmp(&bound_rank(b)),
+  }
+}
+
+/// Returns the rank of the bound, used when bounds are not both values.
+fn bound_rank(bound: &Bound) -> usize {
+  match bound {
+    Bound::Below => 0,
+    Bound::At(_) => 1,
+    Bound::Above => 2,
+  }
+}
+
+/// Returns the rank of the type of the value, used when values are not comparable.
+fn type_rank(value: &Value) -> usize {
+  match value {
+    Value::Null => 0,
+    Value::Boolean(_) => 1,
+    Value::Number(_) => 2,
+    Value::String(_) => 3,
+    Value::List(_) => 4,
+    Value::Context(_) => 5,
+  }
+}
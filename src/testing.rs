@@ -0,0 +1,272 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Batch testing of decision tables with test cases loaded from CSV or YAML files.
+//!
+//! Each test case is a set of named values. Names are matched with input expressions
+//! and output names of the tested decision table. Values are FEEL literals,
+//! text that is not a valid literal is taken as a string.
+//!
+//! CSV file contains a header line with names followed by one line per test case:
+//! ```text
+//! Customer type,Order size,Discount,Priority
+//! "Business",9,0.10,"Normal"
+//! ```
+//! Fields are quoted like in RFC 4180: quotes are removed from quoted fields, doubled quotes
+//! stand for a single quote and quoted fields may contain commas and new lines.
+//! Commas inside brackets of lists and ranges in unquoted fields do not separate fields.
+//!
+//! YAML file contains a list of mappings, one mapping per test case:
+//! ```text
+//! - Customer type: "Business"
+//!   Order size: 9
+//!   Discount: 0.10
+//!   Priority: "Normal"
+//! ```
+
+use crate::errors::*;
+use crate::evaluator::evaluate;
+use crate::feel::*;
+use crate::model::DecisionTable;
+
+/// Single test case, a list of names with values.
+pub type TestCase = Vec<(String, String)>;
+
+/// Parses test cases from the content of a file, the format is chosen based on the file extension.
+pub fn parse_test_cases(file_name: &str, content: &str) -> Result<Vec<TestCase>> {
+  if file_name.ends_with(".yaml") || file_name.ends_with(".yml") {
+    parse_yaml(content)
+  } else {
+    parse_csv(content)
+  }
+}
+
+/// Parses test cases written in CSV format.
+pub fn parse_csv(content: &str) -> Result<Vec<TestCase>> {
+  let mut records = parse_csv_records(content)?.into_iter();
+  let names = records.next().ok_or_else(|| err_invalid_test_cases("missing header line"))?;
+  let mut test_cases = vec![];
+  for (line_index, values) in records.enumerate() {
+    if values.len() != names.len() {
+      return Err(err_invalid_test_cases(&format!(
+        "expected {} values in test case {}, found {}",
+        names.len(),
+        line_index + 1,
+        values.len()
+      )));
+    }
+    test_cases.push(names.iter().cloned().zip(values).collect());
+  }
+  Ok(test_cases)
+}
+
+/// Splits the content of a CSV file into records of fields, blank lines are skipped.
+fn parse_csv_records(content: &str) -> Result<Vec<Vec<String>>> {
+  let mut records = vec![];
+  let mut record = vec![];
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut in_quotes = false;
+  let mut depth = 0_usize;
+  let mut chars = content.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      '"' if in_quotes && chars.peek() == Some(&'"') => {
+        chars.next();
+        field.push('"');
+      }
+      '"' if in_quotes => in_quotes = false,
+      '"' if field.trim().is_empty() && !quoted => {
+        field.clear();
+        quoted = true;
+        in_quotes = true;
+      }
+      _ if in_quotes => field.push(ch),
+      '(' | '[' => {
+        depth += 1;
+        field.push(ch);
+      }
+      ')' | ']' => {
+        depth = depth.saturating_sub(1);
+        field.push(ch);
+      }
+      ',' if depth == 0 => {
+        record.push(csv_field(&field, quoted));
+        field.clear();
+        quoted = false;
+      }
+      '\n' => {
+        if quoted || !field.trim().is_empty() || !record.is_empty() {
+          record.push(csv_field(&field, quoted));
+          records.push(record);
+        }
+        record = vec![];
+        field.clear();
+        quoted = false;
+        depth = 0;
+      }
+      _ => field.push(ch),
+    }
+  }
+  if in_quotes {
+    return Err(err_invalid_test_cases("missing closing quote"));
+  }
+  if quoted || !field.trim().is_empty() || !record.is_empty() {
+    record.push(csv_field(&field, quoted));
+    records.push(record);
+  }
+  Ok(records)
+}
+
+/// Returns the value of the CSV field, whitespace around unquoted fields is removed.
+fn csv_field(field: &str, quoted: bool) -> String {
+  if quoted {
+    field.trim_end_matches('\r').to_string()
+  } else {
+    field.trim().to_string()
+  }
+}
+
+/// Parses test cases written in YAML format (a list of flat mappings).
+pub fn parse_yaml(content: &str) -> Result<Vec<TestCase>> {
+  let mut test_cases: Vec<TestCase> = vec![];
+  for (line_index, line) in content.lines().enumerate() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+      continue;
+    }
+    let entry = if let Some(rest) = trimmed.strip_prefix('-') {
+      test_cases.push(vec![]);
+      rest.trim()
+    } else {
+      trimmed
+    };
+    if entry.is_empty() {
+      continue;
+    }
+    let (name, value) = entry
+      .split_once(':')
+      .ok_or_else(|| err_invalid_test_cases(&format!("expected 'name: value' in line {}", line_index + 1)))?;
+    let name = name.trim().trim_matches(|ch| ch == '"' || ch == '\'');
+    let value = match value.trim() {
+      "~" => "null",
+      other => other,
+    };
+    test_cases
+      .last_mut()
+      .ok_or_else(|| err_invalid_test_cases(&format!("expected '-' before the first test case in line {}", line_index + 1)))?
+      .push((name.to_string(), value.to_string()));
+  }
+  Ok(test_cases)
+}
+
+/// Parses the value of a test case, text that is not a valid FEEL literal is taken as a string.
+fn parse_test_value(text: &str) -> Value {
+  if let Some(inner) = text.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+    return Value::String(inner.to_string());
+  }
+  parse_value(text).unwrap_or_else(|_| if text.is_empty() { Value::Null } else { Value::String(text.to_string()) })
+}
+
+/// Runs all test cases against the decision table.
+/// Returns lines of the report and `true` when all test cases passed.
+pub fn run_test_cases(decision_table: &DecisionTable, test_cases: &[TestCase]) -> Result<(Vec<String>, bool)> {
+  let input_names: Vec<String> = decision_table
+    .input_clauses
+    .iter()
+    .map(|input_clause| input_clause.input_expression.text())
+    .collect();
+  let output_names = decision_table.output_names();
+  let mut report = vec![];
+  let mut passed = 0;
+  let mut failed = 0;
+  let mut invalid_rules = vec![];
+  for (case_index, test_case) in test_cases.iter().enumerate() {
+    let mut input_values = vec![Value::Null; input_names.len()];
+    let mut expected = vec![];
+    for (name, text) in test_case {
+      if let Some(index) = input_names.iter().position(|input_name| input_name == name) {
+        input_values[index] = parse_test_value(text);
+      } else if let Some(index) = output_names.iter().position(|output_name| output_name == name) {
+        if !text.is_empty() {
+          expected.push((index, name, parse_test_value(text)));
+        }
+      } else {
+        return Err(err_invalid_test_cases(&format!("unknown name '{}' in test case {}", name, case_index + 1)));
+      }
+    }
+    let mut failures = vec![];
+    // failed evaluation is reported like a failed test case, remaining test cases are run
+    let result = evaluate(decision_table, &input_values).and_then(|evaluation| {
+      // rules that could not be evaluated are reported once, not with each test case
+      for invalid_rule in evaluation.invalid_rules {
+        if !invalid_rules.contains(&invalid_rule) {
+          invalid_rules.push(invalid_rule);
+        }
+      }
+      evaluation.result
+    });
+    match result {
+      Ok(actual) => {
+        for (index, name, expected_value) in expected {
+          let actual_value = match &actual {
+            Value::Context(entries) if output_names.len() > 1 => entries[index].1.clone(),
+            other => other.clone(),
+          };
+          if actual_value != expected_value {
+            failures.push(format!("  {}: expected {}, actual {}", name, expected_value, actual_value));
+          }
+        }
+      }
+      Err(reason) => failures.push(format!("  {}", reason)),
+    }
+    if failures.is_empty() {
+      passed += 1;
+      report.push(format!("test case {} ... ok", case_index + 1));
+    } else {
+      failed += 1;
+      report.push(format!("test case {} ... FAILED", case_index + 1));
+      report.append(&mut failures);
+    }
+  }
+  report.push(String::new());
+  for (rule_index, reason) in invalid_rules {
+    report.push(format!("rule {} not evaluated: {}", decision_table.rules[rule_index].number.text(), reason));
+  }
+  report.push(format!(
+    "test result: {}. {} passed; {} failed",
+    if failed == 0 { "ok" } else { "FAILED" },
+    passed,
+    failed
+  ));
+  Ok((report, failed == 0))
+}
@@ -0,0 +1,214 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::model::Region;
+use crate::plane::{Plane, Register, SelectionMode};
+
+/// Utility function for creating a register containing cells.
+fn cells(rows: &[&[&[&str]]]) -> Register {
+  Register::Cells(
+    rows
+      .iter()
+      .map(|cells| cells.iter().map(|lines| lines.iter().map(|line| line.to_string()).collect()).collect())
+      .collect(),
+  )
+}
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(3, 7));
+  plane.begin_selection(SelectionMode::Characters);
+  repeat!(3, plane, cursor_move_right);
+  assert_eq!(
+    vec![Region {
+      top: 3,
+      left: 7,
+      bottom: 3,
+      right: 10
+    }],
+    plane.selected_regions()
+  );
+  assert_eq!(Some(Register::Text(vec![">=18".to_string()])), plane.copy());
+  assert_eq!(None, plane.selection_mode());
+  assert!(plane.cursor_move_to(9, 9));
+  plane.delete_char();
+  assert!(plane.paste(&Register::Text(vec![">=18".to_string()])));
+  eq_cursor(9, 13, plane);
+  let expected = r#"
+┌────┬────────┬───────╥────────┐
+│ C+ │  Age   │ Score ║ Points │
+╞════╪════════╪═══════╬════════╡
+│ 1  │ >=18   │   -   ║   10   │
+├────┼────────┼───────╫────────┤
+│ 2  │ >=21   │  >50  ║   5    │
+├────┼────────┼───────╫────────┤
+│ 3  │ <18    │   -   ║   1    │
+├────┼────────┼───────╫────────┤
+│ 4  │   >=18 │  >50  ║   5    │
+└────┴────────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(5, 8));
+  plane.begin_selection(SelectionMode::Characters);
+  plane.cursor_move_right();
+  assert_eq!(Some(Register::Text(vec!["=2".to_string()])), plane.cut());
+  eq_cursor(5, 8, plane);
+  let expected = r#"
+┌────┬─────┬───────╥────────┐
+│ C+ │  Age│ Score ║ Points │
+╞════╪═════╪═══════╬════════╡
+│ 1  │ >=18│   -   ║   10   │
+├────┼─────┼───────╫────────┤
+│ 2  │ >1  │  >50  ║   5    │
+├────┼─────┼───────╫────────┤
+│ 3  │ <18 │   -   ║   1    │
+├────┼─────┼───────╫────────┤
+│ 4  │   - │  >50  ║   5    │
+└────┴─────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(3, 7));
+  plane.begin_selection(SelectionMode::Cells);
+  assert!(plane.cursor_move_to(5, 15));
+  let register = plane.copy().unwrap();
+  assert_eq!(cells(&[&[&[">=18"], &["-"]], &[&[">=21"], &[">50"]]]), register);
+  assert_eq!(">=18\t-\n>=21\t>50", register.to_string());
+  assert!(plane.cursor_move_to(7, 7));
+  assert!(plane.paste(&register));
+  let expected = TEST_INPUT_003
+    .replace("│ 3  │ <18   │   -   ║", "│ 3  │ >=18  │ -     ║")
+    .replace("│ 4  │   -   │  >50  ║", "│ 4  │ >=21  │ >50   ║");
+  eq(plane, &expected);
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(3, 7));
+  assert!(plane.paste(&cells(&[&[&["> 18,", "< 65"], &["much higher than 50"]]])));
+  let expected = r#"
+┌────┬───────┬─────────────────────╥────────┐
+│ C+ │  Age  │ Score               ║ Points │
+╞════╪═══════╪═════════════════════╬════════╡
+│ 1  │ > 18, │ much higher than 50 ║   10   │
+│    │ < 65  │                     ║        │
+├────┼───────┼─────────────────────╫────────┤
+│ 2  │ >=21  │  >50                ║   5    │
+├────┼───────┼─────────────────────╫────────┤
+│ 3  │ <18   │   -                 ║   1    │
+├────┼───────┼─────────────────────╫────────┤
+│ 4  │   -   │  >50                ║   5    │
+└────┴───────┴─────────────────────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(3, 2));
+  plane.begin_selection(SelectionMode::Rules);
+  assert!(plane.cursor_move_to(5, 26));
+  assert_eq!(2, plane.selected_regions().len());
+  let register = plane.cut().unwrap();
+  assert_eq!(">=18\t-\t10\n>=21\t>50\t5", register.to_string());
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 3  │ <18   │   -   ║   1    │
+├────┼───────┼───────╫────────┤
+│ 4  │   -   │  >50  ║   5    │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  assert!(plane.cursor_move_to(5, 2));
+  assert!(plane.paste(&register));
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 3  │ <18   │   -   ║   1    │
+├────┼───────┼───────╫────────┤
+│ 4  │   -   │  >50  ║   5    │
+├────┼───────┼───────╫────────┤
+│    │ >=18  │ -     ║ 10     │
+├────┼───────┼───────╫────────┤
+│    │ >=21  │ >50   ║ 5      │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0006() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(5, 11));
+  assert!(plane.insert_text(",\r\n<65\t >70"));
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ >=18  │   -   ║   10   │
+├────┼───────┼───────╫────────┤
+│ 2  │ >=21, │  >50  ║   5    │
+│    │ <65   │ >70   ║        │
+├────┼───────┼───────╫────────┤
+│ 3  │ <18   │   -   ║   1    │
+├────┼───────┼───────╫────────┤
+│ 4  │   -   │  >50  ║   5    │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  eq_cursor(6, 18, plane);
+}
+
+#[test]
+fn _0007() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(9, 27));
+  assert!(plane.insert_text("0\t1\t2"));
+  eq(plane, &TEST_INPUT_003.replace("║   5    │\n└", "║   5 0  │\n└"));
+  eq_cursor(9, 29, plane);
+}
@@ -0,0 +1,119 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_001);
+  assert!(plane.go_to("rule 2").is_ok());
+  eq_cursor(13, 5, plane);
+  assert_eq!("2:Customer type", plane.cursor_location().unwrap());
+  assert!(plane.go_to("input order SIZE").is_ok());
+  eq_cursor(4, 18, plane);
+  assert_eq!("input Order size", plane.cursor_location().unwrap());
+  assert!(plane.go_to("output Priority").is_ok());
+  assert_eq!("output Priority", plane.cursor_location().unwrap());
+  assert!(plane.go_to("annotation Reference").is_ok());
+  assert_eq!("annotation Reference", plane.cursor_location().unwrap());
+  assert!(plane.go_to("3:Discount").is_ok());
+  assert_eq!("3:Discount", plane.cursor_location().unwrap());
+  eq_cursor(15, 28, plane);
+  assert!(plane.cursor_move_to(13, 2));
+  assert_eq!("rule 2", plane.cursor_location().unwrap());
+  assert!(plane.cursor_move_to(1, 2));
+  assert_eq!(None, plane.cursor_location());
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_001);
+  assert_eq!(
+    "going to 'rule 4' failed with reason: 'rule 4 not found'",
+    plane.go_to("rule 4").unwrap_err().to_string()
+  );
+  assert_eq!(
+    "going to 'input Discount' failed with reason: 'input 'Discount' not found'",
+    plane.go_to("input Discount").unwrap_err().to_string()
+  );
+  assert_eq!(
+    "going to '3:Unknown' failed with reason: 'clause 'Unknown' not found'",
+    plane.go_to("3:Unknown").unwrap_err().to_string()
+  );
+  assert_eq!(
+    "going to 'somewhere' failed with reason: 'expected 'rule N', 'input NAME', 'output NAME', 'annotation NAME' or 'N:NAME''",
+    plane.go_to("somewhere").unwrap_err().to_string()
+  );
+  eq_cursor(1, 1, plane);
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_001);
+  assert!(plane.go_to("input Customer type").is_ok());
+  eq_cursor(4, 6, plane);
+  assert!(plane.cursor_move_rule(true));
+  eq_cursor(11, 5, plane);
+  assert!(plane.cursor_move_rule(true));
+  assert!(plane.cursor_move_rule(true));
+  assert_eq!("3:Customer type", plane.cursor_location().unwrap());
+  assert!(!plane.cursor_move_rule(true));
+  assert!(plane.cursor_move_rule(false));
+  assert!(plane.cursor_move_rule(false));
+  assert!(plane.cursor_move_rule(false));
+  eq_cursor(4, 6, plane);
+  assert!(!plane.cursor_move_rule(false));
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.cursor_move_body());
+  assert_eq!("1:Age", plane.cursor_location().unwrap());
+  eq_cursor(3, 11, plane);
+  assert!(plane.cursor_move_rule(true));
+  assert_eq!("2:Age", plane.cursor_location().unwrap());
+  eq_cursor(3, 18, plane);
+  assert!(plane.cursor_move_header());
+  assert_eq!("input Age", plane.cursor_location().unwrap());
+  eq_cursor(3, 2, plane);
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT_005);
+  assert!(plane.go_to("3:Discount").is_ok());
+  eq_cursor(7, 28, plane);
+  assert!(plane.cursor_move_header());
+  assert_eq!("output Discount", plane.cursor_location().unwrap());
+  eq_cursor(1, 2, plane);
+}
@@ -0,0 +1,115 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::linter::lint;
+use crate::plane::Plane;
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_001);
+  assert_eq!("Order options", plane.information_item_name());
+  assert!(plane.cursor_move_to(1, 5));
+  assert!(plane.is_cursor_in_information_item_name());
+  plane.set_information_item_name("Discounts");
+  eq_cursor(1, 1, plane);
+  let expected = TEST_INPUT_001.replace(
+    "┌─────────────────────────────────────┐\n│ Order options                       │\n├───┬───────────┬───────╥─────────────┴───────╥",
+    "┌───────────┐\n│ Discounts │\n├───┬───────┴───┬───────╥─────────────────────╥",
+  );
+  eq(plane, &expected);
+  assert!(lint(plane).is_empty());
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert_eq!("", plane.information_item_name());
+  assert!(plane.cursor_move_to(3, 7));
+  plane.set_information_item_name("  Points 100 ");
+  eq_cursor(5, 7, plane);
+  let expected = r#"
+┌────────────┐
+│ Points 100 │
+├────┬───────┼───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+"#;
+  assert!(plane.to_string().starts_with(expected.trim()));
+  assert!(lint(plane).is_empty());
+  plane.set_information_item_name("");
+  eq_cursor(3, 7, plane);
+  eq(plane, TEST_INPUT_003);
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  plane.set_information_item_name("Points of the game");
+  let expected = r#"
+┌─────────────────────┐
+│ Points of the game  │
+├────┬───────┬───────╥┴───────┐
+"#;
+  assert!(plane.to_string().starts_with(expected.trim()));
+  assert!(lint(plane).is_empty());
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  plane.set_information_item_name("Points of the score of the age");
+  let expected = r#"
+┌────────────────────────────────┐
+│ Points of the score of the age │
+├────┬───────┬───────╥───────────┤
+│ C+ │  Age  │ Score ║ Points    │
+╞════╪═══════╪═══════╬═══════════╡
+│ 1  │ >=18  │   -   ║   10      │
+"#;
+  assert!(plane.to_string().starts_with(expected.trim()));
+  assert!(lint(plane).is_empty());
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT_X);
+  assert_eq!("abcdef_hijklmnopqrstuvwxyzABCDEFGHIJK", plane.information_item_name());
+  assert!(plane.cursor_move_to(2, 3));
+  plane.set_information_item_name("");
+  eq_cursor(1, 1, plane);
+  assert!(!plane.is_cursor_in_information_item_name());
+  assert!(plane
+    .to_string()
+    .starts_with("┌───┬───────────┬───────╥─────────────────────╥─────────────┬───────────┐\n│ U │"));
+  assert!(lint(plane).is_empty());
+}
@@ -0,0 +1,184 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::plane::Plane;
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(5, 7));
+  assert!(plane.insert_rule());
+  eq_cursor(7, 7, plane);
+  let expected = TEST_INPUT_003.replace(
+    "│ 2  │ >=21  │  >50  ║   5    │\n",
+    "│ 2  │ >=21  │  >50  ║   5    │\n├────┼───────┼───────╫────────┤\n│    │       │       ║        │\n",
+  );
+  eq(plane, &expected);
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(3, 7));
+  assert!(plane.delete_rule());
+  eq_cursor(3, 7, plane);
+  eq(
+    plane,
+    &TEST_INPUT_003.replace("│ 1  │ >=18  │   -   ║   10   │\n├────┼───────┼───────╫────────┤\n", ""),
+  );
+  assert!(plane.cursor_move_to(7, 7));
+  assert!(plane.delete_rule());
+  eq_cursor(5, 7, plane);
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.cursor_move_to(3, 12));
+  assert!(plane.insert_rule());
+  eq_cursor(3, 17, plane);
+  let expected = r#"
+┌────────╥──────┬──────┬──────┬──────┬──────┐
+│ C+     ║  1   │      │  2   │  3   │  4   │
+├────────╫──────┼──────┼──────┼──────┼──────┤
+│ Age    ║ >=18 │      │ >=21 │ <18  │  -   │
+├────────╫──────┼──────┼──────┼──────┼──────┤
+│ Score  ║  -   │      │ >50  │  -   │ >50  │
+╞════════╬══════╪══════╪══════╪══════╪══════╡
+│ Points ║  10  │      │  5   │  1   │  5   │
+└────────╨──────┴──────┴──────┴──────┴──────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.cursor_move_to(3, 33));
+  assert!(plane.delete_rule());
+  eq_cursor(3, 29, plane);
+  let expected = r#"
+┌────────╥──────┬──────┬──────┐
+│ C+     ║  1   │  2   │  3   │
+├────────╫──────┼──────┼──────┤
+│ Age    ║ >=18 │ >=21 │ <18  │
+├────────╫──────┼──────┼──────┤
+│ Score  ║  -   │ >50  │  -   │
+╞════════╬══════╪══════╪══════╡
+│ Points ║  10  │  5   │  1   │
+└────────╨──────┴──────┴──────┘
+"#;
+  eq(plane, expected);
+  assert!(plane.cursor_move_to(3, 11));
+  assert!(plane.delete_rule());
+  eq_cursor(3, 10, plane);
+  assert!(plane.to_string().starts_with("┌────────╥──────┬──────┐\n│ C+     ║  2   │  3   │"));
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  eq_cursor(1, 1, plane);
+  assert!(!plane.insert_rule());
+  assert!(!plane.delete_rule());
+  assert!(plane.cursor_move_to(3, 7));
+  rep(3, || assert!(plane.delete_rule()));
+  assert!(!plane.delete_rule());
+  eq(plane, &TEST_INPUT_003.replace("│ 1  │ >=18  │   -   ║   10   │\n├────┼───────┼───────╫────────┤\n│ 2  │ >=21  │  >50  ║   5    │\n├────┼───────┼───────╫────────┤\n│ 3  │ <18   │   -   ║   1    │\n├────┼───────┼───────╫────────┤\n", ""));
+}
+
+#[test]
+fn _0006() {
+  let input = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ >=18  │   -   ║   10   │
+├────┼───────┼───────╫────────┤
+│ 2  │ >=21, │  >50  ║   5    │
+│    │ <80   │       ║        │
+├────┼───────┼───────╫────────┤
+│ 3  │ <18   │   -   ║   1    │
+└────┴───────┴───────╨────────┘
+"#;
+  let plane = &mut Plane::new(input);
+  assert!(plane.cursor_move_to(6, 9));
+  assert!(plane.move_rule(false));
+  eq_cursor(4, 9, plane);
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ >=21, │  >50  ║   5    │
+│    │ <80   │       ║        │
+├────┼───────┼───────╫────────┤
+│ 2  │ >=18  │   -   ║   10   │
+├────┼───────┼───────╫────────┤
+│ 3  │ <18   │   -   ║   1    │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  assert!(!plane.move_rule(false));
+  assert!(plane.move_rule(true));
+  assert!(plane.move_rule(true));
+  eq_cursor(8, 9, plane);
+  assert!(!plane.move_rule(true));
+  assert!(plane
+    .to_string()
+    .ends_with("│ 3  │ >=21, │  >50  ║   5    │\n│    │ <80   │       ║        │\n└────┴───────┴───────╨────────┘"));
+}
+
+#[test]
+fn _0007() {
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.cursor_move_to(5, 18));
+  assert!(plane.move_rule(true));
+  eq_cursor(5, 25, plane);
+  let expected = r#"
+┌────────╥──────┬──────┬──────┬──────┐
+│ C+     ║  1   │  2   │  3   │  4   │
+├────────╫──────┼──────┼──────┼──────┤
+│ Age    ║ >=18 │ <18  │ >=21 │  -   │
+├────────╫──────┼──────┼──────┼──────┤
+│ Score  ║  -   │  -   │ >50  │ >50  │
+╞════════╬══════╪══════╪══════╪══════╡
+│ Points ║  10  │  1   │  5   │  5   │
+└────────╨──────┴──────┴──────┴──────┘
+"#;
+  eq(plane, expected);
+  assert!(plane.cursor_move_to(1, 3));
+  assert!(!plane.move_rule(true));
+  let plane = &mut Plane::new(TEST_INPUT_005);
+  assert!(plane.cursor_move_to(5, 23));
+  assert!(!plane.move_rule(true));
+}
@@ -0,0 +1,167 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::analyzer::analyze;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Utility function for analyzing the decision table, returns messages of all findings.
+fn findings(decision_table: &str) -> Vec<String> {
+  let plane = Plane::new(decision_table);
+  let decision_table = recognize(&plane).unwrap();
+  analyze(&decision_table).unwrap().into_iter().map(|finding| finding.message).collect()
+}
+
+#[test]
+fn _0001() {
+  assert!(findings(TEST_INPUT_001).is_empty());
+}
+
+#[test]
+fn _0002() {
+  let decision_table = TEST_INPUT_001.replace("│ 3 │\"Private\"  │   -   ║", "│ 3 │\"Private\"  │  <10  ║");
+  assert_eq!(
+    vec![r#"no rule matches: Customer type = "Private", Order size = >=10"#],
+    findings(&decision_table)
+  );
+}
+
+#[test]
+fn _0003() {
+  let decision_table = TEST_INPUT_001.replace("│ 3 │\"Private\"  │", "│ 3 │     -     │");
+  assert_eq!(
+    vec![
+      "rules 1 and 3 overlap (violates hit policy U)",
+      "rules 2 and 3 overlap (violates hit policy U)",
+      "rule 1 is subsumed by rule 3",
+      "rule 2 is subsumed by rule 3",
+    ],
+    findings(&decision_table)
+  );
+}
+
+#[test]
+fn _0004() {
+  // overlapping and subsumed rules are intended when outputs are collected
+  assert!(findings(TEST_INPUT_003).is_empty());
+}
+
+#[test]
+fn _0005() {
+  let decision_table = TEST_INPUT_002.replace("│ 4 │   <18    ║", "│ 4 │   <10    ║");
+  assert_eq!(vec!["no rule matches: Age = [10..18)"], findings(&decision_table));
+}
+
+#[test]
+fn _0006() {
+  let decision_table = TEST_INPUT_003.replace("│ 3  │ <18   │   -   ║", "│ 3  │ true  │   -   ║");
+  assert_eq!(vec!["no rule matches: Age = <18, not(true), Score = <=50"], findings(&decision_table));
+}
+
+#[test]
+fn _0007() {
+  // with hit policy F only rules subsumed by preceding rules are reported, they never match
+  let decision_table = TEST_INPUT_003.replace("│ C+ │", "│ F  │");
+  assert_eq!(vec!["rule 2 is subsumed by rule 1"], findings(&decision_table));
+}
+
+#[test]
+fn _0008() {
+  // with hit policy A overlapping rules must have equal outputs
+  let decision_table = TEST_INPUT_003.replace("│ C+ │", "│ A  │");
+  assert_eq!(
+    vec![
+      "rules 1 and 2 overlap (violates hit policy A)",
+      "rules 1 and 4 overlap (violates hit policy A)",
+      "rules 3 and 4 overlap (violates hit policy A)",
+      "rule 2 is subsumed by rule 1",
+    ],
+    findings(&decision_table)
+  );
+}
+
+#[test]
+fn _0009() {
+  // rules are reported by numbers placed in the table
+  let decision_table = TEST_INPUT_001
+    .replace("│ 3 │\"Private\"  │", "│ 7 │     -     │")
+    .replace("│ 2 │\"Business\" │", "│ 5 │\"Business\" │");
+  assert_eq!(
+    vec![
+      "rules 1 and 7 overlap (violates hit policy U)",
+      "rules 5 and 7 overlap (violates hit policy U)",
+      "rule 1 is subsumed by rule 7",
+      "rule 5 is subsumed by rule 7",
+    ],
+    findings(&decision_table)
+  );
+}
+
+#[test]
+fn _0010() {
+  // bounds of allowed values divide the domain, rules overlapping only within allowed values are reported
+  let decision_table = r#"
+┌───┬─────────┬───────╥────────┐
+│ U │   Age   │ Score ║ Points │
+│   ├─────────┼───────╫────────┤
+│   │ [0..5]  │       ║        │
+╞═══╪═════════╪═══════╬════════╡
+│ 1 │ (1..10) │  <=60 ║   1    │
+├───┼─────────┼───────╫────────┤
+│ 2 │ (3..20) │  >50  ║   2    │
+└───┴─────────┴───────╨────────┘
+"#;
+  assert_eq!(
+    vec![
+      "rules 1 and 2 overlap (violates hit policy U)",
+      "no rule matches: Age = [0..1]",
+      "no rule matches: Age = (1..3], Score = >60",
+    ],
+    findings(decision_table)
+  );
+}
+
+#[test]
+fn _0011() {
+  // gaps are reported within allowed values
+  let decision_table = r#"
+┌───┬─────────┬───────╥────────┐
+│ U │   Age   │ Score ║ Points │
+│   ├─────────┼───────╫────────┤
+│   │ [0..50] │       ║        │
+╞═══╪═════════╪═══════╬════════╡
+│ 1 │ (1..20) │   -   ║   1    │
+└───┴─────────┴───────╨────────┘
+"#;
+  assert_eq!(vec!["no rule matches: Age = [0..1], [20..50]"], findings(decision_table));
+}
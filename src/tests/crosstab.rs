@@ -0,0 +1,176 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::evaluator::evaluate;
+use crate::exporter::export;
+use crate::feel::Value;
+use crate::formatter::format;
+use crate::linter::lint;
+use crate::model::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Utility function for recognizing the decision table.
+fn table(decision_table: &str) -> DecisionTable {
+  recognize(&Plane::new(decision_table)).unwrap()
+}
+
+#[test]
+fn _0001() {
+  let dt = table(TEST_INPUT_005);
+  assert_eq!(Orientation::CrossTable, dt.orientation);
+  assert_eq!(HitPolicy::Unique, dt.hit_policy);
+  let inputs: Vec<String> = dt.input_clauses.iter().map(|clause| clause.input_expression.text()).collect();
+  assert_eq!(vec!["Order size", "Customer type"], inputs);
+  assert_eq!(vec!["Discount"], dt.output_names());
+  assert_eq!(4, dt.rules.len());
+  let rule = &dt.rules[2];
+  assert_eq!("3", rule.number.text());
+  assert_eq!(vec![">=10", "\"Business\""], rule.input_entries.iter().map(Cell::text).collect::<Vec<String>>());
+  assert_eq!("0.15", rule.output_entries[0].text());
+  assert_eq!(4, dt.body_top);
+  assert_eq!(8, dt.bottom);
+}
+
+#[test]
+fn _0002() {
+  let dt = table(TEST_INPUT_005);
+  let evaluation = evaluate(&dt, &[Value::Number(12.0), Value::String("Private".to_string())]).unwrap();
+  assert_eq!(vec![3], evaluation.matched_rules);
+  assert_eq!(Value::Number(0.05), evaluation.result.unwrap());
+  assert!(lint(&Plane::new(TEST_INPUT_005)).is_empty());
+}
+
+#[test]
+fn _0003() {
+  assert_eq!(TEST_INPUT_005.trim(), format(&table(TEST_INPUT_005)));
+}
+
+#[test]
+fn _0004() {
+  let mut dt = table(TEST_INPUT_005);
+  dt.orientation = Orientation::RuleAsRow;
+  let rows = format(&dt);
+  let expected = r#"
+┌───┬────────────┬───────────────╥──────────┐
+│ U │ Order size │ Customer type ║ Discount │
+╞═══╪════════════╪═══════════════╬══════════╡
+│ 1 │ <10        │ "Business"    ║     0.10 │
+├───┼────────────┼───────────────╫──────────┤
+│ 2 │ <10        │ "Private"     ║     0.05 │
+├───┼────────────┼───────────────╫──────────┤
+│ 3 │ >=10       │ "Business"    ║     0.15 │
+├───┼────────────┼───────────────╫──────────┤
+│ 4 │ >=10       │ "Private"     ║     0.05 │
+└───┴────────────┴───────────────╨──────────┘
+"#;
+  assert_eq!(expected.trim(), rows);
+  assert_eq!(TEST_INPUT_005.trim(), format(&table(&rows).to_crosstab().unwrap()));
+}
+
+#[test]
+fn _0005() {
+  assert_eq!(
+    "converting decision table into crosstab failed with reason: 'only decision tables with unique hit policy can be converted'",
+    table(TEST_INPUT_003).to_crosstab().unwrap_err().to_string()
+  );
+  let mut dt = table(TEST_INPUT_005);
+  dt.orientation = Orientation::RuleAsRow;
+  let rows = format(&dt).replace("│ 4 │ >=10       │ \"Private\" ", "│ 4 │ >=10       │ \"Business\"");
+  assert_eq!(
+    "converting decision table into crosstab failed with reason: 'combination of '>=10' and '\"Business\"' must be covered by exactly one rule'",
+    table(&rows).to_crosstab().unwrap_err().to_string()
+  );
+}
+
+#[test]
+fn _0006() {
+  let plane = &mut Plane::new(TEST_INPUT_005);
+  assert!(plane.cursor_move_to(5, 16));
+  assert!(plane.insert_rule());
+  eq_cursor(7, 16, plane);
+  let expected = r#"
+┌───────────────────╥────────────────────────┐
+│ Discount          ║     Customer type      │
+│                   ╟────────────┬───────────┤
+│                   ║ "Business" │ "Private" │
+╞════════════╤══════╬════════════╪═══════════╡
+│ Order size │ <10  ║       0.10 │      0.05 │
+│            ├──────╫────────────┼───────────┤
+│            │      ║            │           │
+│            ├──────╫────────────┼───────────┤
+│            │ >=10 ║       0.15 │      0.05 │
+└────────────┴──────╨────────────┴───────────┘
+"#;
+  eq(plane, expected);
+  assert_eq!(6, table(&plane.to_string()).rules.len());
+  assert!(plane.delete_rule());
+  eq(plane, TEST_INPUT_005);
+}
+
+#[test]
+fn _0007() {
+  let plane = &mut Plane::new(TEST_INPUT_005);
+  assert!(plane.cursor_move_to(3, 24));
+  assert!(plane.insert_rule());
+  eq_cursor(3, 34, plane);
+  let expected = r#"
+┌───────────────────╥─────────────────────────────────────┐
+│ Discount          ║     Customer type                   │
+│                   ╟────────────┬────────────┬───────────┤
+│                   ║ "Business" │            │ "Private" │
+╞════════════╤══════╬════════════╪════════════╪═══════════╡
+│ Order size │ <10  ║       0.10 │            │      0.05 │
+│            ├──────╫────────────┼────────────┼───────────┤
+│            │ >=10 ║       0.15 │            │      0.05 │
+└────────────┴──────╨────────────┴────────────┴───────────┘
+"#;
+  eq(plane, expected);
+  assert_eq!(6, table(&plane.to_string()).rules.len());
+  assert!(plane.delete_rule());
+  eq(plane, TEST_INPUT_005);
+  assert!(plane.cursor_move_to(3, 40));
+  assert!(plane.delete_rule());
+  assert!(!plane.delete_rule());
+  assert_eq!(2, table(&plane.to_string()).rules.len());
+}
+
+#[test]
+fn _0008() {
+  let xml = export("discount", &[table(TEST_INPUT_005)]);
+  assert!(xml.contains(r#"<decisionTable id="_1_dt" hitPolicy="UNIQUE" outputLabel="Discount" preferredOrientation="CrossTable">"#));
+  assert!(xml.contains(r#"<input id="_1_i2" label="Customer type">"#));
+  assert!(xml.contains("<text>&gt;=10</text>"));
+  assert!(xml.contains(r#"<text>"Private"</text>"#));
+  assert_eq!(4, xml.matches("<rule ").count());
+}
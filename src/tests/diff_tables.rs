@@ -0,0 +1,136 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::differ::{diff_documents, report_json, report_side_by_side, report_text, Change};
+use crate::document::Document;
+use crate::model::ClauseKind;
+
+const OLD: &str = r#"
+┌───┬───────────┬───────╥──────────┬──────────╥─────────────┐
+│ U │ Customer  │ Order ║ Discount │ Priority ║ Description │
+│   │ type      │ size  ║          │          ║             │
+╞═══╪═══════════╪═══════╬══════════╪══════════╬═════════════╡
+│ 1 │"Business" │  <10  ║   0.10   │ "Normal" ║ Small order │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 2 │"Business" │ >=10  ║   0.15   │ "High"   ║ Large order │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 3 │"Private"  │   -   ║   0.05   │ "Low"    ║ All orders  │
+└───┴───────────┴───────╨──────────┴──────────╨─────────────┘
+"#;
+
+#[test]
+fn _0001() {
+  // reformatted, renumbered and reordered rules are not reported
+  let new = r#"
+┌───┬────────────────┬───────╥──────────┬──────────╥─────────────┐
+│ U │ Customer type  │ Order ║ Discount │ Priority ║ Description │
+│   │                │ size  ║          │          ║             │
+╞═══╪════════════════╪═══════╬══════════╪══════════╬═════════════╡
+│ 1 │ "Private"      │   -   ║   0.05   │ "Low"    ║ All orders  │
+├───┼────────────────┼───────╫──────────┼──────────╫─────────────┤
+│ 2 │ "Business"     │  <10  ║   0.10   │ "Normal" ║ Small order │
+├───┼────────────────┼───────╫──────────┼──────────╫─────────────┤
+│ 3 │ "Business"     │ >=10  ║   0.15   │ "High"   ║ Large order │
+└───┴────────────────┴───────╨──────────┴──────────╨─────────────┘
+"#;
+  let tables = diff_documents(&Document::new(OLD), &Document::new(new)).unwrap();
+  assert!(tables.is_empty());
+  assert_eq!("[]\n", report_json(&tables));
+}
+
+#[test]
+fn _0002() {
+  let new = r#"
+┌───┬───────────┬───────╥──────────┬──────────╥─────────────┐
+│ F │ Customer  │ Order ║ Discount │ Priority ║ Description │
+│   │ type      │ size  ║          │          ║             │
+╞═══╪═══════════╪═══════╬══════════╪══════════╬═════════════╡
+│ 1 │"Private"  │   -   ║   0.05   │ "Low"    ║ All orders  │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 2 │"Business" │ >=10  ║   0.20   │ "High"   ║ Large order │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 3 │"Public"   │   -   ║   0.00   │ "High"   ║ Public      │
+└───┴───────────┴───────╨──────────┴──────────╨─────────────┘
+"#;
+  let tables = diff_documents(&Document::new(OLD), &Document::new(new)).unwrap();
+  let expected = vec![
+    "hit policy changed from 'U' to 'F'",
+    "rule 1 removed: \"Business\" | <10 | 0.10 | \"Normal\" | Small order",
+    "rule 2 changed: Discount from '0.15' to '0.20'",
+    "rule 3 added: \"Public\" | - | 0.00 | \"High\" | Public",
+  ];
+  assert_eq!(expected, report_text(&tables, false));
+  let json = report_json(&tables);
+  assert!(json.contains(r#"{"table": 1, "change": "hitPolicyChanged", "old": "U", "new": "F"}"#));
+  assert!(json.contains(r#""cells": [{"clause": "Discount", "old": "0.15", "new": "0.20"}]"#));
+  assert!(json.contains(r#""entries": ["\"Public\"", "-", "0.00", "\"High\"", "Public"]"#));
+}
+
+#[test]
+fn _0003() {
+  // clauses are matched by name, entries of removed clauses are not compared
+  let new = r#"
+┌───┬───────────┬───────╥──────────┬──────────╥───────┐
+│ U │ Customer  │ Order ║ Discount │ Priority ║ Notes │
+│   │ type      │ size  ║          │          ║       │
+╞═══╪═══════════╪═══════╬══════════╪══════════╬═══════╡
+│ 1 │"Business" │  <10  ║   0.10   │ "Normal" ║       │
+├───┼───────────┼───────╫──────────┼──────────╫───────┤
+│ 2 │"Business" │ >=10  ║   0.15   │ "High"   ║       │
+├───┼───────────┼───────╫──────────┼──────────╫───────┤
+│ 3 │"Private"  │   -   ║   0.05   │ "Low"    ║       │
+└───┴───────────┴───────╨──────────┴──────────╨───────┘
+"#;
+  let tables = diff_documents(&Document::new(OLD), &Document::new(new)).unwrap();
+  assert_eq!(1, tables.len());
+  let changes: Vec<&Change> = tables[0].differences.iter().map(|difference| &difference.change).collect();
+  assert_eq!(
+    vec![
+      &Change::ClauseRemoved(ClauseKind::Annotation, "Description".to_string()),
+      &Change::ClauseAdded(ClauseKind::Annotation, "Notes".to_string())
+    ],
+    changes
+  );
+}
+
+#[test]
+fn _0004() {
+  // tables are compared by position, missing tables are reported
+  let old = Document::new(OLD);
+  let new = Document::new(&format!("{}\n{}", OLD, OLD));
+  let tables = diff_documents(&old, &new).unwrap();
+  assert_eq!(vec!["table 2: table added"], report_text(&tables, true));
+  let lines = report_side_by_side(&new, &old, &diff_documents(&new, &old).unwrap());
+  assert_eq!("table 2:", lines[0]);
+  assert!(lines[1].starts_with("\u{1b}[31m┌───┬"));
+  assert_eq!(Some(&"table 2: table removed".to_string()), lines.last());
+}
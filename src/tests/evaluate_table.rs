@@ -0,0 +1,166 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::evaluator::evaluate;
+use crate::feel::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Utility function for evaluating a decision table with input values written in FEEL.
+fn eval(decision_table: &str, input_values: &[&str]) -> (Vec<usize>, String) {
+  let plane = Plane::new(decision_table);
+  let decision_table = recognize(&plane).unwrap();
+  let input_values: Vec<Value> = input_values.iter().map(|text| parse_value(text).unwrap()).collect();
+  let evaluation = evaluate(&decision_table, &input_values).unwrap();
+  let rule_numbers = evaluation.matched_rules.iter().map(|index| index + 1).collect();
+  match evaluation.result {
+    Ok(value) => (rule_numbers, value.to_string()),
+    Err(reason) => (rule_numbers, reason.to_string()),
+  }
+}
+
+#[test]
+fn _0001() {
+  assert_eq!(
+    (vec![1], r#"{Discount: 0.1, Priority: "Normal"}"#.to_string()),
+    eval(TEST_INPUT_001, &[r#""Business""#, "9"])
+  );
+  assert_eq!(
+    (vec![2], r#"{Discount: 0.15, Priority: "High"}"#.to_string()),
+    eval(TEST_INPUT_001, &[r#""Business""#, "10"])
+  );
+  assert_eq!(
+    (vec![3], r#"{Discount: 0.05, Priority: "Low"}"#.to_string()),
+    eval(TEST_INPUT_001, &[r#""Private""#, "25"])
+  );
+}
+
+#[test]
+fn _0002() {
+  assert_eq!(
+    (
+      vec![],
+      r#"evaluation failed with reason: 'value "Public" is not allowed for input 'Customer type''"#.to_string()
+    ),
+    eval(TEST_INPUT_001, &[r#""Public""#, "25"])
+  );
+}
+
+#[test]
+fn _0003() {
+  let decision_table = TEST_INPUT_001.replace("│ 3 │\"Private\"  │", "│ 3 │     -     │");
+  assert_eq!(
+    (
+      vec![1, 3],
+      "evaluation failed with reason: 'hit policy U violated, matched rules: 1, 3'".to_string()
+    ),
+    eval(&decision_table, &[r#""Business""#, "1"])
+  );
+}
+
+#[test]
+fn _0004() {
+  assert_eq!((vec![1, 2], r#""High""#.to_string()), eval(TEST_INPUT_002, &["60"]));
+  assert_eq!((vec![1, 3], r#""Medium""#.to_string()), eval(TEST_INPUT_002, &["20"]));
+  assert_eq!((vec![1], r#""Low""#.to_string()), eval(TEST_INPUT_002, &["40"]));
+  let decision_table = TEST_INPUT_002.replace("│ P │", "│ O │");
+  assert_eq!((vec![1, 3], r#"["Medium", "Low"]"#.to_string()), eval(&decision_table, &["30"]));
+  assert_eq!((vec![1, 2], r#"["High", "Low"]"#.to_string()), eval(&decision_table, &["60"]));
+}
+
+#[test]
+fn _0005() {
+  assert_eq!((vec![1, 2, 4], "20".to_string()), eval(TEST_INPUT_003, &["25", "60"]));
+  assert_eq!((vec![], "null".to_string()), eval(&TEST_INPUT_003.replace("C+", "U "), &["null", "10"]));
+  assert_eq!((vec![1, 2, 4], "2".to_string()), eval(&TEST_INPUT_003.replace("C+", "C#"), &["25", "60"]));
+  assert_eq!((vec![1, 2, 4], "5".to_string()), eval(&TEST_INPUT_003.replace("C+", "C<"), &["25", "60"]));
+  assert_eq!((vec![1, 2, 4], "10".to_string()), eval(&TEST_INPUT_003.replace("C+", "C>"), &["25", "60"]));
+  assert_eq!(
+    (vec![1, 2, 4], "[10, 5, 5]".to_string()),
+    eval(&TEST_INPUT_003.replace("C+", "R "), &["25", "60"])
+  );
+  assert_eq!((vec![1, 2, 4], "10".to_string()), eval(&TEST_INPUT_003.replace("C+", "F "), &["25", "60"]));
+  assert_eq!((vec![3], "1".to_string()), eval(&TEST_INPUT_003.replace("C+", "A "), &["5", "5"]));
+}
+
+#[test]
+fn _0006() {
+  let tests = UnaryTests::parse(r#"not("a", [1..5), >=10)"#).unwrap();
+  assert!(!tests.matches(&Value::String("a".to_string())));
+  assert!(!tests.matches(&Value::Number(1.0)));
+  assert!(tests.matches(&Value::Number(5.0)));
+  assert!(!tests.matches(&Value::Number(10.0)));
+  assert!(tests.matches(&Value::String("b".to_string())));
+  assert_eq!(vec![r#""a, b""#, "c"], split_list(r#""a, b", c"#));
+  assert_eq!(
+    "parsing 'abc' failed with reason: 'unsupported expression'",
+    parse_value("abc").unwrap_err().to_string()
+  );
+}
+
+#[test]
+fn _0007() {
+  let decision_table = r#"
+┌───┬──────────┬──────────╥────────┐
+│ F │ Member   │ Country  ║ Rebate │
+╞═══╪══════════╪══════════╬════════╡
+│ 1 │ != false │ != "PL"  ║ 0.10   │
+├───┼──────────┼──────────╫────────┤
+│ 2 │ true     │    -     ║ -      │
+├───┼──────────┼──────────╫────────┤
+│ 3 │ < true   │    -     ║        │
+└───┴──────────┴──────────╨────────┘
+"#;
+  assert_eq!((vec![1, 2], "0.1".to_string()), eval(decision_table, &["true", r#""DE""#]));
+  assert_eq!((vec![2], "null".to_string()), eval(decision_table, &["true", r#""PL""#]));
+  assert_eq!((vec![3], "null".to_string()), eval(decision_table, &["false", r#""DE""#]));
+  assert!(UnaryTests::parse("!= false").unwrap().matches(&Value::Boolean(true)));
+  assert!(!UnaryTests::parse("!= true").unwrap().matches(&Value::Boolean(true)));
+  assert!(UnaryTests::parse(">= false").unwrap().matches(&Value::Boolean(true)));
+  assert!(!UnaryTests::parse("!= 1").unwrap().matches(&Value::Boolean(true)));
+}
+
+#[test]
+fn _0008() {
+  // the rule with invalid input entry is reported, remaining rules are evaluated
+  let decision_table = TEST_INPUT_003.replace("│ 2  │ >=21  │  >50  ║", "│ 2  │ >=21  │ >>50  ║");
+  assert_eq!((vec![1, 4], "15".to_string()), eval(&decision_table, &["25", "60"]));
+  let plane = Plane::new(&decision_table);
+  let decision_table = recognize(&plane).unwrap();
+  let evaluation = evaluate(&decision_table, &[Value::Number(10.0), Value::Number(20.0)]).unwrap();
+  assert_eq!(vec![2], evaluation.matched_rules);
+  assert_eq!(
+    vec![(1, "parsing '>50' failed with reason: 'unsupported expression'".to_string())],
+    evaluation.invalid_rules
+  );
+}
@@ -0,0 +1,105 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::formatter::format;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Utility function for formatting the decision table.
+fn fmt(decision_table: &str) -> String {
+  format(&recognize(&Plane::new(decision_table)).unwrap())
+}
+
+#[test]
+fn _0001() {
+  let expected = r#"
+┌───┬──────────╥───────────┐
+│ P │   Age    ║   Risk    │
+│   ├──────────╫───────────┤
+│   │          ║ "High",   │
+│   │          ║ "Medium", │
+│   │          ║ "Low"     │
+╞═══╪══════════╬═══════════╡
+│ 1 │ >=18     ║ "Low"     │
+├───┼──────────╫───────────┤
+│ 2 │ >=60     ║ "High"    │
+├───┼──────────╫───────────┤
+│ 3 │ [18..30] ║ "Medium"  │
+├───┼──────────╫───────────┤
+│ 4 │ <18      ║ "Medium"  │
+└───┴──────────╨───────────┘
+"#;
+  assert_eq!(expected.trim(), fmt(TEST_INPUT_002));
+}
+
+#[test]
+fn _0002() {
+  let expected = r#"
+┌────┬──────┬───────╥────────┐
+│ C+ │ Age  │ Score ║ Points │
+╞════╪══════╪═══════╬════════╡
+│ 1  │ >=18 │   -   ║     10 │
+├────┼──────┼───────╫────────┤
+│ 2  │ >=21 │ >50   ║      5 │
+├────┼──────┼───────╫────────┤
+│ 3  │ <18  │   -   ║      1 │
+├────┼──────┼───────╫────────┤
+│ 4  │  -   │ >50   ║      5 │
+└────┴──────┴───────╨────────┘
+"#;
+  assert_eq!(expected.trim(), fmt(TEST_INPUT_003));
+}
+
+#[test]
+fn _0003() {
+  let decision_table = format!(
+    "┌──────────────────────────────┐\n│ Points for age and score     │\n├────┬───────┬───────╥────────┴─────┐\n{}",
+    TEST_INPUT_003.trim().lines().skip(1).collect::<Vec<&str>>().join("\n")
+  );
+  let expected = r#"
+┌──────────────────────────┐
+│ Points for age and score │
+├────┬──────┬───────╥──────┴─┐
+│ C+ │ Age  │ Score ║ Points │
+╞════╪══════╪═══════╬════════╡
+"#;
+  assert!(fmt(&decision_table).starts_with(expected.trim()));
+}
+
+#[test]
+fn _0004() {
+  for decision_table in [TEST_INPUT_001, TEST_INPUT_002, TEST_INPUT_003] {
+    let formatted = fmt(decision_table);
+    assert_eq!(formatted, fmt(&formatted));
+  }
+}
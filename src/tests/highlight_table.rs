@@ -0,0 +1,136 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::highlighter::{highlight, theme, Style, MONOCHROME};
+
+const INPUT: &str = r#"
+┌─────────────────┐
+│ Order options   │
+├───┬───────────┬─┴─────╥──────────┐
+│ U │ Customer  │ Order ║ Discount │
+│   │   type    │ size  ║          │
+╞═══╪═══════════╪═══════╬══════════╡
+│ 1 │"Business" │ <10   ║   0.10   │
+├───┼───────────┼───────╫──────────┤
+│ 2 │ "Private" │   -   ║ [1..5]   │
+└───┴───────────┴───────╨──────────┘
+"#;
+
+/// Returns styles of characters in the plane, each style is denoted by a single letter.
+fn styles(input: &str) -> String {
+  let plane = Plane::new(input);
+  highlight(&plane)
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|style| match style {
+          Style::Text => '.',
+          Style::Frame => 'f',
+          Style::Separator => 'S',
+          Style::Header => 'h',
+          Style::HitPolicy => 'p',
+          Style::RuleNumber => 'r',
+          Style::StringLiteral => 's',
+          Style::NumberLiteral => 'n',
+          Style::Operator => 'o',
+          Style::Wildcard => 'w',
+        })
+        .collect::<String>()
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+#[test]
+fn _0001() {
+  let expected = r#"
+fffffffffffffffffff
+f.hhhhhhhhhhhhh...f
+ffffffffffffffffffffffffSfffffffffff
+f.p.f.hhhhhhhh..f.hhhhh.S.hhhhhhhh.f
+f...f...hhhh....f.hhhh..S..........f
+ffffffffffffffffffffffffSfffffffffff
+f.r.fssssssssss.f.onn...S...nnnn...f
+ffffffffffffffffffffffffSfffffffffff
+f.r.f.sssssssss.f...w...S.onoono...f
+ffffffffffffffffffffffffSfffffffffff"#;
+  assert_eq!(expected.trim(), styles(INPUT));
+}
+
+#[test]
+fn _0002() {
+  // names containing digits, negative numbers and escaped quotes in strings
+  let input = r#"
+┌───┬────────────────╥───────────┐
+│ U │ Input          ║ Output    │
+╞═══╪════════════════╬═══════════╡
+│ 1 │ != x1, >= -2.5 ║ "a\"b", 3 │
+└───┴────────────────╨───────────┘
+"#;
+  let expected = r#"
+fffffffffffffffffffffSffffffffffff
+f.p.f.hhhhh..........S.hhhhhh....f
+fffffffffffffffffffffSffffffffffff
+f.r.f.oo.....oo.nnnn.S.ssssss..n.f
+fffffffffffffffffffffSffffffffffff"#;
+  assert_eq!(expected.trim(), styles(input));
+}
+
+#[test]
+fn _0003() {
+  // only box-drawing characters are highlighted when the table is not recognized
+  let input = r#"
+┌───┬───────╥────────┐
+│ U │ Input ║ Output │
+│ 1 │ <10   ║ "low"  │
+└───┴───────╨────────┘
+"#;
+  let expected = r#"
+ffffffffffffSfffffffff
+f...f.......S........f
+f...f.......S........f
+ffffffffffffSfffffffff"#;
+  assert_eq!(expected.trim(), styles(input));
+}
+
+#[test]
+fn _0004() {
+  assert_eq!("dark", theme("dark").name);
+  assert_eq!("light", theme("light").name);
+  assert_eq!("dark", theme("unknown").name);
+  assert!(theme("monochrome").is_monochrome());
+  assert!(!theme("dark").is_monochrome());
+  assert!(MONOCHROME.appearance(Style::Header).bold);
+  assert_eq!(-1, MONOCHROME.appearance(Style::StringLiteral).color);
+}
@@ -0,0 +1,107 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::linter::{fix, lint};
+use crate::plane::Plane;
+
+/// Utility function for checking the decision table, returns positions and messages of all issues.
+fn issues(decision_table: &str) -> Vec<String> {
+  let plane = Plane::new(decision_table);
+  lint(&plane)
+    .into_iter()
+    .map(|issue| format!("{}:{}: {}", issue.row, issue.col, issue.message))
+    .collect()
+}
+
+#[test]
+fn _0001() {
+  assert!(issues(TEST_INPUT_001).is_empty());
+  assert!(issues(TEST_INPUT_002).is_empty());
+  assert!(issues(TEST_INPUT_003).is_empty());
+}
+
+#[test]
+fn _0002() {
+  let decision_table = TEST_INPUT_003
+    .replace("│ C+ │", "│ Z  │")
+    .replace(
+      "├────┼───────┼───────╫────────┤\n│ 3  │ <18   │",
+      "├────┼───────┬───────╫────────┤\n│ 2  │       │",
+    )
+    .replace("│ 4  │   -   │  >50  ║   5    │", "│ 4  │   -   │  >50  ║   5    ║");
+  assert_eq!(
+    vec![
+      "1:1: unknown hit policy 'Z'",
+      "5:13: '│' is not connected below",
+      "7:1: duplicated rule number '2', expected '3'",
+      "7:6: empty input entry in rule 3",
+      "9:30: '║' and the line above have different styles",
+      "10:30: '┘' and the line above have different styles",
+    ],
+    issues(&decision_table)
+  );
+}
+
+#[test]
+fn _0003() {
+  let decision_table = TEST_INPUT_003
+    .replace(
+      "├────┼───────┼───────╫────────┤\n│ 3  │ <18   │",
+      "├────┼───────┬───────╫────────┤\n│ 2  │       │",
+    )
+    .replace("║   10   │", "║   10  │");
+  assert_eq!(
+    vec![
+      "2:30: '╡' is not connected below",
+      "3:29: '│' is not connected above",
+      "3:29: '│' is not connected below",
+      "3:29: row has 30 characters, expected 31",
+      "4:30: '┤' is not connected above",
+      "5:13: '│' is not connected below",
+      "7:1: duplicated rule number '2', expected '3'",
+      "7:6: empty input entry in rule 3",
+    ],
+    issues(&decision_table)
+  );
+}
+
+#[test]
+fn _0004() {
+  let decision_table = TEST_INPUT_003.replace(
+    "├────┼───────┼───────╫────────┤\n│ 3  │ <18   │",
+    "├────┼───────┬───────╫────────┤\n│ 2  │       │",
+  );
+  let mut plane = Plane::new(&decision_table);
+  assert_eq!(3, fix(&mut plane));
+  eq(&plane, &TEST_INPUT_003.replace("│ 3  │ <18   │", "│ 3  │   -   │"));
+}
@@ -0,0 +1,162 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::linter::lint;
+use crate::recognizer::recognize;
+
+const INPUT: &str = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18  │  <50  ║   10   │
+├───┼───────┼───────╫────────┤
+│ 2 │ >=18  │ >=50  ║   5    │
+├───┼───────┼───────╫────────┤
+│ 3 │ <18   │   -   ║        │
+└───┴───────┴───────╨────────┘
+"#;
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(INPUT);
+  assert!(plane.cursor_move_to(3, 6));
+  assert!(plane.merge_cells().is_ok());
+  eq_cursor(3, 6, plane);
+  let expected = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18  │  <50  ║   10   │
+├───┤       ├───────╫────────┤
+│ 2 │       │ >=50  ║   5    │
+├───┼───────┼───────╫────────┤
+│ 3 │ <18   │   -   ║        │
+└───┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  // the merged entry is shared by both rules
+  let decision_table = recognize(plane).unwrap();
+  assert_eq!(decision_table.rules[0].input_entries[0], decision_table.rules[1].input_entries[0]);
+  assert_eq!(">=18", decision_table.rules[1].input_entries[0].text());
+  assert!(!decision_table.rules[1].owns(&decision_table.rules[1].input_entries[0]));
+  // merged cells are not reported as structural problems
+  assert_eq!(lint(&Plane::new(INPUT)).len(), lint(plane).len());
+  assert!(plane.split_cell().is_ok());
+  eq(plane, INPUT);
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(INPUT);
+  assert!(plane.cursor_move_to(3, 6));
+  assert!(plane.merge_cells().is_ok());
+  assert_eq!(
+    "merging cells failed with reason: 'entries have different text'",
+    plane.merge_cells().unwrap_err().to_string()
+  );
+  assert!(plane.cursor_move_to(5, 26));
+  assert!(plane.merge_cells().is_ok());
+  eq_cursor(5, 26, plane);
+  let expected = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18  │  <50  ║   10   │
+├───┤       ├───────╫────────┤
+│ 2 │       │ >=50  ║   5    │
+├───┼───────┼───────╢        │
+│ 3 │ <18   │   -   ║        │
+└───┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  // rules with merged cells are neither sorted nor moved
+  assert_eq!(
+    "sorting rules by 'Age' failed with reason: 'merged cells must be split before sorting'",
+    plane.sort_rules("Age").unwrap_err().to_string()
+  );
+  assert!(!plane.move_rule(true));
+}
+
+#[test]
+fn _0003() {
+  let input = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18, │  <50  ║   10   │
+├───┤ <65   ├───────╫────────┤
+│ 2 │       │ >=50  ║   5    │
+└───┴───────┴───────╨────────┘
+"#;
+  let plane = &mut Plane::new(input);
+  assert!(plane.cursor_move_to(4, 6));
+  assert!(plane.split_cell().is_ok());
+  eq_cursor(4, 6, plane);
+  let expected = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18, │  <50  ║   10   │
+│   │ <65   │       ║        │
+├───┼───────┼───────╫────────┤
+│ 2 │ >=18, │ >=50  ║   5    │
+│   │ <65   │       ║        │
+└───┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(INPUT);
+  assert!(plane.cursor_move_to(1, 6));
+  assert_eq!(
+    "merging cells failed with reason: 'the cursor is not placed in the rule entry'",
+    plane.merge_cells().unwrap_err().to_string()
+  );
+  assert!(plane.cursor_move_to(7, 6));
+  assert_eq!(
+    "merging cells failed with reason: 'there is no entry below'",
+    plane.merge_cells().unwrap_err().to_string()
+  );
+  assert_eq!(
+    "splitting cell failed with reason: 'the entry is not merged'",
+    plane.split_cell().unwrap_err().to_string()
+  );
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.cursor_move_to(3, 12));
+  assert_eq!(
+    "merging cells failed with reason: 'cells are merged only when rules are placed in rows'",
+    plane.merge_cells().unwrap_err().to_string()
+  );
+}
@@ -0,0 +1,214 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::linter::lint;
+use crate::merger::merge_documents;
+use crate::plane::Plane;
+
+const BASE: &str = r#"
+┌───┬───────────┬───────╥──────────┬──────────╥─────────────┐
+│ U │ Customer  │ Order ║ Discount │ Priority ║ Description │
+│   │ type      │ size  ║          │          ║             │
+╞═══╪═══════════╪═══════╬══════════╪══════════╬═════════════╡
+│ 1 │"Business" │  <10  ║   0.10   │ "Normal" ║ Small order │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 2 │"Business" │ >=10  ║   0.15   │ "High"   ║ Large order │
+├───┼───────────┼───────╫──────────┼──────────╫─────────────┤
+│ 3 │"Private"  │   -   ║   0.05   │ "Low"    ║ All orders  │
+└───┴───────────┴───────╨──────────┴──────────╨─────────────┘
+"#;
+
+#[test]
+fn _0001() {
+  // changes of different cells and added rules are merged, the table is formatted
+  let ours = BASE.replace("0.15   │ \"High\"", "0.20   │ \"High\"").replace(
+    "└───┴───────────┴───────╨──────────┴──────────╨─────────────┘",
+    "├───┼───────────┼───────╫──────────┼──────────╫─────────────┤\n│ 4 │\"Public\"   │   -   ║   0.00   │ \"Low\"    ║ Public      │\n└───┴───────────┴───────╨──────────┴──────────╨─────────────┘",
+  );
+  let theirs = BASE.replace("\"High\"  ", "\"Urgent\"").replace("0.10", "0.12");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  let expected = r#"
+┌───┬────────────┬───────╥─────────────────────╥─────────────┐
+│ U │  Customer  │ Order ║                     ║ Description │
+│   │    type    │ size  ╟──────────┬──────────╢             │
+│   │            │       ║ Discount │ Priority ║             │
+╞═══╪════════════╪═══════╬══════════╪══════════╬═════════════╡
+│ 1 │ "Business" │ <10   ║     0.12 │ "Normal" ║ Small order │
+├───┼────────────┼───────╫──────────┼──────────╫─────────────┤
+│ 2 │ "Business" │ >=10  ║     0.20 │ "Urgent" ║ Large order │
+├───┼────────────┼───────╫──────────┼──────────╫─────────────┤
+│ 3 │ "Private"  │   -   ║     0.05 │ "Low"    ║ All orders  │
+├───┼────────────┼───────╫──────────┼──────────╫─────────────┤
+│ 4 │ "Public"   │   -   ║     0.00 │ "Low"    ║ Public      │
+└───┴────────────┴───────╨──────────┴──────────╨─────────────┘
+"#;
+  assert_eq!(expected.trim(), merged.trim());
+  assert!(lint(&Plane::new(&merged)).is_empty());
+}
+
+#[test]
+fn _0002() {
+  // the same entry changed differently on both sides is marked as a conflict
+  let ours = BASE.replace("0.15", "0.20");
+  let theirs = BASE.replace("0.15", "0.25");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(1, conflicts);
+  let expected = r#"
+├───┼────────────┼───────╫────────────────┼──────────╫─────────────┤
+│ 2 │ "Business" │ >=10  ║ <<<<<<< ours   │ "High"   ║ Large order │
+│   │            │       ║ 0.20           │          ║             │
+│   │            │       ║ =======        │          ║             │
+│   │            │       ║ 0.25           │          ║             │
+│   │            │       ║ >>>>>>> theirs │          ║             │
+├───┼────────────┼───────╫────────────────┼──────────╫─────────────┤
+"#;
+  assert!(merged.contains(expected.trim()));
+  let issues = lint(&Plane::new(&merged));
+  assert_eq!(1, issues.len());
+  assert_eq!("unresolved merge conflict in rule 2", issues[0].message);
+}
+
+#[test]
+fn _0003() {
+  // the rule deleted on one side is deleted when not modified on the other side
+  let rule_3 = "├───┼───────────┼───────╫──────────┼──────────╫─────────────┤\n│ 3 │\"Private\"  │   -   ║   0.05   │ \"Low\"    ║ All orders  │\n";
+  let ours = BASE.replace(rule_3, "");
+  let theirs = BASE.replace("0.10", "0.12");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  assert!(!merged.contains("Private"));
+  assert!(merged.contains("0.12"));
+  // the rule deleted on one side and modified on the other is a conflict
+  let theirs = BASE.replace("0.05", "0.07");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(1, conflicts);
+  assert!(merged.contains("│ 3 │ \"Private\"  │   -   ║ <<<<<<< ours   │ \"Low\"    ║ All orders  │"));
+  assert!(merged.contains("║ (rule deleted) │"));
+}
+
+#[test]
+fn _0004() {
+  // clauses renamed on their side are merged
+  let theirs = BASE.replace("Description", "Notes      ");
+  let ours = BASE.replace("0.10", "0.12");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  assert!(merged.contains("║    Notes    │"));
+  assert!(!merged.contains("Description"));
+  // conflicting hit policies are marked in the hit policy cell
+  let ours = BASE.replace("│ U │", "│ F │");
+  let theirs = BASE.replace("│ U │", "│ A │").replace("0.10", "0.12");
+  let (merged, conflicts) = merge_documents(BASE, &ours, &theirs);
+  assert_eq!(1, conflicts);
+  assert!(merged.contains("│ <<<<<<< ours   │  Customer  │"));
+  assert!(merged.contains("│ F              │    type    │"));
+  assert!(merged.contains("│ A              │            │"));
+  assert!(merged.contains("│ >>>>>>> theirs │            │"));
+  assert!(merged.contains("║     0.12 │"));
+  let issues = lint(&Plane::new(&merged));
+  assert_eq!(1, issues.len());
+  assert_eq!((1, 1, "unresolved merge conflict"), (issues[0].row, issues[0].col, issues[0].message.as_str()));
+}
+
+#[test]
+fn _0005() {
+  // the table changed only on one side is taken unchanged, with its indentation and trailing whitespace
+  let base = format!("Discounts{}", BASE);
+  let ours = base
+    .lines()
+    .map(|line| {
+      if line.starts_with(['┌', '│', '╞', '├', '└']) {
+        format!("  {}", line)
+      } else {
+        line.to_string()
+      }
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+    .replace("0.15   │ \"High\"   ║ Large order │", "0.20   │ \"High\"   ║ Large order │  ")
+    + "\n";
+  let theirs = base.replace("Discounts", "Order discounts");
+  let (merged, conflicts) = merge_documents(&base, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  assert_eq!(ours.replace("Discounts", "Order discounts"), merged);
+  assert!(merged.contains("\n  │ 2 │\"Business\" │ >=10  ║   0.20   │ \"High\"   ║ Large order │  \n"));
+}
+
+#[test]
+fn _0006() {
+  // documents with text changed on both sides are merged line by line
+  let base = format!("Discounts{}Notes\n", BASE);
+  let ours = base.replace("Discounts", "Order discounts").replace("0.10", "0.12");
+  let theirs = base.replace("Discounts", "Customer discounts").replace("Notes", "Remarks");
+  let (merged, conflicts) = merge_documents(&base, &ours, &theirs);
+  assert_eq!(1, conflicts);
+  let expected = format!(
+    "<<<<<<< ours\nOrder discounts\n=======\nCustomer discounts\n>>>>>>> theirs{}Remarks\n",
+    BASE.replace("0.10", "0.12")
+  );
+  assert_eq!(expected, merged);
+  // documents with added decision tables are merged line by line
+  let rates = BASE.replace("Business", "Company ").replace("Private", "Person ").replace("Order", "Items");
+  let ours = format!("Rates{}{}", rates, base.replace("0.05", "0.07"));
+  let theirs = base.replace("Notes", "Remarks");
+  let (merged, conflicts) = merge_documents(&base, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  assert_eq!(format!("Rates{}Discounts{}Remarks\n", rates, BASE.replace("0.05", "0.07")), merged);
+}
+
+#[test]
+fn _0007() {
+  // crosstab tables are merged line by line
+  let base = r#"
+┌─────────────────┐
+│ Discount        │
+├───────╥─────────┴─────────┐
+│       ║  Customer type    │
+│       ╟──────────┬────────┤
+│ Order ║"Business"│"Private│
+╞═══════╬══════════╪════════╡
+│ <10   ║   0.10   │  0.05  │
+├───────╫──────────┼────────┤
+│ >=10  ║   0.15   │  0.05  │
+└───────╨──────────┴────────┘
+"#;
+  let ours = base.replace("0.10", "0.12");
+  let theirs = base.replace("0.15", "0.17");
+  let (merged, conflicts) = merge_documents(base, &ours, &theirs);
+  assert_eq!(0, conflicts);
+  assert_eq!(base.replace("0.10", "0.12").replace("0.15", "0.17"), merged);
+  let theirs = base.replace("0.10", "0.11");
+  let (merged, conflicts) = merge_documents(base, &ours, &theirs);
+  assert_eq!(1, conflicts);
+  assert!(merged.contains("<<<<<<< ours\n│ <10   ║   0.12   │  0.05  │\n=======\n│ <10   ║   0.11   │  0.05  │\n>>>>>>> theirs\n"));
+}
@@ -30,11 +30,32 @@
  * limitations under the License.
  */
 
+mod action_clipboard;
 mod action_delete;
+mod action_go_to;
 mod action_insert;
 mod action_move;
+mod action_name;
+mod action_rule;
+mod analyze_table;
+mod crosstab;
+mod diff_tables;
+mod evaluate_table;
+mod format_table;
+mod highlight_table;
+mod lint_table;
+mod merge_cells;
+mod merge_tables;
+mod model_decision_table;
+mod model_document;
 mod model_plane;
+mod rules_as_columns;
+mod search;
+mod sort_rules;
 mod split_line;
+mod split_view;
+mod test_cases;
+mod unicode;
 
 use crate::plane::Plane;
 use difference::Changeset;
@@ -102,6 +123,62 @@ const TEST_INPUT_001: &str = r#"
 └───┴───────────┴───────╨──────────┴──────────╨─────────────┴───────────┘
 "#;
 
+const TEST_INPUT_002: &str = r#"
+┌───┬──────────╥──────────┐
+│ P │   Age    ║   Risk   │
+│   ├──────────╫──────────┤
+│   │          ║ "High",  │
+│   │          ║"Medium", │
+│   │          ║ "Low"    │
+╞═══╪══════════╬══════════╡
+│ 1 │   >=18   ║ "Low"    │
+├───┼──────────╫──────────┤
+│ 2 │   >=60   ║ "High"   │
+├───┼──────────╫──────────┤
+│ 3 │ [18..30] ║ "Medium" │
+├───┼──────────╫──────────┤
+│ 4 │   <18    ║ "Medium" │
+└───┴──────────╨──────────┘
+"#;
+
+const TEST_INPUT_003: &str = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ >=18  │   -   ║   10   │
+├────┼───────┼───────╫────────┤
+│ 2  │ >=21  │  >50  ║   5    │
+├────┼───────┼───────╫────────┤
+│ 3  │ <18   │   -   ║   1    │
+├────┼───────┼───────╫────────┤
+│ 4  │   -   │  >50  ║   5    │
+└────┴───────┴───────╨────────┘
+"#;
+
+const TEST_INPUT_004: &str = r#"
+┌────────╥──────┬──────┬──────┬──────┐
+│ C+     ║  1   │  2   │  3   │  4   │
+├────────╫──────┼──────┼──────┼──────┤
+│ Age    ║ >=18 │ >=21 │ <18  │  -   │
+├────────╫──────┼──────┼──────┼──────┤
+│ Score  ║  -   │ >50  │  -   │ >50  │
+╞════════╬══════╪══════╪══════╪══════╡
+│ Points ║  10  │  5   │  1   │  5   │
+└────────╨──────┴──────┴──────┴──────┘
+"#;
+
+const TEST_INPUT_005: &str = r#"
+┌───────────────────╥────────────────────────┐
+│ Discount          ║     Customer type      │
+│                   ╟────────────┬───────────┤
+│                   ║ "Business" │ "Private" │
+╞════════════╤══════╬════════════╪═══════════╡
+│ Order size │ <10  ║       0.10 │      0.05 │
+│            ├──────╫────────────┼───────────┤
+│            │ >=10 ║       0.15 │      0.05 │
+└────────────┴──────╨────────────┴───────────┘
+"#;
+
 const TEST_INPUT_X: &str = r#"
 ┌─────────────────────────────────────┐
 │abcdef_hijklmnopqrstuvwxyzABCDEFGHIJK│
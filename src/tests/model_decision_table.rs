@@ -0,0 +1,105 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::model::HitPolicy;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+#[test]
+fn _0001() {
+  let plane = Plane::new(TEST_INPUT_001);
+  let decision_table = recognize(&plane).unwrap();
+  assert_eq!("Order options", decision_table.information_item_name.unwrap().text());
+  assert_eq!(HitPolicy::Unique, decision_table.hit_policy);
+  assert_eq!("Order options", decision_table.output_label.unwrap().text());
+  assert_eq!(2, decision_table.input_clauses.len());
+  assert_eq!("Customer type", decision_table.input_clauses[0].input_expression.text());
+  assert_eq!(
+    r#""Business", "Private""#,
+    decision_table.input_clauses[0].allowed_values.as_ref().unwrap().text()
+  );
+  assert_eq!("Order size", decision_table.input_clauses[1].input_expression.text());
+  assert_eq!("<10, >=10", decision_table.input_clauses[1].allowed_values.as_ref().unwrap().text());
+  assert_eq!(2, decision_table.output_clauses.len());
+  assert_eq!("Discount", decision_table.output_clauses[0].name.as_ref().unwrap().text());
+  assert_eq!("Priority", decision_table.output_clauses[1].name.as_ref().unwrap().text());
+  assert_eq!(2, decision_table.annotation_clauses.len());
+  assert_eq!("Description", decision_table.annotation_clauses[0].name.text());
+  assert_eq!("Reference", decision_table.annotation_clauses[1].name.text());
+  assert_eq!(3, decision_table.rules.len());
+  let rule = &decision_table.rules[1];
+  assert_eq!("2", rule.number.text());
+  assert_eq!(r#""Business""#, rule.input_entries[0].text());
+  assert_eq!(">=10", rule.input_entries[1].text());
+  assert_eq!("0.15", rule.output_entries[0].text());
+  assert_eq!(r#""High""#, rule.output_entries[1].text());
+  assert_eq!("Large order", rule.annotation_entries[0].text());
+  assert_eq!((13, 13), (rule.region.top, rule.region.bottom));
+}
+
+#[test]
+fn _0002() {
+  let plane = Plane::new(TEST_INPUT_002);
+  let decision_table = recognize(&plane).unwrap();
+  assert!(decision_table.information_item_name.is_none());
+  assert_eq!(HitPolicy::Priority, decision_table.hit_policy);
+  assert_eq!("Risk", decision_table.output_label.unwrap().text());
+  assert_eq!(1, decision_table.output_clauses.len());
+  assert!(decision_table.output_clauses[0].name.is_none());
+  assert_eq!(
+    vec![r#""High","#, r#""Medium","#, r#""Low""#],
+    decision_table.output_clauses[0].allowed_values.as_ref().unwrap().lines
+  );
+  assert_eq!(4, decision_table.rules.len());
+}
+
+#[test]
+fn _0003() {
+  let plane = Plane::new(&TEST_INPUT_003.replace("C+", "X "));
+  assert_eq!("invalid hit policy: 'X'", recognize(&plane).unwrap_err().to_string());
+}
+
+#[test]
+fn _0004() {
+  let plane = Plane::new(
+    r#"
+┌───┬───────╥──────┐
+│ U │ Age   ║ Risk │
+└───┴───────╨──────┘
+"#,
+  );
+  assert_eq!(
+    "recognizing decision table failed with reason: 'missing line separating the header from rules'",
+    recognize(&plane).unwrap_err().to_string()
+  );
+}
@@ -0,0 +1,134 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::document::Document;
+use crate::plane::Plane;
+
+/// Utility function for building a document with two decision tables and text around them.
+fn content() -> String {
+  format!("Order options\n{}\nRisk\n\n{}\nThe end.\n", TEST_INPUT_001.trim(), TEST_INPUT_002.trim())
+}
+
+#[test]
+fn _0001() {
+  let document = Document::new(&content());
+  assert_eq!(2, document.table_count());
+  assert_eq!(1, document.table_top(0));
+  assert_eq!(20, document.table_top(1));
+  assert_eq!((21, 2), document.origin(1, 0, 1));
+  assert_eq!(content(), document.to_string());
+}
+
+#[test]
+fn _0002() {
+  let mut document = Document::new(&content());
+  assert!(document.select_table(1));
+  assert!(!document.select_table(2));
+  assert_eq!(1, document.current_table());
+  let plane = document.plane_mut();
+  repeat!(3, plane, cursor_move_right);
+  plane.insert_char('x');
+  eq(document.plane(), &TEST_INPUT_002.replace("│ P │   Age    ║", "│ P │x   Age   ║"));
+  eq(document.planes().next().unwrap(), TEST_INPUT_001);
+}
+
+#[test]
+fn _0003() {
+  let mut document = Document::new(&content());
+  let plane = document.plane_mut();
+  repeat!(2, plane, cursor_move_right);
+  rep(25, || plane.insert_char('x'));
+  assert_eq!(TEST_INPUT_001.trim().lines().count(), document.plane().chars.len());
+  let rows = document.rows();
+  assert_eq!("Risk", rows[18]);
+  assert_eq!(TEST_INPUT_002.trim().lines().next().unwrap(), rows[20]);
+}
+
+#[test]
+fn _0004() {
+  let document = Document::new(TEST_INPUT_X);
+  assert_eq!(1, document.table_count());
+  let document = Document::new("no decision table here");
+  assert_eq!(1, document.table_count());
+}
+
+#[test]
+fn _0005() {
+  let table: Vec<String> = TEST_INPUT_003.trim().lines().map(|line| format!("  {}  ", line)).collect();
+  let content = format!("\u{feff}Points\r\n\r\n{}\r\n\r\n  \r\nend", table.join("\r\n"));
+  let document = Document::new(&content);
+  assert_eq!(content, document.to_string());
+  assert_eq!(2, document.margin(0));
+  assert!(document.rows()[2].starts_with("  ┌────┬"));
+}
+
+#[test]
+fn _0006() {
+  let content = include_str!("../../examples/e1.dtb");
+  assert_eq!(content, Document::new(content).to_string());
+}
+
+#[test]
+fn _0007() {
+  let table: Vec<String> = TEST_INPUT_003.trim().lines().map(|line| format!("   {} ", line)).collect();
+  let mut document = Document::new(&format!("{}\n", table.join("\n")));
+  document.replace_table(0, Plane::new(TEST_INPUT_002));
+  let expected: Vec<String> = TEST_INPUT_002.trim().lines().map(|line| format!("   {}", line)).collect();
+  assert_eq!(format!("{}\n", expected.join("\n")), document.to_string());
+}
+
+#[test]
+fn _0008() {
+  // content without decision tables is kept unchanged
+  for content in ["  hello\n\n  world\n", "hello", "", "\n\n"] {
+    let document = Document::new(content);
+    assert_eq!(content, document.to_string());
+    assert_eq!(1, document.table_count());
+  }
+}
+
+#[test]
+fn _0009() {
+  // each line keeps its own line ending, rows added while editing end like the first line
+  for content in ["a\nb\r\nc\n", "a\r\nb\nc\r\n", "a\r\n\r\nb"] {
+    assert_eq!(content, Document::new(content).to_string());
+  }
+  let table: Vec<&str> = TEST_INPUT_003.trim().lines().collect();
+  let content = format!("text\r\n{}\nend\n", table.join("\r\n"));
+  let mut document = Document::new(&content);
+  assert_eq!(content, document.to_string());
+  assert!(!document.rows().iter().any(|row| row.contains('\r')));
+  document.replace_table(0, Plane::new(TEST_INPUT_002));
+  let expected: Vec<&str> = TEST_INPUT_002.trim().lines().collect();
+  assert_eq!(format!("text\r\n{}\r\nend\n", expected.join("\r\n")), document.to_string());
+}
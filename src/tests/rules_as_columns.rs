@@ -0,0 +1,106 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::evaluator::evaluate;
+use crate::feel::Value;
+use crate::formatter::format;
+use crate::linter::lint;
+use crate::model::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+
+/// Utility function for recognizing the decision table.
+fn table(decision_table: &str) -> DecisionTable {
+  recognize(&Plane::new(decision_table)).unwrap()
+}
+
+/// Utility function for rendering the decision table with switched orientation.
+fn pivot(decision_table: &str) -> String {
+  let mut decision_table = table(decision_table);
+  decision_table.orientation = match decision_table.orientation {
+    Orientation::RuleAsRow => Orientation::RuleAsColumn,
+    Orientation::RuleAsColumn | Orientation::CrossTable => Orientation::RuleAsRow,
+  };
+  format(&decision_table)
+}
+
+#[test]
+fn _0001() {
+  let dt = table(TEST_INPUT_004);
+  assert_eq!(Orientation::RuleAsColumn, dt.orientation);
+  assert_eq!(HitPolicy::Collect(Some(BuiltinAggregator::Sum)), dt.hit_policy);
+  let inputs: Vec<String> = dt.input_clauses.iter().map(|clause| clause.input_expression.text()).collect();
+  assert_eq!(vec!["Age", "Score"], inputs);
+  assert_eq!(vec!["Points"], dt.output_names());
+  assert_eq!(4, dt.rules.len());
+  let rule = &dt.rules[1];
+  assert_eq!("2", rule.number.text());
+  assert_eq!(vec![">=21", ">50"], rule.input_entries.iter().map(Cell::text).collect::<Vec<String>>());
+  assert_eq!("5", rule.output_entries[0].text());
+  assert_eq!((1, 17, 7, 22), (rule.region.top, rule.region.left, rule.region.bottom, rule.region.right));
+  assert_eq!(2, dt.body_top);
+  assert_eq!(8, dt.bottom);
+}
+
+#[test]
+fn _0002() {
+  let evaluation = evaluate(&table(TEST_INPUT_004), &[Value::Number(25.0), Value::Number(60.0)]).unwrap();
+  assert_eq!(vec![0, 1, 3], evaluation.matched_rules);
+  assert_eq!(Value::Number(20.0), evaluation.result.unwrap());
+}
+
+#[test]
+fn _0003() {
+  let expected = r#"
+┌────────╥──────┬──────┬─────┬─────┐
+│ C+     ║ 1    │ 2    │ 3   │ 4   │
+├────────╫──────┼──────┼─────┼─────┤
+│ Age    ║ >=18 │ >=21 │ <18 │  -  │
+├────────╫──────┼──────┼─────┼─────┤
+│ Score  ║  -   │ >50  │  -  │ >50 │
+╞════════╬══════╪══════╪═════╪═════╡
+│ Points ║   10 │    5 │   1 │   5 │
+└────────╨──────┴──────┴─────┴─────┘
+"#;
+  assert_eq!(expected.trim(), pivot(TEST_INPUT_003));
+  assert_eq!(format(&table(TEST_INPUT_003)), pivot(expected));
+  assert_eq!(expected.trim(), format(&table(TEST_INPUT_004)));
+}
+
+#[test]
+fn _0004() {
+  assert!(lint(&Plane::new(TEST_INPUT_004)).is_empty());
+  let formatted = pivot(TEST_INPUT_001);
+  assert!(lint(&Plane::new(&formatted)).is_empty());
+  assert_eq!(formatted, format(&table(&formatted)));
+}
@@ -0,0 +1,118 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::model::Region;
+use crate::plane::SearchScope;
+
+/// Utility function for creating a single line region.
+fn found(row: usize, left: usize, right: usize) -> Region {
+  Region {
+    top: row,
+    left,
+    bottom: row,
+    right,
+  }
+}
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert_eq!(vec![found(5, 17, 18), found(9, 17, 18)], plane.find("50", SearchScope::Table));
+  assert_eq!(
+    vec![found(3, 7, 7), found(5, 7, 7), found(5, 16, 16), found(9, 16, 16)],
+    plane.find(">", SearchScope::Table)
+  );
+  assert_eq!(
+    vec![found(5, 17, 17), found(5, 25, 25), found(9, 17, 17), found(9, 25, 25)],
+    plane.find("5", SearchScope::Table)
+  );
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  // box-drawing characters are never matched
+  assert!(plane.find("│ 1", SearchScope::Table).is_empty());
+  assert!(plane.find("", SearchScope::Table).is_empty());
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(7, 17));
+  assert_eq!(vec![found(5, 17, 17), found(9, 17, 17)], plane.find("5", SearchScope::Clause));
+  assert!(plane.cursor_move_to(1, 25));
+  assert_eq!(vec![found(5, 25, 25), found(9, 25, 25)], plane.find("5", SearchScope::Clause));
+  assert!(plane.cursor_move_to(1, 2));
+  assert!(plane.find("5", SearchScope::Clause).is_empty());
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  for region in plane.find(">", SearchScope::Table).into_iter().rev() {
+    assert!(plane.replace(region, "greater than "));
+  }
+  eq_cursor(3, 20, plane);
+  let expected = r#"
+┌────┬─────────────────┬─────────────────╥────────┐
+│ C+ │  Age            │ Score           ║ Points │
+╞════╪═════════════════╪═════════════════╬════════╡
+│ 1  │ greater than =18│   -             ║   10   │
+├────┼─────────────────┼─────────────────╫────────┤
+│ 2  │ greater than =21│  greater than 50║   5    │
+├────┼─────────────────┼─────────────────╫────────┤
+│ 3  │ <18             │   -             ║   1    │
+├────┼─────────────────┼─────────────────╫────────┤
+│ 4  │   -             │  greater than 50║   5    │
+└────┴─────────────────┴─────────────────╨────────┘
+"#;
+  eq(plane, expected);
+  for region in plane.find("greater than ", SearchScope::Table).into_iter().rev() {
+    assert!(plane.replace(region, ""));
+  }
+  let expected = r#"
+┌────┬─────┬──────╥────────┐
+│ C+ │  Age│ Score║ Points │
+╞════╪═════╪══════╬════════╡
+│ 1  │ =18 │   -  ║   10   │
+├────┼─────┼──────╫────────┤
+│ 2  │ =21 │  50  ║   5    │
+├────┼─────┼──────╫────────┤
+│ 3  │ <18 │   -  ║   1    │
+├────┼─────┼──────╫────────┤
+│ 4  │   - │  50  ║   5    │
+└────┴─────┴──────╨────────┘
+"#;
+  eq(plane, expected);
+}
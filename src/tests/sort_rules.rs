@@ -0,0 +1,196 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[test]
+fn _0001() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert!(plane.cursor_move_to(5, 10));
+  assert_eq!(None, plane.sort_rules("Age").unwrap());
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ C+ │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ <18   │   -   ║   1    │
+├────┼───────┼───────╫────────┤
+│ 2  │ >=18  │   -   ║   10   │
+├────┼───────┼───────╫────────┤
+│ 3  │ >=21  │  >50  ║   5    │
+├────┼───────┼───────╫────────┤
+│ 4  │   -   │  >50  ║   5    │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+  // the cursor follows the sorted rule
+  eq_cursor(7, 10, plane);
+  assert!(plane.sort_rules("age DESC").is_ok());
+  assert!(plane.to_string().contains("│ 1  │ >=21  │  >50  ║   5    │"));
+  assert!(plane.to_string().contains("│ 4  │   -   │  >50  ║   5    │"));
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT_002);
+  assert!(plane.sort_rules("Risk, Age").is_ok());
+  let expected = r#"
+┌───┬──────────╥──────────┐
+│ P │   Age    ║   Risk   │
+│   ├──────────╫──────────┤
+│   │          ║ "High",  │
+│   │          ║"Medium", │
+│   │          ║ "Low"    │
+╞═══╪══════════╬══════════╡
+│ 1 │   >=60   ║ "High"   │
+├───┼──────────╫──────────┤
+│ 2 │   <18    ║ "Medium" │
+├───┼──────────╫──────────┤
+│ 3 │ [18..30] ║ "Medium" │
+├───┼──────────╫──────────┤
+│ 4 │   >=18   ║ "Low"    │
+└───┴──────────╨──────────┘
+"#;
+  eq(plane, expected);
+  assert!(plane.sort_rules("Risk desc, Age desc").is_ok());
+  assert!(plane
+    .to_string()
+    .contains("│ 1 │   >=18   ║ \"Low\"    │\n├───┼──────────╫──────────┤\n│ 2 │ [18..30] ║"));
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT_004);
+  assert!(plane.sort_rules("Points desc, Age").is_ok());
+  let expected = r#"
+┌────────╥──────┬──────┬──────┬──────┐
+│ C+     ║  1   │  2   │  3   │  4   │
+├────────╫──────┼──────┼──────┼──────┤
+│ Age    ║ >=18 │ >=21 │  -   │ <18  │
+├────────╫──────┼──────┼──────┼──────┤
+│ Score  ║  -   │ >50  │ >50  │  -   │
+╞════════╬══════╪══════╪══════╪══════╡
+│ Points ║  10  │  5   │  5   │  1   │
+└────────╨──────┴──────┴──────┴──────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0004() {
+  let input = r#"
+┌───┬───────┬───────╥────────┐
+│ U │  Age  │ Score ║ Points │
+╞═══╪═══════╪═══════╬════════╡
+│ 1 │ >=18  │  <50  ║   10   │
+├───┼───────┼───────╫────────┤
+│ 2 │ <18,  │  >=50 ║   5    │
+│   │ >=80  │       ║        │
+│   │       │       ║        │
+├───┼───────┼───────╫────────┤
+│ 3 │ <18   │  <50  ║   1    │
+│   │       │       ║        │
+├───┼───────┼───────╫────────┤
+│ 4 │ >=18  │  >=50 ║   2    │
+├───┼───────┼───────╫────────┤
+│ 5 │ >=18  │  >=50 ║   2    │
+├───┼───────┼───────╫────────┤
+│ 6 │ >=18  │  >=50 ║   2    │
+├───┼───────┼───────╫────────┤
+│ 7 │ >=18  │  >=50 ║   2    │
+├───┼───────┼───────╫────────┤
+│ 8 │ >=18  │  >=50 ║   2    │
+├───┼───────┼───────╫────────┤
+│ 9 │ >=18  │  >=50 ║   3    │
+├───┼───────┼───────╫────────┤
+│ 10│ <18   │  >=50 ║   4    │
+└───┴───────┴───────╨────────┘
+"#;
+  let plane = &mut Plane::new(input);
+  assert!(plane.sort_rules("Score, Points desc").is_ok());
+  let expected = r#"
+┌────┬───────┬───────╥────────┐
+│ U  │  Age  │ Score ║ Points │
+╞════╪═══════╪═══════╬════════╡
+│ 1  │ >=18  │  <50  ║   10   │
+├────┼───────┼───────╫────────┤
+│ 2  │ <18   │  <50  ║   1    │
+│    │       │       ║        │
+├────┼───────┼───────╫────────┤
+│ 3  │ <18,  │  >=50 ║   5    │
+│    │ >=80  │       ║        │
+│    │       │       ║        │
+├────┼───────┼───────╫────────┤
+│ 4  │ <18   │  >=50 ║   4    │
+├────┼───────┼───────╫────────┤
+│ 5  │ >=18  │  >=50 ║   3    │
+├────┼───────┼───────╫────────┤
+│ 6  │ >=18  │  >=50 ║   2    │
+├────┼───────┼───────╫────────┤
+│ 7  │ >=18  │  >=50 ║   2    │
+├────┼───────┼───────╫────────┤
+│ 8  │ >=18  │  >=50 ║   2    │
+├────┼───────┼───────╫────────┤
+│ 9  │ >=18  │  >=50 ║   2    │
+├────┼───────┼───────╫────────┤
+│ 10 │ >=18  │  >=50 ║   2    │
+└────┴───────┴───────╨────────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT_003);
+  assert_eq!(
+    "sorting rules by 'Age, Height' failed with reason: 'unknown clause 'Height''",
+    plane.sort_rules("Age, Height").unwrap_err().to_string()
+  );
+  assert_eq!(
+    "sorting rules by 'Age,, Score' failed with reason: 'expected clause name'",
+    plane.sort_rules("Age,, Score").unwrap_err().to_string()
+  );
+  let plane = &mut Plane::new(&TEST_INPUT_003.replace("│ C+ │", "│ F  │"));
+  assert_eq!(
+    "sorting rules by 'Age' failed with reason: 'the order of rules is significant for hit policy F'",
+    plane.sort_rules("Age").unwrap_err().to_string()
+  );
+  let plane = &mut Plane::new(&TEST_INPUT_003.replace("│ C+ │", "│ R  │"));
+  assert_eq!(
+    Some("hit policy R returns outputs in the new order of rules".to_string()),
+    plane.sort_rules("Age").unwrap()
+  );
+  let plane = &mut Plane::new(TEST_INPUT_005);
+  assert_eq!(
+    "sorting rules by 'Order size' failed with reason: 'rules of crosstab tables can not be sorted'",
+    plane.sort_rules("Order size").unwrap_err().to_string()
+  );
+}
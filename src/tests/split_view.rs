@@ -0,0 +1,158 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::document::Document;
+use crate::views::{Area, Cursor, Split, Views};
+
+/// Utility function for building a document with two decision tables.
+fn document() -> Document {
+  Document::new(&format!("{}\n\n{}\n", TEST_INPUT_001.trim(), TEST_INPUT_002.trim()))
+}
+
+#[test]
+fn _0001() {
+  let mut views = Views::new(0);
+  assert_eq!(
+    Area {
+      top: 0,
+      left: 0,
+      height: 24,
+      width: 80
+    },
+    views.area(0, 24, 80)
+  );
+  views.split_in(Split::Horizontal, Cursor::default());
+  assert_eq!(2, views.count());
+  assert_eq!(
+    Area {
+      top: 0,
+      left: 0,
+      height: 11,
+      width: 80
+    },
+    views.area(0, 24, 80)
+  );
+  assert_eq!(
+    Area {
+      top: 12,
+      left: 0,
+      height: 12,
+      width: 80
+    },
+    views.area(1, 24, 80)
+  );
+  views.split_in(Split::Vertical, Cursor::default());
+  assert_eq!(2, views.count());
+  assert_eq!(
+    Area {
+      top: 0,
+      left: 0,
+      height: 24,
+      width: 39
+    },
+    views.area(0, 24, 80)
+  );
+  assert_eq!(
+    Area {
+      top: 0,
+      left: 40,
+      height: 24,
+      width: 40
+    },
+    views.area(1, 24, 80)
+  );
+  // splitting again in the same direction closes the view not having the focus
+  views.split_in(Split::Vertical, Cursor::default());
+  assert_eq!(1, views.count());
+  assert_eq!(None, views.split());
+  assert_eq!(None, views.switch(Cursor::default()));
+}
+
+#[test]
+fn _0002() {
+  // two views of the same document keep their own cursor positions
+  let mut document = document();
+  let mut views = Views::new(0);
+  assert!(document.plane_mut().cursor_move_to(11, 2));
+  views.split_in(Split::Horizontal, Cursor::of(&document));
+  assert!(document.select_table(1));
+  assert!(document.plane_mut().cursor_move_to(3, 6));
+  let cursor = views.switch(Cursor::of(&document)).unwrap();
+  assert_eq!(1, views.current());
+  assert_eq!(Cursor { table: 0, row: 11, col: 2 }, cursor);
+  cursor.restore(&mut document);
+  assert_eq!(0, document.current_table());
+  eq_cursor(11, 2, document.plane());
+  repeat!(2, document.plane_mut(), cursor_move_down);
+  let cursor = views.switch(Cursor::of(&document)).unwrap();
+  assert_eq!(0, views.current());
+  cursor.restore(&mut document);
+  assert_eq!(1, document.current_table());
+  eq_cursor(3, 6, document.plane());
+  let cursor = views.switch(Cursor::of(&document)).unwrap();
+  cursor.restore(&mut document);
+  assert_eq!(0, document.current_table());
+  eq_cursor(15, 2, document.plane());
+}
+
+#[test]
+fn _0003() {
+  // positions no longer allowed are skipped
+  let mut document = document();
+  Cursor { table: 1, row: 0, col: 0 }.restore(&mut document);
+  assert_eq!(1, document.current_table());
+  eq_cursor(1, 1, document.plane());
+  Cursor { table: 2, row: 3, col: 3 }.restore(&mut document);
+  assert_eq!(1, document.current_table());
+  eq_cursor(1, 1, document.plane());
+}
+
+#[test]
+fn _0004() {
+  let mut views = Views::new(0);
+  let area = Area {
+    top: 0,
+    left: 0,
+    height: 10,
+    width: 20,
+  };
+  assert!(!views.view_mut().scroll_to(9, 19, &area));
+  assert!(views.view_mut().scroll_to(12, 25, &area));
+  assert_eq!((3, 6), (views.view().top_row, views.view().left_col));
+  assert!(views.view_mut().scroll_to(1, 2, &area));
+  assert_eq!((1, 2), (views.view().top_row, views.view().left_col));
+  // selecting another buffer displays it from the beginning
+  assert!(!views.select_buffer(0));
+  assert!(views.select_buffer(1));
+  assert_eq!((1, 0, 0), (views.view().buffer, views.view().top_row, views.view().left_col));
+}
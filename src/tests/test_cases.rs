@@ -0,0 +1,146 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::plane::Plane;
+use crate::recognizer::recognize;
+use crate::testing::*;
+
+/// Utility function for running test cases against the decision table.
+fn run(decision_table: &str, file_name: &str, content: &str) -> (String, bool) {
+  let plane = Plane::new(decision_table);
+  let decision_table = recognize(&plane).unwrap();
+  let test_cases = parse_test_cases(file_name, content).unwrap();
+  let (report, passed) = run_test_cases(&decision_table, &test_cases).unwrap();
+  (report.join("\n"), passed)
+}
+
+#[test]
+fn _0001() {
+  let content = r#"
+Customer type,Order size,Discount,Priority
+"Business",9,0.10,"Normal"
+"Business",10,0.15,
+Private,10,0.05,"High"
+"#;
+  let expected = r#"test case 1 ... ok
+test case 2 ... ok
+test case 3 ... FAILED
+  Priority: expected "High", actual "Low"
+
+test result: FAILED. 2 passed; 1 failed"#;
+  assert_eq!((expected.to_string(), false), run(TEST_INPUT_001, "cases.csv", content));
+}
+
+#[test]
+fn _0002() {
+  let content = r#"
+# collected points
+- Age: 25
+  Score: 60
+  Points: 20
+- Age: 10
+  Score: ~
+  Points: 1
+"#;
+  let expected = r#"test case 1 ... ok
+test case 2 ... ok
+
+test result: ok. 2 passed; 0 failed"#;
+  assert_eq!((expected.to_string(), true), run(TEST_INPUT_003, "cases.yaml", content));
+  let content = "- Age: 10\n  Score: 60\n  Points: [1, 5]\n";
+  let expected = "test case 1 ... ok\n\ntest result: ok. 1 passed; 0 failed";
+  assert_eq!((expected.to_string(), true), run(&TEST_INPUT_003.replace("C+", "R "), "cases.yml", content));
+}
+
+#[test]
+fn _0003() {
+  let plane = Plane::new(TEST_INPUT_003);
+  let decision_table = recognize(&plane).unwrap();
+  let test_cases = parse_csv("Age,Weight\n10,20").unwrap();
+  assert_eq!(
+    "invalid test cases: 'unknown name 'Weight' in test case 1'",
+    run_test_cases(&decision_table, &test_cases).unwrap_err().to_string()
+  );
+  assert_eq!(
+    "invalid test cases: 'expected 2 values in test case 1, found 3'",
+    parse_csv("Age,Score\n10,20,30").unwrap_err().to_string()
+  );
+  assert_eq!(
+    "invalid test cases: 'expected '-' before the first test case in line 1'",
+    parse_yaml("Age: 10").unwrap_err().to_string()
+  );
+}
+
+#[test]
+fn _0004() {
+  // the test case failing to evaluate is reported, remaining test cases are run
+  let decision_table = TEST_INPUT_003.replace("│ 3  │ <18   │   -   ║   1    │", "│ 3  │ <18   │   -   ║  abc   │");
+  let content = "Age,Score,Points\n10,20,1\n25,60,20\n";
+  let expected = r#"test case 1 ... FAILED
+  parsing 'abc' failed with reason: 'unsupported expression'
+test case 2 ... ok
+
+test result: FAILED. 1 passed; 1 failed"#;
+  assert_eq!((expected.to_string(), false), run(&decision_table, "cases.csv", content));
+}
+
+#[test]
+fn _0005() {
+  // the rule with invalid input entry is reported once, test cases are run with remaining rules
+  let decision_table = TEST_INPUT_003.replace("│ 2  │ >=21  │  >50  ║", "│ 2  │ >=21  │ >>50  ║");
+  let content = "Age,Score,Points\n10,20,1\n25,60,15\n";
+  let expected = r#"test case 1 ... ok
+test case 2 ... ok
+
+rule 2 not evaluated: parsing '>50' failed with reason: 'unsupported expression'
+test result: ok. 2 passed; 0 failed"#;
+  assert_eq!((expected.to_string(), true), run(&decision_table, "cases.csv", content));
+}
+
+#[test]
+fn _0006() {
+  // quoted fields may contain commas, doubled quotes and new lines
+  let content = "Name,Note\r\n\"Smith, John\",\"say \"\"hi\"\"\"\r\n\"two\nlines\", [1, 5]\r\n";
+  let name_note = |name: &str, note: &str| vec![("Name".to_string(), name.to_string()), ("Note".to_string(), note.to_string())];
+  assert_eq!(
+    vec![name_note("Smith, John", "say \"hi\""), name_note("two\nlines", "[1, 5]")],
+    parse_csv(content).unwrap()
+  );
+  assert_eq!(
+    "invalid test cases: 'missing closing quote'",
+    parse_csv("Name\n\"Smith").unwrap_err().to_string()
+  );
+  let content = "Customer type,Order size,Discount,Priority\n\"\"\"Business\"\"\",9,0.10,\"\"\"Normal\"\"\"\n";
+  let expected = "test case 1 ... ok\n\ntest result: ok. 1 passed; 0 failed";
+  assert_eq!((expected.to_string(), true), run(TEST_INPUT_001, "cases.csv", content));
+}
@@ -0,0 +1,255 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::document::Document;
+use crate::formatter::format;
+use crate::model::Region;
+use crate::plane::{Register, SearchScope, SelectionMode};
+use crate::recognizer::recognize;
+use crate::unicode::{clip_columns, compose, text_width, Clusters, CH_WIDE};
+
+const TEST_INPUT: &str = r#"
+┌──────────────────┐
+│ 割引 (discount)  │
+├───┬──────────┬───┴──╥───────┐
+│ U │ 顧客     │ 注文 ║ 割引  │
+╞═══╪══════════╪══════╬═══════╡
+│ 1 │ "企業"   │ <10  ║ 0.10  │
+├───┼──────────┼──────╫───────┤
+│ 2 │ "Łódź"   │ >=10 ║ 0.15  │
+└───┴──────────┴──────╨───────┘
+"#;
+
+#[test]
+fn _0001() {
+  assert_eq!(6, text_width("割引ab"));
+  assert_eq!(4, text_width("Łódź"));
+  assert_eq!(1, text_width("e\u{0301}"));
+  assert_eq!(Some('ą'), compose('a', '\u{0328}'));
+  assert_eq!(None, compose('x', '\u{0328}'));
+}
+
+#[test]
+fn _0002() {
+  let plane = &mut Plane::new(TEST_INPUT);
+  eq(plane, TEST_INPUT);
+  let decision_table = recognize(plane).unwrap();
+  assert_eq!("割引 (discount)", decision_table.information_item_name.unwrap().text());
+  assert_eq!("顧客", decision_table.input_clauses[0].input_expression.text());
+}
+
+#[test]
+fn _0003() {
+  let plane = &mut Plane::new(TEST_INPUT);
+  assert!(plane.cursor_move_to(3, 6));
+  plane.cursor_move_right();
+  eq_cursor(3, 8, plane);
+  plane.cursor_move_right();
+  eq_cursor(3, 10, plane);
+  plane.cursor_move_left();
+  eq_cursor(3, 8, plane);
+  plane.cursor_move_down();
+  eq_cursor(5, 7, plane);
+  assert!(plane.cursor_move_to(3, 18));
+  eq_cursor(3, 17, plane);
+  plane.cursor_move_right();
+  eq_cursor(3, 19, plane);
+  plane.cursor_move_left();
+  eq_cursor(3, 17, plane);
+}
+
+#[test]
+fn _0004() {
+  let plane = &mut Plane::new(TEST_INPUT);
+  assert!(plane.cursor_move_to(3, 10));
+  plane.insert_char('様');
+  eq_cursor(3, 12, plane);
+  eq(plane, &TEST_INPUT.replace("│ 顧客     │", "│ 顧客様   │"));
+  plane.delete_char_before();
+  eq_cursor(3, 10, plane);
+  plane.delete_char_before();
+  eq_cursor(3, 8, plane);
+  assert!(plane.cursor_move_to(7, 6));
+  plane.delete_char();
+  plane.insert_char('Z');
+  plane.insert_char('\u{0307}');
+  // combining overline has no precomposed character, so it is kept in a cluster
+  plane.insert_char('\u{0305}');
+  let expected = r#"
+┌──────────────────┐
+│ 割引 (discount)  │
+├───┬───────┬──────╥───────┐
+│ U │ 顧    │ 注文 ║ 割引  │
+╞═══╪═══════╪══════╬═══════╡
+│ 1 │ "企業"│ <10  ║ 0.10  │
+├───┼───────┼──────╫───────┤
+│ 2 │ ŻŁódź"│ >=10 ║ 0.15  │
+└───┴───────┴──────╨───────┘
+"#;
+  eq(plane, &expected.replace('Ż', "Ż\u{0305}"));
+}
+
+#[test]
+fn _0005() {
+  let plane = &mut Plane::new(TEST_INPUT);
+  let expected = r#"
+┌─────────────────┐
+│ 割引 (discount) │
+├───┬────────┬────┴─╥──────┐
+│ U │  顧客  │ 注文 ║ 割引 │
+╞═══╪════════╪══════╬══════╡
+│ 1 │ "企業" │ <10  ║ 0.10 │
+├───┼────────┼──────╫──────┤
+│ 2 │ "Łódź" │ >=10 ║ 0.15 │
+└───┴────────┴──────╨──────┘
+"#;
+  assert_eq!(expected.trim(), format(&recognize(plane).unwrap()).trim());
+}
+
+#[test]
+fn _0006() {
+  let plane = &mut Plane::new(TEST_INPUT);
+  assert!(plane.cursor_move_to(5, 7));
+  plane.begin_selection(SelectionMode::Characters);
+  plane.cursor_move_right();
+  assert_eq!(Some(Register::Text(vec!["企業".to_string()])), plane.cut());
+  eq_cursor(5, 7, plane);
+  let expected = r#"
+┌──────────────────┐
+│ 割引 (discount)  │
+├───┬───────┬──────╥───────┐
+│ U │ 顧客  │ 注文 ║ 割引  │
+╞═══╪═══════╪══════╬═══════╡
+│ 1 │ ""    │ <10  ║ 0.10  │
+├───┼───────┼──────╫───────┤
+│ 2 │ "Łódź"│ >=10 ║ 0.15  │
+└───┴───────┴──────╨───────┘
+"#;
+  eq(plane, expected);
+  // characters before the selection are kept when the selection ends at the cell border
+  assert!(plane.cursor_move_to(7, 10));
+  plane.begin_selection(SelectionMode::Characters);
+  plane.cursor_move_right();
+  assert_eq!(Some(Register::Text(vec!["ź\"".to_string()])), plane.cut());
+  let expected = r#"
+┌──────────────────┐
+│ 割引 (discount)  │
+├───┬─────┬──────╥─┴─────┐
+│ U │ 顧客│ 注文 ║ 割引  │
+╞═══╪═════╪══════╬═══════╡
+│ 1 │ ""  │ <10  ║ 0.10  │
+├───┼─────┼──────╫───────┤
+│ 2 │ "Łód│ >=10 ║ 0.15  │
+└───┴─────┴──────╨───────┘
+"#;
+  eq(plane, expected);
+}
+
+#[test]
+fn _0007() {
+  // zero-width characters and decomposed characters are saved unchanged
+  let input = "┌───┬────────╥───────┐\n│ U │ Name   ║ Value │\n╞═══╪════════╬═══════╡\n│ 1 │ \"e\u{301}\"    ║ x\u{200B}y    │\n└───┴────────╨───────┘\n";
+  assert_eq!(input, Document::new(input).to_string());
+  let plane = Plane::new(input);
+  assert_eq!(plane.chars[1].len(), plane.chars[3].len());
+  let decision_table = recognize(&plane).unwrap();
+  assert_eq!("\"e\u{301}\"", decision_table.rules[0].input_entries[0].text());
+  assert_eq!("x\u{200B}y", decision_table.rules[0].output_entries[0].text());
+  let expected = "┌───┬──────╥───────┐\n│ U │ Name ║ Value │\n╞═══╪══════╬═══════╡\n│ 1 │ \"e\u{301}\"  ║ x\u{200B}y    │\n└───┴──────╨───────┘";
+  assert_eq!(expected, format(&decision_table).trim());
+  // zero-width characters at the beginning are kept with the following character
+  let clusters = &mut Clusters::default();
+  assert_eq!(2, clusters.columns("\u{200D}\u{05B0}ab").len());
+  let columns = clusters.columns("\u{200D}\u{05B0}ab");
+  assert_eq!("\u{200D}\u{05B0}ab", clusters.text(&columns));
+  let columns = clusters.columns("\u{200B}");
+  assert_eq!("\u{200B}", clusters.text(&columns));
+  assert_eq!(vec!['企', CH_WIDE], clusters.columns("企"));
+  let columns = clusters.columns("企\u{20DD}");
+  assert_eq!("企\u{20DD}", clusters.text(&columns));
+  assert_eq!(2, columns.len());
+}
+
+#[test]
+fn _0008() {
+  assert_eq!("│ 1 │", clip_columns("│ 1 │ \"企業\"", 0, 5));
+  assert_eq!("\"企業\"", clip_columns("│ 1 │ \"企業\"", 6, 6));
+  assert_eq!(" 業\"  ", clip_columns("│ 1 │ \"企業\"", 8, 6));
+  assert_eq!("\"企 ", clip_columns("│ 1 │ \"企業\"", 6, 4));
+  assert_eq!("   ", clip_columns("ab", 5, 3));
+}
+
+#[test]
+fn _0009() {
+  // characters from the private use area are not mistaken for clusters
+  let input = "┌───┬──────╥───────┐\n│ U │ Name ║ Value │\n╞═══╪══════╬═══════╡\n│ 1 │ \u{F0000}a\u{301}   ║ \u{F0001}     │\n└───┴──────╨───────┘\n";
+  assert_eq!(input, Document::new(input).to_string());
+  let plane = Plane::new(input);
+  assert_eq!(plane.chars[1].len(), plane.chars[3].len());
+  let decision_table = recognize(&plane).unwrap();
+  assert_eq!("\u{F0000}a\u{301}", decision_table.rules[0].input_entries[0].text());
+  assert_eq!("\u{F0001}", decision_table.rules[0].output_entries[0].text());
+  // clusters of other planes are independent
+  let other = Plane::new("│ x\u{20DD} │");
+  assert_eq!("│ x\u{20DD} │", other.to_string());
+  assert_eq!(input.trim(), plane.to_string());
+}
+
+#[test]
+fn _0010() {
+  // combining marks without precomposed characters are kept in a cluster with the preceding character
+  let plane = &mut Plane::new(TEST_INPUT);
+  plane.cursor_move_to(5, 7);
+  for ch in "q\u{0301}企\u{20DD}\u{200B}".chars() {
+    plane.insert_char(ch);
+  }
+  let decision_table = recognize(plane).unwrap();
+  assert_eq!("\"q\u{0301}企\u{20DD}\u{200B}企業\"", decision_table.rules[0].input_entries[0].text());
+  assert_eq!(plane.chars[4].len(), plane.chars[5].len());
+  assert_eq!(
+    vec![Region {
+      top: 5,
+      left: 7,
+      bottom: 5,
+      right: 9
+    }],
+    plane.find("q\u{0301}企\u{20DD}\u{200B}", SearchScope::Table)
+  );
+  assert!(plane.find("x\u{0301}", SearchScope::Table).is_empty());
+  // a character typed from the private use area is kept unchanged
+  plane.insert_char('\u{F0000}');
+  assert_eq!(
+    "\"q\u{0301}企\u{20DD}\u{200B}\u{F0000}企業\"",
+    recognize(plane).unwrap().rules[0].input_entries[0].text()
+  );
+}
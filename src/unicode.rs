@@ -0,0 +1,314 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Display width of Unicode characters.
+//!
+//! Rows of the plane contain one character for each column of the terminal. A double-width
+//! character is followed by [CH_WIDE] filling its second column. Characters not occupying any column
+//! (combining marks, zero-width spaces) are kept together with the preceding character in a cluster,
+//! denoted by a single character from the private use area. Text of clusters is kept in [Clusters]
+//! owned by the plane, so the original text is restored unchanged.
+
+use std::collections::HashMap;
+
+/// Character filling the second column of a double-width character.
+pub const CH_WIDE: char = '\u{FDD0}';
+
+/// First character denoting a cluster (Supplementary Private Use Area-A).
+const CLUSTER_FIRST: u32 = 0xF0000;
+
+/// Last character denoting a cluster.
+const CLUSTER_LAST: u32 = 0xFFFFD;
+
+/// Replacement of characters denoting clusters, when they can not be placed in a cluster.
+const CH_REPLACEMENT: char = '\u{FFFD}';
+
+/// Ranges of characters displayed in two columns (East Asian wide and fullwidth characters).
+const WIDE: &[(char, char)] = &[
+  ('\u{1100}', '\u{115F}'),
+  ('\u{231A}', '\u{231B}'),
+  ('\u{2329}', '\u{232A}'),
+  ('\u{23E9}', '\u{23EC}'),
+  ('\u{25FD}', '\u{25FE}'),
+  ('\u{2614}', '\u{2615}'),
+  ('\u{26AA}', '\u{26AB}'),
+  ('\u{26BD}', '\u{26BE}'),
+  ('\u{2705}', '\u{2705}'),
+  ('\u{274C}', '\u{274C}'),
+  ('\u{2E80}', '\u{303E}'),
+  ('\u{3041}', '\u{33FF}'),
+  ('\u{3400}', '\u{4DBF}'),
+  ('\u{4E00}', '\u{9FFF}'),
+  ('\u{A000}', '\u{A4CF}'),
+  ('\u{A960}', '\u{A97F}'),
+  ('\u{AC00}', '\u{D7A3}'),
+  ('\u{F900}', '\u{FAFF}'),
+  ('\u{FE10}', '\u{FE19}'),
+  ('\u{FE30}', '\u{FE6F}'),
+  ('\u{FF00}', '\u{FF60}'),
+  ('\u{FFE0}', '\u{FFE6}'),
+  ('\u{16FE0}', '\u{18CFF}'),
+  ('\u{1B000}', '\u{1B2FF}'),
+  ('\u{1F004}', '\u{1F004}'),
+  ('\u{1F18E}', '\u{1F18E}'),
+  ('\u{1F191}', '\u{1F19A}'),
+  ('\u{1F200}', '\u{1F2FF}'),
+  ('\u{1F300}', '\u{1F64F}'),
+  ('\u{1F680}', '\u{1F6FF}'),
+  ('\u{1F900}', '\u{1F9FF}'),
+  ('\u{1FA70}', '\u{1FAFF}'),
+  ('\u{20000}', '\u{2FFFD}'),
+  ('\u{30000}', '\u{3FFFD}'),
+];
+
+/// Ranges of combining marks and other characters not occupying any column.
+const ZERO: &[(char, char)] = &[
+  ('\u{0300}', '\u{036F}'),
+  ('\u{0483}', '\u{0489}'),
+  ('\u{0591}', '\u{05BD}'),
+  ('\u{05BF}', '\u{05C7}'),
+  ('\u{0610}', '\u{061A}'),
+  ('\u{064B}', '\u{065F}'),
+  ('\u{0670}', '\u{0670}'),
+  ('\u{06D6}', '\u{06ED}'),
+  ('\u{0900}', '\u{0903}'),
+  ('\u{093A}', '\u{094F}'),
+  ('\u{0E31}', '\u{0E31}'),
+  ('\u{0E34}', '\u{0E3A}'),
+  ('\u{0E47}', '\u{0E4E}'),
+  ('\u{1AB0}', '\u{1AFF}'),
+  ('\u{1DC0}', '\u{1DFF}'),
+  ('\u{200B}', '\u{200F}'),
+  ('\u{2060}', '\u{2064}'),
+  ('\u{20D0}', '\u{20FF}'),
+  ('\u{302A}', '\u{302F}'),
+  ('\u{3099}', '\u{309A}'),
+  ('\u{FE00}', '\u{FE0F}'),
+  ('\u{FE20}', '\u{FE2F}'),
+  ('\u{FEFF}', '\u{FEFF}'),
+];
+
+/// Precomposed characters: combining mark, base characters and the corresponding composed characters.
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+  ('\u{0300}', "AEIOUaeiouNn", "ÀÈÌÒÙàèìòùǸǹ"),
+  ('\u{0301}', "AEIOUYaeiouyCcLlNnRrSsZzGg", "ÁÉÍÓÚÝáéíóúýĆćĹĺŃńŔŕŚśŹźǴǵ"),
+  ('\u{0302}', "AEIOUaeiouCcGgHhJjSsWwYy", "ÂÊÎÔÛâêîôûĈĉĜĝĤĥĴĵŜŝŴŵŶŷ"),
+  ('\u{0303}', "ANOanoIiUu", "ÃÑÕãñõĨĩŨũ"),
+  ('\u{0304}', "AaEeIiOoUuYy", "ĀāĒēĪīŌōŪūȲȳ"),
+  ('\u{0306}', "AaEeGgIiOoUu", "ĂăĔĕĞğĬĭŎŏŬŭ"),
+  ('\u{0307}', "CcEeGgIZzAaOo", "ĊċĖėĠġİŻżȦȧȮȯ"),
+  ('\u{0308}', "AEIOUaeiouyY", "ÄËÏÖÜäëïöüÿŸ"),
+  ('\u{030A}', "AaUu", "ÅåŮů"),
+  ('\u{030B}', "OoUu", "ŐőŰű"),
+  ('\u{030C}', "CcDdEeLlNnRrSsTtZzAaIiOoUuGgKkjHh", "ČčĎďĚěĽľŇňŘřŠšŤťŽžǍǎǏǐǑǒǓǔǦǧǨǩǰȞȟ"),
+  ('\u{0327}', "CcGgKkLlNnRrSsTtEe", "ÇçĢģĶķĻļŅņŖŗŞşŢţȨȩ"),
+  ('\u{0328}', "AaEeIiUuOo", "ĄąĘęĮįŲųǪǫ"),
+];
+
+/// Returns the number of columns occupied by the character in the terminal.
+pub fn char_width(ch: char) -> usize {
+  let contains = |ranges: &[(char, char)]| ranges.iter().any(|(first, last)| (*first..=*last).contains(&ch));
+  if contains(ZERO) {
+    0
+  } else if contains(WIDE) {
+    2
+  } else {
+    1
+  }
+}
+
+/// Returns the number of columns occupied by the text in the terminal.
+pub fn text_width(text: &str) -> usize {
+  groups(text).iter().map(|(_, width)| width).sum()
+}
+
+/// Returns the precomposed character for the base character followed by the combining mark.
+pub fn compose(base: char, mark: char) -> Option<char> {
+  COMPOSITIONS
+    .iter()
+    .find(|(m, _, _)| *m == mark)
+    .and_then(|(_, bases, composed)| bases.chars().position(|ch| ch == base).and_then(|index| composed.chars().nth(index)))
+}
+
+/// Splits text into groups of characters placed in the same column and returns them with their widths,
+/// characters not occupying any column join the preceding character (or the following one at the beginning of the text).
+fn groups(text: &str) -> Vec<(String, usize)> {
+  let mut groups: Vec<String> = vec![];
+  let mut leading = String::new();
+  for ch in text.chars() {
+    match groups.last_mut() {
+      Some(group) if char_width(ch) == 0 => group.push(ch),
+      None if char_width(ch) == 0 => leading.push(ch),
+      _ => groups.push(format!("{}{}", std::mem::take(&mut leading), ch)),
+    }
+  }
+  if !leading.is_empty() {
+    groups.push(leading);
+  }
+  groups
+    .into_iter()
+    .map(|group| {
+      let width = group.chars().map(char_width).max().unwrap_or(0).max(1);
+      (group, width)
+    })
+    .collect()
+}
+
+/// Returns the number of characters placed in columns, a double-width character or a cluster is counted once.
+pub fn column_char_count(columns: &[char]) -> usize {
+  columns.iter().filter(|ch| **ch != CH_WIDE).count()
+}
+
+/// Returns `true` when the character belongs to the range of characters denoting clusters.
+fn is_cluster(ch: char) -> bool {
+  (CLUSTER_FIRST..=CLUSTER_LAST).contains(&(ch as u32))
+}
+
+/// Text of clusters placed in columns of a single plane.
+#[derive(Debug, Clone, Default)]
+pub struct Clusters {
+  /// Text and width of clusters, the index of the cluster is its distance from [CLUSTER_FIRST].
+  clusters: Vec<(String, usize)>,
+  /// Index of the cluster having the specified text.
+  indexes: HashMap<String, usize>,
+}
+
+impl Clusters {
+  /// Returns the number of columns occupied by the character placed in a column.
+  pub fn char_width(&self, ch: char) -> usize {
+    self.cluster(ch).map_or_else(|| char_width(ch), |(_, width)| *width)
+  }
+
+  /// Converts text into columns, double-width characters are followed by [CH_WIDE], characters
+  /// not occupying any column are placed in a cluster with the preceding character (or the following one at the beginning of the text).
+  /// Characters from the range denoting clusters are placed in clusters too, so they are never mistaken for other clusters.
+  /// When all characters denoting clusters are used, characters of a new cluster are placed in separate columns.
+  pub fn columns(&mut self, text: &str) -> Vec<char> {
+    let mut columns = vec![];
+    for (group, width) in groups(text) {
+      let mut chars = group.chars();
+      match (chars.next(), chars.next()) {
+        (Some(ch), None) if !is_cluster(ch) => columns.push(ch),
+        _ => match self.new_cluster(&group, width) {
+          Some(ch) => columns.push(ch),
+          None => {
+            for ch in group.chars() {
+              columns.push(if is_cluster(ch) { CH_REPLACEMENT } else { ch });
+              if char_width(ch) == 2 {
+                columns.push(CH_WIDE);
+              }
+            }
+            continue;
+          }
+        },
+      }
+      if width == 2 {
+        columns.push(CH_WIDE);
+      }
+    }
+    columns
+  }
+
+  /// Converts text into columns using only existing clusters,
+  /// returns `None` when the text contains a cluster not placed in any column yet.
+  pub fn existing_columns(&self, text: &str) -> Option<Vec<char>> {
+    let mut columns = vec![];
+    for (group, width) in groups(text) {
+      let mut chars = group.chars();
+      match (chars.next(), chars.next()) {
+        (Some(ch), None) if !is_cluster(ch) => columns.push(ch),
+        _ => columns.push(self.cluster_char(*self.indexes.get(&group)?)),
+      }
+      if width == 2 {
+        columns.push(CH_WIDE);
+      }
+    }
+    Some(columns)
+  }
+
+  /// Converts columns back into text.
+  pub fn text(&self, columns: &[char]) -> String {
+    let mut text = String::new();
+    for ch in columns.iter().filter(|ch| **ch != CH_WIDE) {
+      match self.cluster(*ch) {
+        Some((cluster_text, _)) => text.push_str(cluster_text),
+        None => text.push(*ch),
+      }
+    }
+    text
+  }
+
+  /// Returns the text and the width of the cluster denoted by the character.
+  fn cluster(&self, ch: char) -> Option<&(String, usize)> {
+    if !is_cluster(ch) {
+      return None;
+    }
+    self.clusters.get((ch as u32 - CLUSTER_FIRST) as usize)
+  }
+
+  /// Returns the character denoting the cluster with the specified index.
+  fn cluster_char(&self, index: usize) -> char {
+    char::from_u32(CLUSTER_FIRST + index as u32).unwrap_or(CH_REPLACEMENT)
+  }
+
+  /// Returns the character denoting the cluster with the specified text, equal texts are denoted by the same character.
+  /// Returns `None` when all characters denoting clusters are used.
+  fn new_cluster(&mut self, text: &str, width: usize) -> Option<char> {
+    let index = match self.indexes.get(text) {
+      Some(index) => *index,
+      None if self.clusters.len() <= (CLUSTER_LAST - CLUSTER_FIRST) as usize => {
+        self.clusters.push((text.to_string(), width));
+        self.indexes.insert(text.to_string(), self.clusters.len() - 1);
+        self.clusters.len() - 1
+      }
+      None => return None,
+    };
+    Some(self.cluster_char(index))
+  }
+}
+
+/// Returns the part of the text displayed in the specified range of columns, padded with spaces to the width of the range.
+/// Double-width characters split by the range boundaries are replaced with spaces.
+pub fn clip_columns(text: &str, left: usize, width: usize) -> String {
+  let mut clusters = Clusters::default();
+  let mut columns: Vec<char> = clusters.columns(text).into_iter().skip(left).take(width).collect();
+  if columns.first() == Some(&CH_WIDE) {
+    columns[0] = ' ';
+  }
+  if let Some(last) = columns.last_mut() {
+    if clusters.char_width(*last) == 2 {
+      *last = ' ';
+    }
+  }
+  columns.resize(width, ' ');
+  clusters.text(&columns)
+}
@@ -0,0 +1,246 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Views of the terminal displaying buffers opened in the editor.
+
+use crate::document::Document;
+
+/// Position of the cursor in the document displayed in a view.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Cursor {
+  /// Index of the current decision table.
+  pub table: usize,
+  /// Row of the cursor in the plane of the current decision table.
+  pub row: usize,
+  /// Column of the cursor in the plane of the current decision table.
+  pub col: usize,
+}
+
+impl Cursor {
+  /// Returns the position of the cursor in the document.
+  pub fn of(document: &Document) -> Self {
+    Self {
+      table: document.current_table(),
+      row: document.plane().cursor_row(),
+      col: document.plane().cursor_col(),
+    }
+  }
+
+  /// Places the cursor in the document, the position is skipped when it is no longer allowed.
+  pub fn restore(&self, document: &mut Document) {
+    if document.select_table(self.table) {
+      document.plane_mut().cursor_move_to(self.row, self.col);
+    }
+  }
+}
+
+/// Part of the terminal displaying a buffer, the same buffer may be displayed in both views of the split screen.
+#[derive(Clone)]
+pub struct View {
+  /// Index of the displayed buffer.
+  pub buffer: usize,
+  /// Index of the first document row displayed in the view.
+  pub top_row: usize,
+  /// Index of the first document column displayed in the view.
+  pub left_col: usize,
+  /// Position of the cursor, saved when the view loses the focus.
+  pub cursor: Cursor,
+}
+
+impl View {
+  /// Creates a view displaying the buffer with the specified index from the beginning.
+  fn new(buffer: usize) -> Self {
+    Self {
+      buffer,
+      top_row: 0,
+      left_col: 0,
+      cursor: Cursor::default(),
+    }
+  }
+
+  /// Scrolls the view, so the specified document position is visible in the area, returns `true` when scrolled.
+  pub fn scroll_to(&mut self, row: usize, col: usize, area: &Area) -> bool {
+    let top_row = self.top_row.min(row).max((row + 1).saturating_sub(area.height));
+    let left_col = self.left_col.min(col).max((col + 1).saturating_sub(area.width));
+    let scrolled = top_row != self.top_row || left_col != self.left_col;
+    self.top_row = top_row;
+    self.left_col = left_col;
+    scrolled
+  }
+}
+
+/// Direction of splitting the terminal into two views.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Split {
+  /// Views are placed one above the other.
+  Horizontal,
+  /// Views are placed side by side.
+  Vertical,
+}
+
+/// Area of the terminal occupied by a view.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Area {
+  /// Screen row of the top edge.
+  pub top: usize,
+  /// Screen column of the left edge.
+  pub left: usize,
+  /// Number of rows.
+  pub height: usize,
+  /// Number of columns.
+  pub width: usize,
+}
+
+/// Views displaying buffers, there are two views when the terminal is split.
+pub struct Views {
+  /// Displayed views.
+  views: Vec<View>,
+  /// Index of the view having the focus, its buffer is edited.
+  current: usize,
+  /// Direction of splitting the terminal, `None` when there is a single view.
+  split: Option<Split>,
+}
+
+impl Views {
+  /// Creates a single view displaying the buffer with the specified index.
+  pub fn new(buffer: usize) -> Self {
+    Self {
+      views: vec![View::new(buffer)],
+      current: 0,
+      split: None,
+    }
+  }
+
+  /// Returns the number of views.
+  pub fn count(&self) -> usize {
+    self.views.len()
+  }
+
+  /// Returns the index of the view having the focus.
+  pub fn current(&self) -> usize {
+    self.current
+  }
+
+  /// Returns the direction of splitting the terminal.
+  pub fn split(&self) -> Option<Split> {
+    self.split
+  }
+
+  /// Returns the view with the specified index.
+  pub fn get(&self, index: usize) -> &View {
+    &self.views[index]
+  }
+
+  /// Returns the view having the focus.
+  pub fn view(&self) -> &View {
+    &self.views[self.current]
+  }
+
+  /// Returns the view having the focus for modification.
+  pub fn view_mut(&mut self) -> &mut View {
+    &mut self.views[self.current]
+  }
+
+  /// Returns the area occupied by the view with the specified index,
+  /// when views share the area with the specified size, views are separated with a line.
+  pub fn area(&self, index: usize, height: usize, width: usize) -> Area {
+    match (self.split, index) {
+      (Some(Split::Horizontal), 0) => Area {
+        top: 0,
+        left: 0,
+        height: (height / 2).saturating_sub(1).max(1),
+        width,
+      },
+      (Some(Split::Horizontal), _) => Area {
+        top: height / 2,
+        left: 0,
+        height: height - height / 2,
+        width,
+      },
+      (Some(Split::Vertical), 0) => Area {
+        top: 0,
+        left: 0,
+        height,
+        width: (width / 2).saturating_sub(1).max(1),
+      },
+      (Some(Split::Vertical), _) => Area {
+        top: 0,
+        left: width / 2,
+        height,
+        width: width - width / 2,
+      },
+      (None, _) => Area {
+        top: 0,
+        left: 0,
+        height,
+        width,
+      },
+    }
+  }
+
+  /// Displays the buffer with the specified index in the view having the focus,
+  /// returns `false` when the buffer is already displayed.
+  pub fn select_buffer(&mut self, buffer: usize) -> bool {
+    if buffer == self.view().buffer {
+      return false;
+    }
+    *self.view_mut() = View::new(buffer);
+    true
+  }
+
+  /// Splits the terminal in the specified direction, the new view displays the same buffer at the same cursor position.
+  /// Splitting again in the same direction closes the view not having the focus.
+  pub fn split_in(&mut self, split: Split, cursor: Cursor) {
+    self.view_mut().cursor = cursor;
+    if self.split == Some(split) {
+      self.views = vec![self.view().clone()];
+      self.current = 0;
+      self.split = None;
+    } else {
+      if self.views.len() == 1 {
+        self.views.push(self.view().clone());
+      }
+      self.split = Some(split);
+    }
+  }
+
+  /// Moves the focus to the other view, the cursor position of the view losing the focus is saved.
+  /// Returns the saved cursor position of the view receiving the focus, `None` when there is a single view.
+  pub fn switch(&mut self, cursor: Cursor) -> Option<Cursor> {
+    if self.views.len() < 2 {
+      return None;
+    }
+    self.view_mut().cursor = cursor;
+    self.current = (self.current + 1) % self.views.len();
+    Some(self.view().cursor)
+  }
+}